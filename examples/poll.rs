@@ -1,13 +1,10 @@
 use dotenv::dotenv;
-use env_logger;
-use futures::Future;
-use log;
-use std::env;
+use std::{env, future::Future, pin::Pin};
 use tgbot::{
     handle_updates,
     methods::SendMessage,
     types::{Update, UpdateKind},
-    Api, UpdateHandler, UpdateMethod,
+    Api, UpdateHandler, UpdateMethod, UpdatesStream,
 };
 
 struct Handler {
@@ -15,27 +12,41 @@ struct Handler {
 }
 
 impl UpdateHandler for Handler {
-    fn handle(&mut self, update: Update) {
-        log::info!("got an update: {:?}\n", update);
-        if let UpdateKind::Message(message) = update.kind {
-            if let Some(text) = message.get_text() {
-                let chat_id = message.get_chat_id();
-                let method = SendMessage::new(chat_id, text.data.clone());
-                self.api.spawn(self.api.execute(&method).then(|x| {
-                    log::info!("sendMessage result: {:?}\n", x);
-                    Ok::<(), ()>(())
-                }));
+    type Future = Pin<Box<dyn Future<Output = ()> + Send>>;
+
+    fn handle(&mut self, update: Update) -> Self::Future {
+        let api = self.api.clone();
+        Box::pin(async move {
+            log::info!("got an update: {:?}\n", update);
+            if let UpdateKind::Message(message) = update.kind {
+                if let Some(text) = message.get_text() {
+                    let chat_id = message.get_chat_id();
+                    let method = SendMessage::new(chat_id, text.data.clone());
+                    log::info!("sendMessage result: {:?}\n", api.execute(&method).await);
+                }
             }
-        }
+        })
     }
 }
 
-fn main() {
+#[tokio::main]
+async fn main() {
     dotenv().ok();
     env_logger::init();
 
     let token = env::var("TGBOT_TOKEN").expect("TGBOT_TOKEN is not set");
-    let proxy = env::var("TGBOT_PROXY").ok();
-    let api = Api::new(token, proxy).expect("Failed to create API");
-    handle_updates(UpdateMethod::poll(api.clone()), Handler { api });
+    let mut builder = Api::builder(token);
+    if let Ok(proxy) = env::var("TGBOT_PROXY") {
+        builder = builder.proxy(proxy);
+    }
+    let api = builder.build().expect("Failed to create API");
+    let stream = UpdatesStream::new(api.clone());
+    let shutdown = stream.shutdown_handle();
+    tokio::spawn(async move {
+        if tokio::signal::ctrl_c().await.is_ok() {
+            log::info!("shutting down");
+            shutdown.shutdown();
+        }
+    });
+    handle_updates(UpdateMethod::poll(stream), Handler { api }).await;
 }
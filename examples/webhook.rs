@@ -1,18 +1,15 @@
 use dotenv::dotenv;
-use env_logger;
-use log;
-use tgbot::{handle_updates, types::Update, UpdateHandler, UpdateMethod};
+use tgbot::{handle_updates, UpdateMethod};
 
-struct Handler;
-
-impl UpdateHandler for Handler {
-    fn handle(&mut self, update: Update) {
-        log::info!("got an update: {:?}\n", update);
-    }
-}
-
-fn main() {
+#[tokio::main]
+async fn main() {
     dotenv().ok();
     env_logger::init();
-    handle_updates(UpdateMethod::webhook(([127, 0, 0, 1], 8080), "/"), Handler);
+    handle_updates(
+        UpdateMethod::webhook(([127, 0, 0, 1], 8080), "/"),
+        |update| async move {
+            log::info!("got an update: {:?}\n", update);
+        },
+    )
+    .await;
 }
@@ -0,0 +1,218 @@
+use crate::{
+    executor::{Executor, Response, StreamResponse},
+    methods::{FormValue, Request, RequestBody},
+    types::Integer,
+};
+use bytes::Bytes;
+use failure::Error;
+use futures::stream;
+use serde::Serialize;
+use serde_json::{json, Map as JsonMap, Value as JsonValue};
+use std::{
+    collections::{HashMap, VecDeque},
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Mutex},
+};
+
+/// Executor which returns canned responses instead of sending requests
+///
+/// Use it to test bot logic without network access
+/// Responses are keyed by method name and returned in the order they were added,
+/// the last response of a method is repeated for subsequent calls
+/// A method without a response fails with a transport error
+///
+/// Clones share responses and received requests,
+/// so keep a clone to inspect requests after passing the executor to ApiBuilder::executor()
+#[derive(Clone, Default)]
+pub struct MockExecutor {
+    state: Arc<Mutex<MockState>>,
+}
+
+#[derive(Default)]
+struct MockState {
+    responses: HashMap<String, VecDeque<Response>>,
+    files: HashMap<String, Bytes>,
+    requests: Vec<Request>,
+}
+
+impl MockExecutor {
+    /// Creates an executor without responses
+    pub fn new() -> Self {
+        MockExecutor::default()
+    }
+
+    /// Adds a successful response of a method
+    ///
+    /// # Arguments
+    ///
+    /// * method - Name of a method, e.g. "sendMessage"
+    /// * result - Value of result field
+    pub fn with_result<N: Into<String>, T: Serialize>(self, method: N, result: T) -> Self {
+        let body = json!({"ok": true, "result": result});
+        self.with_response(method, 200, body.to_string())
+    }
+
+    /// Adds an error response of a method
+    ///
+    /// # Arguments
+    ///
+    /// * method - Name of a method, e.g. "sendMessage"
+    /// * error_code - Error code, e.g. 403
+    /// * description - Human-readable description
+    pub fn with_error<N: Into<String>, D: Into<String>>(self, method: N, error_code: Integer, description: D) -> Self {
+        let body = json!({"ok": false, "error_code": error_code, "description": description.into()});
+        self.with_response(method, error_code as u16, body.to_string())
+    }
+
+    /// Adds a raw HTTP response of a method
+    pub fn with_response<N: Into<String>, B: Into<Vec<u8>>>(self, method: N, status: u16, body: B) -> Self {
+        self.state
+            .lock()
+            .unwrap()
+            .responses
+            .entry(method.into())
+            .or_default()
+            .push_back(Response {
+                status,
+                body: body.into(),
+            });
+        self
+    }
+
+    /// Adds contents of a file returned by Api::download_file()
+    ///
+    /// # Arguments
+    ///
+    /// * file_path - Path of a file as returned by getFile
+    /// * data - Contents of the file
+    pub fn with_file<P: Into<String>, D: Into<Bytes>>(self, file_path: P, data: D) -> Self {
+        self.state.lock().unwrap().files.insert(file_path.into(), data.into());
+        self
+    }
+
+    /// Returns all received requests
+    pub fn requests(&self) -> Vec<Request> {
+        self.state.lock().unwrap().requests.clone()
+    }
+
+    /// Returns parameters of received requests of a method
+    ///
+    /// Parameters of a multipart request are returned as strings, files are omitted
+    pub fn params(&self, method: &str) -> Vec<JsonValue> {
+        self.state
+            .lock()
+            .unwrap()
+            .requests
+            .iter()
+            .filter(|request| method_name(&request.url) == method)
+            .map(request_params)
+            .collect()
+    }
+}
+
+impl Executor for MockExecutor {
+    fn execute(&self, req: Request) -> Pin<Box<dyn Future<Output = Result<Response, Error>> + Send>> {
+        let mut state = self.state.lock().unwrap();
+        let method = method_name(&req.url).to_string();
+        state.requests.push(req);
+        let response = state.responses.get_mut(&method).and_then(|responses| {
+            if responses.len() > 1 {
+                responses.pop_front()
+            } else {
+                responses.front().cloned()
+            }
+        });
+        let result = response.ok_or_else(|| failure::format_err!("No response for {}", method));
+        Box::pin(async move { result })
+    }
+
+    fn download(&self, url: String) -> Pin<Box<dyn Future<Output = Result<StreamResponse, Error>> + Send>> {
+        let data = url
+            .find("/file/bot")
+            .and_then(|start| url[start + 9..].split_once('/'))
+            .and_then(|(_, file_path)| self.state.lock().unwrap().files.get(file_path).cloned());
+        let response = match data {
+            Some(data) => StreamResponse {
+                status: 200,
+                body: Box::pin(stream::once(async move { Ok(data) })),
+            },
+            None => StreamResponse {
+                status: 404,
+                body: Box::pin(stream::empty()),
+            },
+        };
+        Box::pin(async move { Ok(response) })
+    }
+}
+
+/// Returns name of a method from request URL
+pub(super) fn method_name(url: &str) -> &str {
+    url.rsplit('/').next().unwrap_or_default()
+}
+
+/// Returns parameters of a request as JSON
+///
+/// Parameters of a multipart request are returned as strings, files are omitted
+pub(super) fn request_params(request: &Request) -> JsonValue {
+    match request.body {
+        RequestBody::Json(ref data) => serde_json::from_slice(data).unwrap_or(JsonValue::Null),
+        RequestBody::Multipart(ref form) => form
+            .fields()
+            .iter()
+            .filter_map(|(name, value)| match value {
+                FormValue::Text(value) => Some((name.clone(), JsonValue::from(value.as_str()))),
+                FormValue::File(_) => None,
+            })
+            .collect::<JsonMap<String, JsonValue>>()
+            .into(),
+        RequestBody::Empty => JsonValue::Null,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        handler::fixtures::private_message,
+        methods::{GetMe, SendMessage},
+        types::ChatAction,
+        Api, ApiError,
+    };
+    use futures::TryStreamExt;
+
+    #[tokio::test]
+    async fn test_mock_executor() {
+        let executor = MockExecutor::new()
+            .with_result("getMe", json!({"id": 1, "is_bot": true, "first_name": "bot"}))
+            .with_error("sendMessage", 403, "Forbidden: bot was blocked by the user")
+            .with_result("sendMessage", private_message(1, "hi"))
+            .with_file("photos/1.jpg", "photo");
+        let api = Api::builder("token").executor(executor.clone()).build().unwrap();
+
+        assert_eq!(api.execute(&GetMe).await.unwrap().id, 1);
+        assert_eq!(api.execute(&GetMe).await.unwrap().id, 1);
+        let err = api.execute(&SendMessage::new(1, "hi")).await.unwrap_err();
+        assert_eq!(err.error_code(), Some(403));
+        let message = api.execute(&SendMessage::new(1, "hi")).await.unwrap();
+        assert_eq!(message.id, 1);
+        assert!(matches!(
+            api.send_action(1, ChatAction::Typing).await,
+            Err(ApiError::Transport(_))
+        ));
+
+        assert_eq!(executor.requests().len(), 5);
+        let params = executor.params("sendMessage");
+        assert_eq!(params.len(), 2);
+        assert_eq!(params[0]["chat_id"], 1);
+        assert_eq!(params[0]["text"], "hi");
+
+        let data: Vec<Bytes> = api.download_file("photos/1.jpg").try_collect().await.unwrap();
+        assert_eq!(data.concat(), b"photo");
+        assert!(api
+            .download_file("photos/2.jpg")
+            .try_collect::<Vec<Bytes>>()
+            .await
+            .is_err());
+    }
+}
@@ -1,11 +1,57 @@
-use crate::methods::Request;
+pub use crate::methods::{Form, FormValue, Request, RequestBody, RequestMethod};
+use bytes::Bytes;
 use failure::Error;
-use futures::Future;
+use futures::Stream;
+use std::{future::Future, pin::Pin};
 
 mod hyper;
+mod mock;
+mod multipart;
+mod record;
+mod socks;
 
-pub(crate) use self::hyper::{default_executor, proxy_executor};
+pub use self::{
+    hyper::{default_executor, proxy_executor, proxy_executor_with_auth, ProxyAuthError},
+    mock::MockExecutor,
+    multipart::{encode_form, encode_form_stream, generate_boundary},
+    record::{Interaction, RecordingExecutor, ReplayExecutor},
+};
 
-pub(crate) trait Executor: Send + Sync {
-    fn execute(&self, req: Request) -> Box<Future<Item = Vec<u8>, Error = Error> + Send>;
+pub(crate) use self::{
+    hyper::{create_default_executor, create_proxy_executor, proxy_url_with_auth, HyperOptions},
+    multipart::read_path,
+};
+
+/// Sends HTTP requests to Bot API
+///
+/// Implement it to use another HTTP client or to test methods without network access,
+/// then pass it to ApiBuilder::executor()
+/// MockExecutor is available for tests which only need canned responses
+pub trait Executor: Send + Sync {
+    /// Sends a request and receives the whole response body
+    ///
+    /// A response must be returned for any HTTP status, errors are for transport failures only
+    fn execute(&self, req: Request) -> Pin<Box<dyn Future<Output = Result<Response, Error>> + Send>>;
+
+    /// Sends a GET request to the given URL and returns a stream of response body
+    ///
+    /// Used to download files
+    fn download(&self, url: String) -> Pin<Box<dyn Future<Output = Result<StreamResponse, Error>> + Send>>;
+}
+
+/// HTTP response returned by executor
+#[derive(Clone, Debug)]
+pub struct Response {
+    /// HTTP status code
+    pub status: u16,
+    /// Response body
+    pub body: Vec<u8>,
+}
+
+/// HTTP response with a body which is not received yet
+pub struct StreamResponse {
+    /// HTTP status code
+    pub status: u16,
+    /// Stream of body chunks
+    pub body: Pin<Box<dyn Stream<Item = Result<Bytes, Error>> + Send>>,
 }
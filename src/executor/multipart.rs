@@ -0,0 +1,203 @@
+use crate::{
+    methods::{Form, FormValue},
+    types::{InputFileKind, InputFileStream},
+};
+use bytes::Bytes;
+use futures::{
+    future::ready,
+    stream::{self, BoxStream},
+    Stream, StreamExt, TryStreamExt,
+};
+use std::{
+    io::{Error as IoError, Result as IoResult},
+    path::PathBuf,
+    sync::atomic::{AtomicUsize, Ordering},
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tokio::{fs::File, io::AsyncReadExt};
+
+const CHUNK_SIZE: usize = 64 * 1024;
+
+static BOUNDARY_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// Generates a boundary for multipart/form-data body
+pub fn generate_boundary() -> String {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.subsec_nanos())
+        .unwrap_or_default();
+    let counter = BOUNDARY_COUNTER.fetch_add(1, Ordering::Relaxed);
+    format!("------------------------tgbot{:08x}{:08x}", nanos, counter)
+}
+
+/// Encodes a form as multipart/form-data
+///
+/// Files are read here, so they are opened only when a request is sent
+/// Use it in a custom executor, the boundary must be specified in Content-Type header:
+/// `multipart/form-data; boundary=<boundary>`
+///
+/// The whole body is kept in memory, use encode_form_stream() to avoid it
+pub async fn encode_form(form: Form, boundary: &str) -> IoResult<Vec<u8>> {
+    encode_form_stream(form, boundary)
+        .try_fold(Vec::new(), |mut body, chunk| {
+            body.extend_from_slice(&chunk);
+            ready(Ok(body))
+        })
+        .await
+}
+
+/// Encodes a form as a stream of multipart/form-data chunks
+///
+/// Local files and readers are read chunk by chunk while the stream is polled
+/// The boundary must be specified in Content-Type header: `multipart/form-data; boundary=<boundary>`
+pub fn encode_form_stream(form: Form, boundary: &str) -> impl Stream<Item = IoResult<Bytes>> + Send + 'static {
+    let end = Bytes::from(format!("--{}--\r\n", boundary));
+    let boundary = boundary.to_string();
+    stream::iter(form.fields)
+        .map(move |(name, value)| encode_field(&boundary, &name, value))
+        .flatten()
+        .chain(stream::once(ready(Ok(end))))
+}
+
+fn encode_field(boundary: &str, name: &str, value: FormValue) -> BoxStream<'static, IoResult<Bytes>> {
+    let text_header = || {
+        Bytes::from(format!(
+            "--{}\r\nContent-Disposition: form-data; name=\"{}\"\r\n\r\n",
+            boundary,
+            escape(name)
+        ))
+    };
+    let file_header = |file_name: &str, mime_type: &str| {
+        Bytes::from(format!(
+            "--{}\r\nContent-Disposition: form-data; name=\"{}\"; filename=\"{}\"\r\nContent-Type: {}\r\n\r\n",
+            boundary,
+            escape(name),
+            escape(file_name),
+            mime_type
+        ))
+    };
+    let (header, content) = match value {
+        FormValue::Text(text) => (text_header(), stream::once(ready(Ok(Bytes::from(text)))).boxed()),
+        FormValue::File(file) => match file.kind {
+            InputFileKind::Id(value) | InputFileKind::Url(value) => {
+                (text_header(), stream::once(ready(Ok(Bytes::from(value)))).boxed())
+            }
+            InputFileKind::Path(file) => (file_header(&file.file_name, file.mime_type), read_path(file.path)),
+            InputFileKind::Bytes(file) => (
+                file_header(&file.file_name, file.mime_type),
+                stream::once(ready(Ok(file.data))).boxed(),
+            ),
+            InputFileKind::Reader(file) => {
+                let content = match file.take() {
+                    Some(reader) => read_chunks(reader),
+                    None => stream::once(ready(Err(IoError::other(format!(
+                        "reader of {} has already been consumed",
+                        file.file_name
+                    )))))
+                    .boxed(),
+                };
+                (file_header(&file.file_name, file.mime_type), content)
+            }
+        },
+    };
+    stream::once(ready(Ok(header)))
+        .chain(content)
+        .chain(stream::once(ready(Ok(Bytes::from_static(b"\r\n")))))
+        .boxed()
+}
+
+/// Reads a file from the disk by chunks
+pub(crate) fn read_path(path: PathBuf) -> BoxStream<'static, IoResult<Bytes>> {
+    stream::once(async move { File::open(path).await })
+        .map_ok(|file| read_chunks(Box::pin(file)))
+        .try_flatten()
+        .boxed()
+}
+
+fn read_chunks(reader: InputFileStream) -> BoxStream<'static, IoResult<Bytes>> {
+    stream::unfold(Some(reader), |reader| async move {
+        let mut reader = reader?;
+        let mut buf = vec![0; CHUNK_SIZE];
+        match reader.read(&mut buf).await {
+            Ok(0) => None,
+            Ok(len) => {
+                buf.truncate(len);
+                Some((Ok(Bytes::from(buf)), Some(reader)))
+            }
+            Err(err) => Some((Err(err), None)),
+        }
+    })
+    .boxed()
+}
+
+fn escape(value: &str) -> String {
+    value.replace('"', "%22").replace('\r', "%0D").replace('\n', "%0A")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::InputFile;
+    use std::env;
+    use tokio::fs;
+
+    #[tokio::test]
+    async fn test_encode_form() {
+        let path = env::temp_dir().join("tgbot-multipart-test.txt");
+        fs::write(&path, b"file content").await.unwrap();
+        let mut form = Form::default();
+        form.insert_field("chat_id", String::from("1"));
+        form.insert_field("document", InputFile::path(&path).unwrap());
+        form.insert_field("thumb", InputFile::bytes("thumb.jpg", &b"thumb"[..]));
+        let body = encode_form(form, "boundary").await.unwrap();
+        fs::remove_file(&path).await.unwrap();
+        assert_eq!(
+            String::from_utf8(body).unwrap(),
+            "--boundary\r\n\
+             Content-Disposition: form-data; name=\"chat_id\"\r\n\r\n\
+             1\r\n\
+             --boundary\r\n\
+             Content-Disposition: form-data; name=\"document\"; filename=\"tgbot-multipart-test.txt\"\r\n\
+             Content-Type: text/plain\r\n\r\n\
+             file content\r\n\
+             --boundary\r\n\
+             Content-Disposition: form-data; name=\"thumb\"; filename=\"thumb.jpg\"\r\n\
+             Content-Type: image/jpeg\r\n\r\n\
+             thumb\r\n\
+             --boundary--\r\n"
+        );
+        assert_ne!(generate_boundary(), generate_boundary());
+    }
+
+    #[tokio::test]
+    async fn test_encode_form_stream() {
+        let content = vec![b'a'; CHUNK_SIZE + 1];
+        let file = InputFile::reader("video.mp4", std::io::Cursor::new(content.clone()));
+        let mut form = Form::default();
+        form.insert_field("video", file.clone());
+        let chunks: Vec<Bytes> = encode_form_stream(form, "boundary").try_collect().await.unwrap();
+        assert_eq!(chunks.len(), 5);
+        assert_eq!(
+            chunks[0],
+            "--boundary\r\n\
+             Content-Disposition: form-data; name=\"video\"; filename=\"video.mp4\"\r\n\
+             Content-Type: video/mp4\r\n\r\n"
+        );
+        assert_eq!(chunks[1].len(), CHUNK_SIZE);
+        assert_eq!(chunks[2].len(), 1);
+        assert_eq!(chunks[3], "\r\n");
+        assert_eq!(chunks[4], "--boundary--\r\n");
+
+        let mut form = Form::default();
+        form.insert_field("video", file);
+        let err = encode_form(form, "boundary").await.unwrap_err();
+        assert_eq!(err.to_string(), "reader of video.mp4 has already been consumed");
+
+        let mut form = Form::default();
+        let path = env::temp_dir().join("tgbot-multipart-missing.txt");
+        fs::write(&path, b"").await.unwrap();
+        form.insert_field("document", InputFile::path(&path).unwrap());
+        fs::remove_file(&path).await.unwrap();
+        assert!(encode_form(form, "boundary").await.is_err());
+    }
+}
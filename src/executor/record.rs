@@ -0,0 +1,204 @@
+use crate::{
+    executor::{
+        mock::{method_name, request_params},
+        Executor, Response, StreamResponse,
+    },
+    methods::Request,
+};
+use failure::Error;
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use std::{
+    collections::VecDeque,
+    fs,
+    future::Future,
+    path::{Path, PathBuf},
+    pin::Pin,
+    sync::{Arc, Mutex},
+};
+use tokio::{fs::OpenOptions, io::AsyncWriteExt};
+
+/// A request and its response stored by RecordingExecutor
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct Interaction {
+    /// Name of the method
+    pub method: String,
+    /// Parameters of the request, files are omitted
+    pub params: JsonValue,
+    /// HTTP status code of the response
+    pub status: u16,
+    /// Response body
+    pub body: String,
+}
+
+/// Executor which writes requests and responses of another executor to a file
+///
+/// Each interaction is appended to the file as a line of JSON, see ReplayExecutor
+/// Bot token is not written, file downloads are passed through without being recorded
+pub struct RecordingExecutor<E> {
+    inner: E,
+    path: Arc<PathBuf>,
+    lock: Arc<tokio::sync::Mutex<()>>,
+}
+
+impl<E: Executor> RecordingExecutor<E> {
+    /// Creates a new RecordingExecutor
+    ///
+    /// # Arguments
+    ///
+    /// * inner - Executor which sends requests, e.g. default_executor()
+    /// * path - File to append interactions to, it is created when it does not exist
+    pub fn new<P: Into<PathBuf>>(inner: E, path: P) -> Self {
+        RecordingExecutor {
+            inner,
+            path: Arc::new(path.into()),
+            lock: Arc::new(tokio::sync::Mutex::new(())),
+        }
+    }
+}
+
+impl<E: Executor> Executor for RecordingExecutor<E> {
+    fn execute(&self, req: Request) -> Pin<Box<dyn Future<Output = Result<Response, Error>> + Send>> {
+        let method = method_name(&req.url).to_string();
+        let params = request_params(&req);
+        let response = self.inner.execute(req);
+        let path = self.path.clone();
+        let lock = self.lock.clone();
+        Box::pin(async move {
+            let response = response.await?;
+            let interaction = Interaction {
+                method,
+                params,
+                status: response.status,
+                body: String::from_utf8_lossy(&response.body).into_owned(),
+            };
+            let mut line = serde_json::to_vec(&interaction)?;
+            line.push(b'\n');
+            let _guard = lock.lock().await;
+            let mut file = OpenOptions::new().create(true).append(true).open(path.as_ref()).await?;
+            file.write_all(&line).await?;
+            file.flush().await?;
+            Ok(response)
+        })
+    }
+
+    fn download(&self, url: String) -> Pin<Box<dyn Future<Output = Result<StreamResponse, Error>> + Send>> {
+        self.inner.download(url)
+    }
+}
+
+/// Executor which returns responses written by RecordingExecutor
+///
+/// Interactions are replayed in the recorded order,
+/// a request fails when its method does not match the next interaction or there are no interactions left
+/// Parameters are not compared, use MockExecutor or Inspector to check them
+/// File downloads always fail
+#[derive(Clone)]
+pub struct ReplayExecutor {
+    interactions: Arc<Mutex<VecDeque<Interaction>>>,
+}
+
+impl ReplayExecutor {
+    /// Creates an executor from recorded interactions
+    pub fn new<I: IntoIterator<Item = Interaction>>(interactions: I) -> Self {
+        ReplayExecutor {
+            interactions: Arc::new(Mutex::new(interactions.into_iter().collect())),
+        }
+    }
+
+    /// Loads interactions from a file written by RecordingExecutor
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        let data = fs::read_to_string(path)?;
+        let interactions = data
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(serde_json::from_str)
+            .collect::<Result<Vec<Interaction>, _>>()?;
+        Ok(ReplayExecutor::new(interactions))
+    }
+
+    /// Returns the number of interactions which are not replayed yet
+    pub fn remaining(&self) -> usize {
+        self.interactions.lock().unwrap().len()
+    }
+}
+
+impl Executor for ReplayExecutor {
+    fn execute(&self, req: Request) -> Pin<Box<dyn Future<Output = Result<Response, Error>> + Send>> {
+        let method = method_name(&req.url);
+        let mut interactions = self.interactions.lock().unwrap();
+        let result = match interactions.front() {
+            Some(interaction) if interaction.method == method => {
+                let interaction = interactions.pop_front().unwrap();
+                Ok(Response {
+                    status: interaction.status,
+                    body: interaction.body.into_bytes(),
+                })
+            }
+            Some(interaction) => Err(failure::format_err!(
+                "Unexpected request: {}, {} is recorded",
+                method,
+                interaction.method
+            )),
+            None => Err(failure::format_err!(
+                "Unexpected request: {}, nothing is recorded",
+                method
+            )),
+        };
+        Box::pin(async move { result })
+    }
+
+    fn download(&self, _url: String) -> Pin<Box<dyn Future<Output = Result<StreamResponse, Error>> + Send>> {
+        Box::pin(async move { Err(failure::format_err!("File downloads can not be replayed")) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        executor::MockExecutor,
+        methods::{GetMe, SendMessage},
+        Api,
+    };
+    use serde_json::json;
+    use std::env;
+
+    #[tokio::test]
+    async fn test_record_and_replay() {
+        let path = env::temp_dir().join("tgbot-test-record-and-replay.jsonl");
+        let _ = fs::remove_file(&path);
+        let mock = MockExecutor::new()
+            .with_result("getMe", json!({"id": 1, "is_bot": true, "first_name": "bot"}))
+            .with_error("sendMessage", 403, "Forbidden");
+        let api = Api::builder("123:secret")
+            .executor(RecordingExecutor::new(mock, &path))
+            .build()
+            .unwrap();
+        assert_eq!(api.execute(&GetMe).await.unwrap().id, 1);
+        assert!(api.execute(&SendMessage::new(1, "hi")).await.is_err());
+
+        let recorded = fs::read_to_string(&path).unwrap();
+        assert!(!recorded.contains("secret"));
+        let replay = ReplayExecutor::from_file(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(replay.remaining(), 2);
+        {
+            let interactions = replay.interactions.lock().unwrap();
+            assert_eq!(interactions[1].method, "sendMessage");
+            assert_eq!(interactions[1].params, json!({"chat_id": 1, "text": "hi"}));
+            assert_eq!(interactions[1].status, 403);
+        }
+
+        let api = Api::builder("token").executor(replay.clone()).build().unwrap();
+        assert!(api.execute(&SendMessage::new(1, "hi")).await.is_err());
+        assert_eq!(replay.remaining(), 2);
+        assert_eq!(api.execute(&GetMe).await.unwrap().id, 1);
+        assert_eq!(
+            api.execute(&SendMessage::new(1, "hi")).await.unwrap_err().error_code(),
+            Some(403)
+        );
+        assert_eq!(replay.remaining(), 0);
+        assert!(api.execute(&GetMe).await.is_err());
+    }
+}
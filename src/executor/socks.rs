@@ -0,0 +1,151 @@
+use hyper::{service::Service, Uri};
+use std::{
+    error::Error as StdError,
+    fmt,
+    future::Future,
+    io::{Error as IoError, ErrorKind as IoErrorKind},
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+use tokio::{net::TcpStream, time::timeout};
+use tokio_socks::{
+    tcp::{Socks4Stream, Socks5Stream},
+    Error as SocksError,
+};
+
+/// SOCKS proxy server
+#[derive(Clone, Debug)]
+pub(super) enum SocksProxy {
+    Socks4 { addr: String, user_id: String },
+    Socks5 { addr: String, auth: Option<SocksAuth> },
+}
+
+/// Credentials for a SOCKS5 proxy
+#[derive(Clone, Debug)]
+pub(super) struct SocksAuth {
+    pub(super) username: String,
+    pub(super) password: String,
+}
+
+/// Connects to a destination through a SOCKS proxy
+///
+/// TLS is not handled here, wrap it into HttpsConnector
+#[derive(Clone, Debug)]
+pub(super) struct SocksConnector {
+    proxy: SocksProxy,
+    connect_timeout: Option<Duration>,
+}
+
+impl SocksConnector {
+    /// Creates a connector
+    ///
+    /// Connect timeout includes connecting to the proxy and the SOCKS handshake
+    pub(super) fn new(proxy: SocksProxy, connect_timeout: Option<Duration>) -> Self {
+        SocksConnector { proxy, connect_timeout }
+    }
+}
+
+impl Service<Uri> for SocksConnector {
+    type Response = TcpStream;
+    type Error = IoError;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, dst: Uri) -> Self::Future {
+        let proxy = self.proxy.clone();
+        let connect_timeout = self.connect_timeout;
+        Box::pin(async move {
+            let host = match dst.host() {
+                Some(host) => host.to_string(),
+                None => return Err(IoError::new(IoErrorKind::InvalidInput, "destination host is missing")),
+            };
+            let port = match dst.port_u16() {
+                Some(port) => port,
+                None if dst.scheme_str() == Some("https") => 443,
+                None => 80,
+            };
+            let target = (host.as_str(), port);
+            let connect = async {
+                match proxy {
+                    SocksProxy::Socks4 { ref addr, ref user_id } if user_id.is_empty() => {
+                        Socks4Stream::connect(addr.as_str(), target)
+                            .await
+                            .map(Socks4Stream::into_inner)
+                    }
+                    SocksProxy::Socks4 { ref addr, ref user_id } => {
+                        Socks4Stream::connect_with_userid(addr.as_str(), target, user_id)
+                            .await
+                            .map(Socks4Stream::into_inner)
+                    }
+                    SocksProxy::Socks5 { ref addr, auth: None } => Socks5Stream::connect(addr.as_str(), target)
+                        .await
+                        .map(Socks5Stream::into_inner),
+                    SocksProxy::Socks5 {
+                        ref addr,
+                        auth: Some(ref auth),
+                    } => Socks5Stream::connect_with_password(addr.as_str(), target, &auth.username, &auth.password)
+                        .await
+                        .map(Socks5Stream::into_inner),
+                }
+            };
+            let result = match connect_timeout {
+                Some(connect_timeout) => timeout(connect_timeout, connect)
+                    .await
+                    .map_err(|_| IoError::new(IoErrorKind::TimedOut, "connection to the proxy timed out"))?,
+                None => connect.await,
+            };
+            result.map_err(|err| match err {
+                SocksError::PasswordAuthFailure(_)
+                | SocksError::AuthorizationRequired
+                | SocksError::NoAcceptableAuthMethods => {
+                    IoError::new(IoErrorKind::PermissionDenied, ProxyAuthFailure(err.to_string()))
+                }
+                err => IoError::other(err),
+            })
+        })
+    }
+}
+
+/// SOCKS proxy has rejected credentials or requires them
+#[derive(Debug)]
+pub(super) struct ProxyAuthFailure(pub(super) String);
+
+impl fmt::Display for ProxyAuthFailure {
+    fn fmt(&self, out: &mut fmt::Formatter) -> fmt::Result {
+        write!(out, "{}", self.0)
+    }
+}
+
+impl StdError for ProxyAuthFailure {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::net::TcpListener;
+
+    #[tokio::test]
+    async fn test_connect_timeout() {
+        // accepts connections but never answers the handshake
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap().to_string();
+        tokio::spawn(async move {
+            let mut streams = Vec::new();
+            while let Ok((stream, _)) = listener.accept().await {
+                streams.push(stream);
+            }
+        });
+        let mut connector = SocksConnector::new(
+            SocksProxy::Socks5 { addr, auth: None },
+            Some(Duration::from_millis(100)),
+        );
+        let err = connector
+            .call(Uri::from_static("https://api.telegram.org"))
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), IoErrorKind::TimedOut);
+    }
+}
@@ -0,0 +1,156 @@
+use crate::{
+    api::Api,
+    handler::{ChatKey, UpdateMiddleware},
+    methods::SendMessage,
+    types::{Integer, Update, UserId},
+};
+use std::collections::HashSet;
+
+type Policy = Box<dyn Fn(&Update) -> bool + Send + Sync>;
+
+/// Restricts updates to allowed users and chats
+///
+/// An update passes when its user or chat is in the allowlist, or when the policy accepts it
+/// Everything is denied when neither allowlist nor policy is set
+/// Denied updates are dropped silently unless a denial message is set
+#[derive(Default)]
+pub struct AccessControl {
+    users: HashSet<UserId>,
+    chats: HashSet<Integer>,
+    policy: Option<Policy>,
+    denial: Option<(Api, String)>,
+}
+
+impl AccessControl {
+    /// Creates a middleware denying all updates
+    pub fn new() -> Self {
+        AccessControl::default()
+    }
+
+    /// Allows updates sent by the given users
+    pub fn allow_users<I, U>(mut self, user_ids: I) -> Self
+    where
+        I: IntoIterator<Item = U>,
+        U: Into<UserId>,
+    {
+        self.users.extend(user_ids.into_iter().map(Into::into));
+        self
+    }
+
+    /// Allows updates from the given chats
+    ///
+    /// Callback queries are checked against a chat of their message
+    pub fn allow_chats<I: IntoIterator<Item = Integer>>(mut self, chat_ids: I) -> Self {
+        self.chats.extend(chat_ids);
+        self
+    }
+
+    /// Allows updates accepted by the policy
+    ///
+    /// Use it when allowed users are not known in advance, e.g. kept in a database
+    pub fn policy<F>(mut self, policy: F) -> Self
+    where
+        F: Fn(&Update) -> bool + Send + Sync + 'static,
+    {
+        self.policy = Some(Box::new(policy));
+        self
+    }
+
+    /// Reply to denied updates with a message
+    ///
+    /// The message is sent in background to a chat of the update,
+    /// updates without a chat are dropped silently
+    pub fn deny_message<S: Into<String>>(mut self, api: Api, text: S) -> Self {
+        self.denial = Some((api, text.into()));
+        self
+    }
+
+    /// Returns true when the update is allowed
+    pub fn is_allowed(&self, update: &Update) -> bool {
+        if let Some(user) = update.get_user() {
+            if self.users.contains(&user.id) {
+                return true;
+            }
+        }
+        if let Some(key) = ChatKey::from_update(update, false) {
+            if self.chats.contains(&key.chat_id) {
+                return true;
+            }
+        }
+        self.policy.as_ref().is_some_and(|policy| policy(update))
+    }
+}
+
+impl UpdateMiddleware for AccessControl {
+    fn before(&self, update: &Update) -> bool {
+        if self.is_allowed(update) {
+            return true;
+        }
+        log::debug!("Access denied for update {}", update.id);
+        if let Some((ref api, ref text)) = self.denial {
+            if let Some(key) = ChatKey::from_update(update, false) {
+                api.spawn(api.execute(&SendMessage::new(key.chat_id, text.clone())));
+            }
+        }
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        executor::MockExecutor,
+        handler::fixtures::{group_message, message_update, private_message},
+    };
+    use tokio::time::{sleep, Duration};
+
+    fn private_update(user_id: Integer) -> Update {
+        message_update(private_message(user_id, "hi"))
+    }
+
+    fn group_update(chat_id: Integer, user_id: Integer) -> Update {
+        message_update(group_message(chat_id, user_id, "hi"))
+    }
+
+    #[test]
+    fn test_is_allowed() {
+        let access = AccessControl::new();
+        assert!(!access.is_allowed(&private_update(1)));
+
+        let access = AccessControl::new()
+            .allow_users(vec![1])
+            .allow_chats(vec![-2])
+            .policy(|update: &Update| update.get_chat_id() == Some(-3));
+        assert!(access.is_allowed(&private_update(1)));
+        // the user is allowed in any chat
+        assert!(access.is_allowed(&group_update(-4, 1)));
+        // any user is allowed in the chat
+        assert!(access.is_allowed(&group_update(-2, 5)));
+        assert!(access.is_allowed(&group_update(-3, 5)));
+        assert!(!access.is_allowed(&group_update(-4, 5)));
+        assert!(!access.is_allowed(&private_update(2)));
+        assert!(access.before(&private_update(1)));
+        assert!(!access.before(&private_update(4)));
+    }
+
+    #[tokio::test]
+    async fn test_deny_message() {
+        let executor = MockExecutor::new().with_result("sendMessage", private_message(4, "denied"));
+        let api = Api::builder("token").executor(executor.clone()).build().unwrap();
+        let access = AccessControl::new().allow_users(vec![1]).deny_message(api, "denied");
+
+        assert!(access.before(&private_update(1)));
+        assert!(!access.before(&group_update(-4, 5)));
+        for _ in 0..100 {
+            if !executor.params("sendMessage").is_empty() {
+                break;
+            }
+            sleep(Duration::from_millis(10)).await;
+        }
+        let params = executor.params("sendMessage");
+        assert_eq!(params.len(), 1);
+        assert_eq!(params[0]["chat_id"], -4);
+        assert_eq!(params[0]["text"], "denied");
+    }
+}
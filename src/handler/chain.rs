@@ -0,0 +1,178 @@
+use crate::{handler::UpdateHandler, types::Update};
+use std::{future::Future, pin::Pin, sync::Arc};
+
+/// Hooks called around an update handler
+///
+/// Use it for logging, access control or metrics without touching every handler
+/// before() is called in the order middlewares were added, after() in the reverse order
+/// after() is called only for middlewares whose before() returned true
+pub trait UpdateMiddleware: Send + Sync {
+    /// Called before the handler
+    ///
+    /// Return false to stop processing of the update,
+    /// the handler and the remaining middlewares are not called in this case
+    fn before(&self, _update: &Update) -> bool {
+        true
+    }
+
+    /// Called when the handler has completed or when the update has been stopped by a following middleware
+    fn after(&self, _update: &Update) {}
+}
+
+/// Passes updates through middlewares to the handler
+///
+/// The handler must return a value implementing Default, e.g. () or `Option<WebhookReply>`,
+/// which is used when an update is stopped by a middleware
+pub struct MiddlewareChain<H> {
+    handler: H,
+    middlewares: Vec<Arc<dyn UpdateMiddleware>>,
+}
+
+impl<H> MiddlewareChain<H> {
+    /// Creates a chain without middlewares
+    pub fn new(handler: H) -> Self {
+        MiddlewareChain {
+            handler,
+            middlewares: Vec::new(),
+        }
+    }
+
+    /// Adds a middleware to the end of the chain
+    pub fn middleware<M: UpdateMiddleware + 'static>(mut self, middleware: M) -> Self {
+        self.middlewares.push(Arc::new(middleware));
+        self
+    }
+}
+
+impl<H> UpdateHandler for MiddlewareChain<H>
+where
+    H: UpdateHandler,
+    <H::Future as Future>::Output: Default,
+{
+    type Future = Pin<Box<dyn Future<Output = <H::Future as Future>::Output> + Send>>;
+
+    fn handle(&mut self, update: Update) -> Self::Future {
+        if self.middlewares.is_empty() {
+            return Box::pin(self.handler.handle(update));
+        }
+        let passed = self
+            .middlewares
+            .iter()
+            .take_while(|middleware| middleware.before(&update))
+            .count();
+        if passed < self.middlewares.len() {
+            for middleware in self.middlewares[..passed].iter().rev() {
+                middleware.after(&update);
+            }
+            return Box::pin(async { Default::default() });
+        }
+        let middlewares = self.middlewares.clone();
+        let future = self.handler.handle(update.clone());
+        Box::pin(async move {
+            let result = future.await;
+            for middleware in middlewares.iter().rev() {
+                middleware.after(&update);
+            }
+            result
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    struct Recorder {
+        name: &'static str,
+        allow: bool,
+        events: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl UpdateMiddleware for Recorder {
+        fn before(&self, update: &Update) -> bool {
+            self.events
+                .lock()
+                .unwrap()
+                .push(format!("{} before {}", self.name, update.id));
+            self.allow || update.id != 2
+        }
+
+        fn after(&self, update: &Update) {
+            self.events
+                .lock()
+                .unwrap()
+                .push(format!("{} after {}", self.name, update.id));
+        }
+    }
+
+    fn create_update(id: i64) -> Update {
+        serde_json::from_value(serde_json::json!({"update_id": id, "unknown": {}})).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_middleware_chain() {
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let handler_events = events.clone();
+        let mut chain = MiddlewareChain::new(move |update: Update| {
+            let events = handler_events.clone();
+            async move { events.lock().unwrap().push(format!("handle {}", update.id)) }
+        })
+        .middleware(Recorder {
+            name: "first",
+            allow: true,
+            events: events.clone(),
+        })
+        .middleware(Recorder {
+            name: "auth",
+            allow: false,
+            events: events.clone(),
+        })
+        .middleware(Recorder {
+            name: "last",
+            allow: true,
+            events: events.clone(),
+        });
+
+        chain.handle(create_update(1)).await;
+        chain.handle(create_update(2)).await;
+
+        assert_eq!(
+            *events.lock().unwrap(),
+            vec![
+                "first before 1",
+                "auth before 1",
+                "last before 1",
+                "handle 1",
+                "last after 1",
+                "auth after 1",
+                "first after 1",
+                "first before 2",
+                "auth before 2",
+                "first after 2",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_middleware_chain_stopped() {
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let handler_events = events.clone();
+        let mut chain = MiddlewareChain::new(move |update: Update| {
+            let events = handler_events.clone();
+            async move { events.lock().unwrap().push(format!("handle {}", update.id)) }
+        })
+        .middleware(Recorder {
+            name: "auth",
+            allow: false,
+            events: events.clone(),
+        })
+        .middleware(Recorder {
+            name: "last",
+            allow: true,
+            events: events.clone(),
+        });
+        chain.handle(create_update(2)).await;
+        assert_eq!(*events.lock().unwrap(), vec!["auth before 2"]);
+    }
+}
@@ -0,0 +1,263 @@
+use crate::{
+    api::Api,
+    handler::UpdateHandler,
+    types::{Message, Update, UpdateKind},
+};
+use std::{
+    collections::HashMap,
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Mutex, MutexGuard},
+};
+
+/// A command from a message text
+///
+/// Text must start with a command: `/command@botusername arg1 arg2`
+/// A command is detected using a bot_command entity, so a command in the middle of a text is ignored
+#[derive(Clone, Debug)]
+pub struct Command {
+    /// Name of the command including leading slash, without bot username
+    pub name: String,
+    /// Command arguments separated by whitespace
+    pub args: Vec<String>,
+    /// Message containing the command
+    pub message: Message,
+}
+
+type HandlerFuture = Pin<Box<dyn Future<Output = ()> + Send>>;
+type CommandHandler = Box<dyn FnMut(Command) -> HandlerFuture + Send + Sync>;
+type DefaultHandler = Box<dyn FnMut(Update) -> HandlerFuture + Send + Sync>;
+
+/// Routes commands to registered handlers
+///
+/// Implements UpdateHandler, so it works with both long polling and webhook
+/// Updates without a registered command are passed to the default handler
+/// Commands addressed to other bots (/command@otherbot) are passed to the default handler too,
+/// username of the bot is obtained using Api::bot_user() unless it is set explicitly
+pub struct CommandRouter {
+    api: Api,
+    bot_username: Option<String>,
+    routes: Arc<Mutex<Routes>>,
+}
+
+#[derive(Default)]
+struct Routes {
+    handlers: HashMap<String, CommandHandler>,
+    default_handler: Option<DefaultHandler>,
+}
+
+impl CommandRouter {
+    /// Creates a new router without handlers
+    pub fn new(api: Api) -> Self {
+        CommandRouter {
+            api,
+            bot_username: None,
+            routes: Arc::new(Mutex::new(Routes::default())),
+        }
+    }
+
+    /// Username of the bot
+    ///
+    /// Skips getMe request when the username is known in advance
+    pub fn bot_username<S: Into<String>>(&mut self, bot_username: S) -> &mut Self {
+        self.bot_username = Some(bot_username.into());
+        self
+    }
+
+    /// Registers a handler for a command
+    ///
+    /// # Arguments
+    ///
+    /// * name - Name of the command, leading slash is optional
+    /// * handler - Command handler
+    pub fn add<S, H, F>(&mut self, name: S, mut handler: H) -> &mut Self
+    where
+        S: Into<String>,
+        H: FnMut(Command) -> F + Send + Sync + 'static,
+        F: Future<Output = ()> + Send + 'static,
+    {
+        let mut name = name.into();
+        if !name.starts_with('/') {
+            name.insert(0, '/');
+        }
+        lock_routes(&self.routes)
+            .handlers
+            .insert(name, Box::new(move |command| Box::pin(handler(command))));
+        self
+    }
+
+    /// Sets a handler for updates without a registered command
+    pub fn default_handler<H, F>(&mut self, mut handler: H) -> &mut Self
+    where
+        H: FnMut(Update) -> F + Send + Sync + 'static,
+        F: Future<Output = ()> + Send + 'static,
+    {
+        lock_routes(&self.routes).default_handler = Some(Box::new(move |update| Box::pin(handler(update))));
+        self
+    }
+}
+
+fn lock_routes(routes: &Mutex<Routes>) -> MutexGuard<'_, Routes> {
+    match routes.lock() {
+        Ok(routes) => routes,
+        Err(err) => err.into_inner(),
+    }
+}
+
+impl Routes {
+    /// Calls a handler of the command or the default handler
+    ///
+    /// A command addressed to a bot is passed to the default handler when username of this bot is unknown
+    fn dispatch(&mut self, update: Update, bot_username: Option<&str>) -> HandlerFuture {
+        if let UpdateKind::Message(ref message) = update.kind {
+            if let Some(command) = message.get_command() {
+                let is_addressed = match (command.bot_name.as_ref(), bot_username) {
+                    (None, _) => true,
+                    (Some(_), Some(bot_username)) => command.is_addressed_to(bot_username),
+                    (Some(_), None) => false,
+                };
+                if is_addressed {
+                    if let Some(handler) = self.handlers.get_mut(&command.command) {
+                        let name = command.command.clone();
+                        let args = message
+                            .get_command_args()
+                            .unwrap_or_default()
+                            .into_iter()
+                            .map(String::from)
+                            .collect();
+                        if let UpdateKind::Message(message) = update.kind {
+                            return handler(Command { name, args, message });
+                        }
+                    }
+                }
+            }
+        }
+        match self.default_handler {
+            Some(ref mut handler) => handler(update),
+            None => Box::pin(async {}),
+        }
+    }
+}
+
+impl UpdateHandler for CommandRouter {
+    type Future = HandlerFuture;
+
+    fn handle(&mut self, update: Update) -> Self::Future {
+        let routes = self.routes.clone();
+        if let Some(ref bot_username) = self.bot_username {
+            return lock_routes(&routes).dispatch(update, Some(bot_username));
+        }
+        let bot_user = self.api.bot_user();
+        Box::pin(async move {
+            let bot_username = match bot_user.await {
+                Ok(user) => user.username,
+                Err(err) => {
+                    log::error!("Failed to get username of the bot: {}", err);
+                    None
+                }
+            };
+            let handler = lock_routes(&routes).dispatch(update, bot_username.as_deref());
+            handler.await
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        executor::MockExecutor,
+        handler::fixtures::{message_update, private_message},
+    };
+
+    fn create_update(text: &str, command_length: usize) -> Update {
+        let entities = if command_length > 0 {
+            serde_json::json!([{"type": "bot_command", "offset": 0, "length": command_length}])
+        } else {
+            serde_json::json!([])
+        };
+        let mut message = private_message(1, text);
+        message["entities"] = entities;
+        message_update(message)
+    }
+
+    #[tokio::test]
+    async fn test_router() {
+        let commands = Arc::new(Mutex::new(Vec::new()));
+        let defaults = Arc::new(Mutex::new(0));
+        let mut router = CommandRouter::new(Api::new("token").unwrap());
+        router.bot_username("@TestBot");
+        router.add("start", {
+            let commands = commands.clone();
+            move |command: Command| {
+                let commands = commands.clone();
+                async move { commands.lock().unwrap().push((command.name, command.args)) }
+            }
+        });
+        router.default_handler({
+            let defaults = defaults.clone();
+            move |_| {
+                let defaults = defaults.clone();
+                async move { *defaults.lock().unwrap() += 1 }
+            }
+        });
+
+        router.handle(create_update("/start@testbot a b", 14)).await;
+        router.handle(create_update("/start", 6)).await;
+        router.handle(create_update("/start@otherbot", 15)).await;
+        router.handle(create_update("/help", 5)).await;
+        router.handle(create_update("hello", 0)).await;
+        router.handle(create_update("/start without entity", 0)).await;
+
+        assert_eq!(
+            *commands.lock().unwrap(),
+            vec![
+                (String::from("/start"), vec![String::from("a"), String::from("b")]),
+                (String::from("/start"), vec![]),
+            ]
+        );
+        assert_eq!(*defaults.lock().unwrap(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_router_bot_user() {
+        let executor = MockExecutor::new().with_result(
+            "getMe",
+            serde_json::json!({"id": 2, "is_bot": true, "first_name": "bot", "username": "TestBot"}),
+        );
+        let api = Api::builder("token").executor(executor.clone()).build().unwrap();
+        let commands = Arc::new(Mutex::new(Vec::new()));
+        let mut router = CommandRouter::new(api);
+        router.add("/start", {
+            let commands = commands.clone();
+            move |command: Command| {
+                let commands = commands.clone();
+                async move { commands.lock().unwrap().push(command.name) }
+            }
+        });
+
+        router.handle(create_update("/start@otherbot", 15)).await;
+        router.handle(create_update("/start@testbot", 14)).await;
+        router.handle(create_update("/start", 6)).await;
+        assert_eq!(
+            *commands.lock().unwrap(),
+            vec![String::from("/start"), String::from("/start")]
+        );
+        assert_eq!(executor.requests().len(), 1);
+
+        let executor = MockExecutor::new().with_error("getMe", 401, "Unauthorized");
+        let api = Api::builder("token").executor(executor).build().unwrap();
+        let commands = Arc::new(Mutex::new(0));
+        let mut router = CommandRouter::new(api);
+        router.add("/start", {
+            let commands = commands.clone();
+            move |_| {
+                let commands = commands.clone();
+                async move { *commands.lock().unwrap() += 1 }
+            }
+        });
+        router.handle(create_update("/start@otherbot", 15)).await;
+        router.handle(create_update("/start", 6)).await;
+        assert_eq!(*commands.lock().unwrap(), 1);
+    }
+}
@@ -0,0 +1,97 @@
+use crate::{
+    handler::UpdateMiddleware,
+    types::{Integer, Update},
+};
+use std::{
+    cmp::max,
+    collections::{HashSet, VecDeque},
+    sync::Mutex,
+};
+
+const DEFAULT_CAPACITY: usize = 1000;
+
+/// Drops updates which have been already seen
+///
+/// Telegram may deliver an update again when a webhook request fails or times out,
+/// add this middleware to a MiddlewareChain to make sure the handler is called once
+/// IDs of the most recent updates are kept in a ring buffer
+pub struct Deduplicator {
+    capacity: usize,
+    seen: Mutex<SeenIds>,
+}
+
+#[derive(Default)]
+struct SeenIds {
+    order: VecDeque<Integer>,
+    ids: HashSet<Integer>,
+}
+
+impl Default for Deduplicator {
+    fn default() -> Self {
+        Deduplicator::new(DEFAULT_CAPACITY)
+    }
+}
+
+impl Deduplicator {
+    /// Creates a new deduplicator
+    ///
+    /// # Arguments
+    ///
+    /// * capacity - Number of update IDs to remember, zero is treated as 1; 1000 by default
+    pub fn new(capacity: usize) -> Self {
+        Deduplicator {
+            capacity: max(capacity, 1),
+            seen: Mutex::new(SeenIds::default()),
+        }
+    }
+
+    /// Remembers the update ID, returns false when it has been already seen
+    pub fn insert(&self, update_id: Integer) -> bool {
+        let mut seen = match self.seen.lock() {
+            Ok(seen) => seen,
+            Err(err) => err.into_inner(),
+        };
+        if !seen.ids.insert(update_id) {
+            return false;
+        }
+        seen.order.push_back(update_id);
+        if seen.order.len() > self.capacity {
+            if let Some(oldest) = seen.order.pop_front() {
+                seen.ids.remove(&oldest);
+            }
+        }
+        true
+    }
+}
+
+impl UpdateMiddleware for Deduplicator {
+    fn before(&self, update: &Update) -> bool {
+        let is_new = self.insert(update.id);
+        if !is_new {
+            log::debug!("Skipping duplicate update {}", update.id);
+        }
+        is_new
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deduplicator() {
+        let dedup = Deduplicator::new(2);
+        assert!(dedup.insert(1));
+        assert!(dedup.insert(2));
+        assert!(!dedup.insert(1));
+        assert!(!dedup.insert(2));
+        assert!(dedup.insert(3));
+        // 1 is evicted
+        assert!(dedup.insert(1));
+        assert!(!dedup.insert(3));
+
+        let update: Update = serde_json::from_value(serde_json::json!({"update_id": 5, "unknown": {}})).unwrap();
+        assert!(dedup.before(&update));
+        assert!(!dedup.before(&update));
+    }
+}
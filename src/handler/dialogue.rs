@@ -0,0 +1,262 @@
+use crate::{
+    handler::{filter::Filter, ChatKey, InMemorySessionStore, Session, SessionStore, UpdateHandler},
+    types::Update,
+};
+use failure::Error;
+use serde::{de::DeserializeOwned, Serialize};
+use std::{future::Future, pin::Pin, sync::Arc};
+
+const STATE_NAME: &str = "dialogue_state";
+
+type HandlerFuture = Pin<Box<dyn Future<Output = ()> + Send>>;
+type TransitionFuture<S> = Pin<Box<dyn Future<Output = Transition<S>> + Send>>;
+type StateHandler<S> = Arc<(
+    Box<dyn Filter<S>>,
+    Box<dyn Fn(S, Update, Session) -> TransitionFuture<S> + Send + Sync>,
+)>;
+type DefaultHandler = Arc<dyn Fn(Update) -> HandlerFuture + Send + Sync>;
+
+/// A transition returned by a dialogue handler
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Transition<S> {
+    /// Keep the current state
+    Stay,
+    /// Switch to the given state
+    Next(S),
+    /// Finish the dialogue, the next update starts from the default state
+    Exit,
+}
+
+/// A finite state machine for multi-step conversations
+///
+/// Each chat (or each user in a chat) has its own state, which is `S::default()` initially
+/// An update is passed to the first handler whose filter accepts the current state,
+/// the state is changed according to a transition returned by the handler
+/// Updates without a chat or a matching handler are passed to the default handler
+/// States are kept in a SessionStore, so they survive restarts when a persistent store is used
+/// Implements UpdateHandler, so it works with both long polling and webhook
+pub struct Dialogue<S> {
+    per_user: bool,
+    store: Arc<dyn SessionStore>,
+    handlers: Vec<StateHandler<S>>,
+    default_handler: Option<DefaultHandler>,
+}
+
+impl<S> Default for Dialogue<S> {
+    fn default() -> Self {
+        Dialogue {
+            per_user: false,
+            store: Arc::new(InMemorySessionStore::new()),
+            handlers: Vec::new(),
+            default_handler: None,
+        }
+    }
+}
+
+impl<S> Dialogue<S>
+where
+    S: Default + Serialize + DeserializeOwned + Send + 'static,
+{
+    /// Creates a new dialogue without handlers
+    pub fn new() -> Self {
+        Dialogue::default()
+    }
+
+    /// Keep a separate state for each user in a chat
+    ///
+    /// Defaults to false, i.e. all users of a chat share the state
+    pub fn per_user(&mut self, per_user: bool) -> &mut Self {
+        self.per_user = per_user;
+        self
+    }
+
+    /// Set a store for states
+    ///
+    /// Defaults to InMemorySessionStore
+    pub fn session_store<T: SessionStore + 'static>(&mut self, store: T) -> &mut Self {
+        self.store = Arc::new(store);
+        self
+    }
+
+    /// Adds a handler for states accepted by the filter
+    ///
+    /// # Arguments
+    ///
+    /// * filter - Filter for the current state, e.g. `|state: &State| *state == State::AskName`
+    /// * handler - Receives the current state, an update and a session of the chat, returns a transition
+    ///
+    /// Use the session to keep data collected during the dialogue,
+    /// the state is stored in the same session under "dialogue_state" name
+    pub fn state<P, H, F>(&mut self, filter: P, handler: H) -> &mut Self
+    where
+        P: Filter<S> + 'static,
+        H: Fn(S, Update, Session) -> F + Send + Sync + 'static,
+        F: Future<Output = Transition<S>> + Send + 'static,
+    {
+        self.handlers.push(Arc::new((
+            Box::new(filter),
+            Box::new(move |state, update, session| Box::pin(handler(state, update, session))),
+        )));
+        self
+    }
+
+    /// Sets a handler for updates which are not handled by the dialogue
+    pub fn default_handler<H, F>(&mut self, handler: H) -> &mut Self
+    where
+        H: Fn(Update) -> F + Send + Sync + 'static,
+        F: Future<Output = ()> + Send + 'static,
+    {
+        self.default_handler = Some(Arc::new(move |update| Box::pin(handler(update))));
+        self
+    }
+
+    /// Returns the current state for the key
+    pub async fn get_state(&self, key: ChatKey) -> Result<S, Error> {
+        load_state(&Session::new(self.store.clone(), key)).await
+    }
+}
+
+async fn load_state<S: Default + DeserializeOwned>(session: &Session) -> Result<S, Error> {
+    Ok(session.get(STATE_NAME).await?.unwrap_or_default())
+}
+
+impl<S> UpdateHandler for Dialogue<S>
+where
+    S: Default + Serialize + DeserializeOwned + Send + 'static,
+{
+    type Future = HandlerFuture;
+
+    fn handle(&mut self, update: Update) -> Self::Future {
+        let default_handler = self.default_handler.clone();
+        let handle_default = move |update| match default_handler {
+            Some(handler) => handler(update),
+            None => Box::pin(async {}),
+        };
+        let key = match ChatKey::from_update(&update, self.per_user) {
+            Some(key) => key,
+            None => return handle_default(update),
+        };
+        let session = Session::new(self.store.clone(), key);
+        let handlers = self.handlers.clone();
+        Box::pin(async move {
+            let state: S = match load_state(&session).await {
+                Ok(state) => state,
+                Err(err) => {
+                    log::error!("Failed to load dialogue state: {}", err);
+                    return;
+                }
+            };
+            let handler = match handlers.iter().find(|handler| handler.0.check(&state)) {
+                Some(handler) => handler,
+                None => return handle_default(update).await,
+            };
+            let result = match (handler.1)(state, update, session.clone()).await {
+                Transition::Stay => Ok(()),
+                Transition::Next(state) => session.set(STATE_NAME, &state).await,
+                Transition::Exit => session.remove(STATE_NAME).await,
+            };
+            if let Err(err) = result {
+                log::error!("Failed to save dialogue state: {}", err);
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        handler::fixtures::{message_update, private_message},
+        types::{Integer, UpdateKind},
+    };
+    use serde::Deserialize;
+    use serde_json::Value as JsonValue;
+    use std::sync::Mutex;
+
+    #[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+    enum State {
+        #[default]
+        Start,
+        AskName,
+        AskAge(String),
+    }
+
+    fn create_update(user_id: Integer, text: &str) -> Update {
+        message_update(private_message(user_id, text))
+    }
+
+    fn get_text(update: &Update) -> String {
+        match update.kind {
+            UpdateKind::Message(ref message) => message.get_text().unwrap().data.clone(),
+            _ => unreachable!(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_dialogue() {
+        let results = Arc::new(Mutex::new(Vec::new()));
+        let store = InMemorySessionStore::new();
+        let mut dialogue = Dialogue::new();
+        dialogue.session_store(store.clone());
+        dialogue
+            .state(
+                |state: &State| *state == State::Start,
+                |_, update: Update, _| async move {
+                    if get_text(&update) == "/register" {
+                        Transition::Next(State::AskName)
+                    } else {
+                        Transition::Stay
+                    }
+                },
+            )
+            .state(
+                |state: &State| *state == State::AskName,
+                |_, update: Update, session: Session| async move {
+                    let name = get_text(&update);
+                    session.set("name", &name).await.unwrap();
+                    Transition::Next(State::AskAge(name))
+                },
+            )
+            .state(|state: &State| matches!(state, State::AskAge(_)), {
+                let results = results.clone();
+                move |state, update: Update, _| {
+                    let results = results.clone();
+                    async move {
+                        if let State::AskAge(name) = state {
+                            results.lock().unwrap().push((name, get_text(&update)));
+                        }
+                        Transition::Exit
+                    }
+                }
+            });
+
+        let key = ChatKey {
+            chat_id: 1,
+            user_id: None,
+        };
+        dialogue.handle(create_update(1, "hello")).await;
+        assert_eq!(dialogue.get_state(key).await.unwrap(), State::Start);
+        dialogue.handle(create_update(1, "/register")).await;
+        assert_eq!(dialogue.get_state(key).await.unwrap(), State::AskName);
+        dialogue.handle(create_update(2, "/register")).await;
+        dialogue.handle(create_update(1, "John")).await;
+        assert_eq!(
+            dialogue.get_state(key).await.unwrap(),
+            State::AskAge(String::from("John"))
+        );
+        dialogue.handle(create_update(1, "30")).await;
+        assert_eq!(dialogue.get_state(key).await.unwrap(), State::Start);
+        assert!(store.get(key, STATE_NAME).await.unwrap().is_none());
+        assert_eq!(store.get(key, "name").await.unwrap(), Some(JsonValue::from("John")));
+
+        let key = ChatKey {
+            chat_id: 2,
+            user_id: None,
+        };
+        assert_eq!(dialogue.get_state(key).await.unwrap(), State::AskName);
+        assert_eq!(
+            *results.lock().unwrap(),
+            vec![(String::from("John"), String::from("30"))]
+        );
+    }
+}
@@ -0,0 +1,363 @@
+use crate::{
+    handler::{filter::Filter, UpdateHandler},
+    types::{
+        CallbackQuery, ChosenInlineResult, InlineQuery, Message, Poll, PollAnswer, PreCheckoutQuery, ShippingQuery,
+        Update, UpdateKind,
+    },
+};
+use failure::Error;
+use std::{future::Future, pin::Pin, sync::Arc};
+
+type HandlerFuture = Pin<Box<dyn Future<Output = ()> + Send>>;
+type RouteFuture = Pin<Box<dyn Future<Output = Result<(), Error>> + Send>>;
+type Handler<T> = Box<dyn FnMut(T) -> RouteFuture + Send + Sync>;
+type ErrorHandler = Arc<dyn Fn(Update, Error) -> HandlerFuture + Send + Sync>;
+
+struct Route<T> {
+    filter: Option<Box<dyn Filter<T>>>,
+    handler: Handler<T>,
+}
+
+type Routes<T> = Vec<Route<T>>;
+
+/// A value returned by a Dispatcher handler
+///
+/// Implemented for () and `Result<(), E>`,
+/// errors are passed to the error handler set by Dispatcher::on_error()
+pub trait HandlerResult: Send + 'static {
+    /// Converts the value into a result
+    fn into_result(self) -> Result<(), Error>;
+}
+
+impl HandlerResult for () {
+    fn into_result(self) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+impl<E> HandlerResult for Result<(), E>
+where
+    E: Into<Error> + Send + 'static,
+{
+    fn into_result(self) -> Result<(), Error> {
+        self.map_err(Into::into)
+    }
+}
+
+fn boxed<T, H, F>(mut handler: H) -> Handler<T>
+where
+    H: FnMut(T) -> F + Send + Sync + 'static,
+    F: Future + Send + 'static,
+    F::Output: HandlerResult,
+{
+    Box::new(move |data| {
+        let future = handler(data);
+        Box::pin(async move { future.await.into_result() })
+    })
+}
+
+/// Passes data to the first handler with a matching filter
+/// or returns it back as an update kind when there is no such handler
+#[allow(clippy::result_large_err)]
+fn route<T>(routes: &mut Routes<T>, data: T, kind: fn(T) -> UpdateKind) -> Result<RouteFuture, UpdateKind> {
+    let route = routes
+        .iter_mut()
+        .find(|route| route.filter.as_ref().is_none_or(|filter| filter.check(&data)));
+    match route {
+        Some(route) => Ok((route.handler)(data)),
+        None => Err(kind(data)),
+    }
+}
+
+/// Generates a pair of methods adding handlers for a kind of update:
+/// one for all updates and one for updates which pass a filter
+///
+/// Handlers are stored in the field with the same name as the first method
+macro_rules! routes {
+    ($($name:ident, $name_if:ident: $data:ty => $desc:literal;)*) => {
+        $(
+            #[doc = concat!("Adds a handler for ", $desc)]
+            pub fn $name<H, F>(&mut self, handler: H) -> &mut Self
+            where
+                H: FnMut($data) -> F + Send + Sync + 'static,
+                F: Future + Send + 'static,
+                F::Output: HandlerResult,
+            {
+                Dispatcher::add(&mut self.$name, None, boxed(handler));
+                self
+            }
+
+            #[doc = concat!("Adds a handler for ", $desc, " which pass the filter")]
+            pub fn $name_if<P, H, F>(&mut self, filter: P, handler: H) -> &mut Self
+            where
+                P: Filter<$data> + 'static,
+                H: FnMut($data) -> F + Send + Sync + 'static,
+                F: Future + Send + 'static,
+                F::Output: HandlerResult,
+            {
+                Dispatcher::add(&mut self.$name, Some(Box::new(filter)), boxed(handler));
+                self
+            }
+        )*
+    };
+}
+
+/// Routes updates to handlers registered for each kind of update
+///
+/// Implements UpdateHandler, so it works with both long polling and webhook
+/// Several handlers can be added for a kind of update using filters,
+/// an update is passed to the first handler whose filter passes
+/// Updates without a matching handler are passed to the default handler
+/// Handlers may return `Result<(), E>`, errors are passed to the error handler
+/// To handle commands, pass updates to CommandRouter from the default handler:
+/// `dispatcher.default_handler(move |update| router.handle(update))`
+#[derive(Default)]
+pub struct Dispatcher {
+    message: Routes<Message>,
+    edited_message: Routes<Message>,
+    channel_post: Routes<Message>,
+    edited_channel_post: Routes<Message>,
+    inline_query: Routes<InlineQuery>,
+    chosen_inline_result: Routes<ChosenInlineResult>,
+    callback_query: Routes<CallbackQuery>,
+    shipping_query: Routes<ShippingQuery>,
+    pre_checkout_query: Routes<PreCheckoutQuery>,
+    poll: Routes<Poll>,
+    poll_answer: Routes<PollAnswer>,
+    default_handler: Option<Handler<Update>>,
+    error_handler: Option<ErrorHandler>,
+}
+
+impl Dispatcher {
+    /// Creates a new dispatcher without handlers
+    pub fn new() -> Self {
+        Dispatcher::default()
+    }
+
+    fn add<T>(routes: &mut Routes<T>, filter: Option<Box<dyn Filter<T>>>, handler: Handler<T>) {
+        routes.push(Route { filter, handler });
+    }
+
+    routes! {
+        message, message_if: Message => "new messages";
+        edited_message, edited_message_if: Message => "edited messages";
+        channel_post, channel_post_if: Message => "new channel posts";
+        edited_channel_post, edited_channel_post_if: Message => "edited channel posts";
+        inline_query, inline_query_if: InlineQuery => "inline queries";
+        chosen_inline_result, chosen_inline_result_if: ChosenInlineResult => "chosen inline results";
+        callback_query, callback_query_if: CallbackQuery => "callback queries";
+        shipping_query, shipping_query_if: ShippingQuery => "shipping queries";
+        pre_checkout_query, pre_checkout_query_if: PreCheckoutQuery => "pre-checkout queries";
+        poll, poll_if: Poll => "poll states";
+        poll_answer, poll_answer_if: PollAnswer => "poll answers";
+    }
+
+    /// Sets a handler for updates without a matching handler
+    pub fn default_handler<H, F>(&mut self, handler: H) -> &mut Self
+    where
+        H: FnMut(Update) -> F + Send + Sync + 'static,
+        F: Future + Send + 'static,
+        F::Output: HandlerResult,
+    {
+        self.default_handler = Some(boxed(handler));
+        self
+    }
+
+    /// Sets a handler for errors returned by handlers
+    ///
+    /// Receives an update which has caused the error,
+    /// use it to report failures, e.g. send a message to an admin
+    /// Errors are logged when the handler is not set
+    pub fn on_error<H, F>(&mut self, handler: H) -> &mut Self
+    where
+        H: Fn(Update, Error) -> F + Send + Sync + 'static,
+        F: Future<Output = ()> + Send + 'static,
+    {
+        self.error_handler = Some(Arc::new(move |update, err| Box::pin(handler(update, err))));
+        self
+    }
+}
+
+impl UpdateHandler for Dispatcher {
+    type Future = HandlerFuture;
+
+    fn handle(&mut self, update: Update) -> Self::Future {
+        let error_handler = self.error_handler.clone();
+        let original = error_handler.as_ref().map(|_| update.clone());
+        let update_id = update.id;
+        let result = match update.kind {
+            UpdateKind::Message(data) => route(&mut self.message, data, UpdateKind::Message),
+            UpdateKind::EditedMessage(data) => route(&mut self.edited_message, data, UpdateKind::EditedMessage),
+            UpdateKind::ChannelPost(data) => route(&mut self.channel_post, data, UpdateKind::ChannelPost),
+            UpdateKind::EditedChannelPost(data) => {
+                route(&mut self.edited_channel_post, data, UpdateKind::EditedChannelPost)
+            }
+            UpdateKind::InlineQuery(data) => route(&mut self.inline_query, data, UpdateKind::InlineQuery),
+            UpdateKind::ChosenInlineResult(data) => {
+                route(&mut self.chosen_inline_result, data, UpdateKind::ChosenInlineResult)
+            }
+            UpdateKind::CallbackQuery(data) => route(&mut self.callback_query, data, UpdateKind::CallbackQuery),
+            UpdateKind::ShippingQuery(data) => route(&mut self.shipping_query, data, UpdateKind::ShippingQuery),
+            UpdateKind::PreCheckoutQuery(data) => {
+                route(&mut self.pre_checkout_query, data, UpdateKind::PreCheckoutQuery)
+            }
+            UpdateKind::Poll(data) => route(&mut self.poll, data, UpdateKind::Poll),
+            UpdateKind::PollAnswer(data) => route(&mut self.poll_answer, data, UpdateKind::PollAnswer),
+            kind => Err(kind),
+        };
+        let future = match (result, self.default_handler.as_mut()) {
+            (Ok(future), _) => future,
+            (Err(kind), Some(handler)) => handler(Update { id: update_id, kind }),
+            (Err(_), None) => return Box::pin(async {}),
+        };
+        Box::pin(async move {
+            if let Err(err) = future.await {
+                match (error_handler, original) {
+                    (Some(handler), Some(update)) => handler(update, err).await,
+                    _ => log::error!("Failed to handle update {}: {}", update_id, err),
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    fn create_update(kind: &str, data: serde_json::Value) -> Update {
+        let mut update = serde_json::json!({"update_id": 1});
+        update[kind] = data;
+        serde_json::from_value(update).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_dispatcher() {
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let mut dispatcher = Dispatcher::new();
+        dispatcher
+            .message({
+                let events = events.clone();
+                move |message: Message| {
+                    let events = events.clone();
+                    async move { events.lock().unwrap().push(format!("message {}", message.id)) }
+                }
+            })
+            .callback_query({
+                let events = events.clone();
+                move |query: CallbackQuery| {
+                    let events = events.clone();
+                    async move { events.lock().unwrap().push(format!("callback_query {}", query.id)) }
+                }
+            })
+            .default_handler({
+                let events = events.clone();
+                move |update: Update| {
+                    let events = events.clone();
+                    async move { events.lock().unwrap().push(format!("default {}", update.id)) }
+                }
+            });
+
+        let message = serde_json::json!({
+            "message_id": 2,
+            "date": 0,
+            "from": {"id": 1, "is_bot": false, "first_name": "test"},
+            "chat": {"id": 1, "type": "private", "first_name": "test"},
+            "text": "hi"
+        });
+        dispatcher.handle(create_update("message", message.clone())).await;
+        dispatcher.handle(create_update("edited_message", message)).await;
+        dispatcher
+            .handle(create_update(
+                "callback_query",
+                serde_json::json!({
+                    "id": "query",
+                    "from": {"id": 1, "is_bot": false, "first_name": "test"},
+                    "chat_instance": "instance"
+                }),
+            ))
+            .await;
+        dispatcher.handle(create_update("unknown", serde_json::json!({}))).await;
+
+        assert_eq!(
+            *events.lock().unwrap(),
+            vec!["message 2", "default 1", "callback_query query", "default 1"]
+        );
+
+        let mut dispatcher = Dispatcher::new();
+        dispatcher.handle(create_update("unknown", serde_json::json!({}))).await;
+    }
+
+    #[tokio::test]
+    async fn test_filters() {
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let mut dispatcher = Dispatcher::new();
+        dispatcher
+            .callback_query_if(crate::handler::filter::data_prefix("page:"), {
+                let events = events.clone();
+                move |query: CallbackQuery| {
+                    let events = events.clone();
+                    async move { events.lock().unwrap().push(format!("page {:?}", query.data)) }
+                }
+            })
+            .callback_query({
+                let events = events.clone();
+                move |query: CallbackQuery| {
+                    let events = events.clone();
+                    async move { events.lock().unwrap().push(format!("other {:?}", query.data)) }
+                }
+            });
+
+        for data in &["page:1", "item:1"] {
+            dispatcher
+                .handle(create_update(
+                    "callback_query",
+                    serde_json::json!({
+                        "id": "query",
+                        "from": {"id": 1, "is_bot": false, "first_name": "test"},
+                        "chat_instance": "instance",
+                        "data": data
+                    }),
+                ))
+                .await;
+        }
+
+        assert_eq!(
+            *events.lock().unwrap(),
+            vec!["page Some(\"page:1\")", "other Some(\"item:1\")"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_on_error() {
+        let errors = Arc::new(Mutex::new(Vec::new()));
+        let mut dispatcher = Dispatcher::new();
+        dispatcher
+            .callback_query(|query: CallbackQuery| async move {
+                match query.data {
+                    Some(_) => Ok(()),
+                    None => Err(failure::err_msg("data is missing")),
+                }
+            })
+            .on_error({
+                let errors = errors.clone();
+                move |update: Update, err: Error| {
+                    let errors = errors.clone();
+                    async move { errors.lock().unwrap().push(format!("{} {}", update.id, err)) }
+                }
+            });
+
+        let query = serde_json::json!({
+            "id": "query",
+            "from": {"id": 1, "is_bot": false, "first_name": "test"},
+            "chat_instance": "instance"
+        });
+        dispatcher.handle(create_update("callback_query", query.clone())).await;
+        let mut query = query;
+        query["data"] = serde_json::json!("data");
+        dispatcher.handle(create_update("callback_query", query)).await;
+
+        assert_eq!(*errors.lock().unwrap(), vec!["1 data is missing"]);
+    }
+}
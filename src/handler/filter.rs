@@ -0,0 +1,162 @@
+//! Predicates deciding whether a handler registered in Dispatcher is called
+//!
+//! Filters are composed using Filter::and(), Filter::or() and Not
+
+use crate::types::{CallbackQuery, Message, MessageData, MessageKind, UserId};
+use regex::Regex;
+use std::collections::HashSet;
+
+/// A predicate over an update of a specific kind
+///
+/// Implemented for closures, e.g. `|message: &Message| message.get_text().is_some()`
+pub trait Filter<T>: Send + Sync {
+    /// Returns true when the handler has to be called
+    fn check(&self, value: &T) -> bool;
+
+    /// Passes when both filters pass
+    fn and<F: Filter<T>>(self, other: F) -> And<Self, F>
+    where
+        Self: Sized,
+    {
+        And(self, other)
+    }
+
+    /// Passes when any of filters passes
+    fn or<F: Filter<T>>(self, other: F) -> Or<Self, F>
+    where
+        Self: Sized,
+    {
+        Or(self, other)
+    }
+}
+
+impl<T, F> Filter<T> for F
+where
+    F: Fn(&T) -> bool + Send + Sync,
+{
+    fn check(&self, value: &T) -> bool {
+        self(value)
+    }
+}
+
+/// Passes when both filters pass
+#[derive(Clone, Debug)]
+pub struct And<A, B>(pub A, pub B);
+
+impl<T, A: Filter<T>, B: Filter<T>> Filter<T> for And<A, B> {
+    fn check(&self, value: &T) -> bool {
+        self.0.check(value) && self.1.check(value)
+    }
+}
+
+/// Passes when any of filters passes
+#[derive(Clone, Debug)]
+pub struct Or<A, B>(pub A, pub B);
+
+impl<T, A: Filter<T>, B: Filter<T>> Filter<T> for Or<A, B> {
+    fn check(&self, value: &T) -> bool {
+        self.0.check(value) || self.1.check(value)
+    }
+}
+
+/// Passes when the inner filter does not pass
+#[derive(Clone, Debug)]
+pub struct Not<F>(pub F);
+
+impl<T, F: Filter<T>> Filter<T> for Not<F> {
+    fn check(&self, value: &T) -> bool {
+        !self.0.check(value)
+    }
+}
+
+/// Passes when text of a message matches the regex
+pub fn text_matches(regex: Regex) -> impl Filter<Message> {
+    move |message: &Message| message.get_text().is_some_and(|text| regex.is_match(&text.data))
+}
+
+/// Passes when a message is sent to a private chat
+pub fn is_private() -> impl Filter<Message> {
+    |message: &Message| matches!(message.kind, MessageKind::Private { .. })
+}
+
+/// Passes when a message is sent by one of the given users
+pub fn from_users<I, U>(user_ids: I) -> impl Filter<Message>
+where
+    I: IntoIterator<Item = U>,
+    U: Into<UserId>,
+{
+    let user_ids: HashSet<UserId> = user_ids.into_iter().map(Into::into).collect();
+    move |message: &Message| message.get_user().is_some_and(|user| user_ids.contains(&user.id))
+}
+
+/// Passes when a message contains a photo
+pub fn has_photo() -> impl Filter<Message> {
+    |message: &Message| matches!(message.data, MessageData::Photo { .. })
+}
+
+/// Passes when data of a callback query starts with the prefix
+pub fn data_prefix<S: Into<String>>(prefix: S) -> impl Filter<CallbackQuery> {
+    let prefix = prefix.into();
+    move |query: &CallbackQuery| query.data.as_ref().is_some_and(|data| data.starts_with(&prefix))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_message(chat: serde_json::Value, data: serde_json::Value) -> Message {
+        let mut message = serde_json::json!({
+            "message_id": 1,
+            "date": 0,
+            "from": {"id": 1, "is_bot": false, "first_name": "test"},
+            "chat": chat
+        });
+        for (key, value) in data.as_object().unwrap() {
+            message[key] = value.clone();
+        }
+        serde_json::from_value(message).unwrap()
+    }
+
+    #[test]
+    fn test_message_filters() {
+        let private = serde_json::json!({"id": 1, "type": "private", "first_name": "test"});
+        let group =
+            serde_json::json!({"id": -1, "type": "group", "title": "test", "all_members_are_administrators": true});
+        let text = create_message(private.clone(), serde_json::json!({"text": "order 42"}));
+        let photo = create_message(
+            group,
+            serde_json::json!({"photo": [{"file_id": "id", "file_unique_id": "uid", "width": 1, "height": 1}]}),
+        );
+
+        let filter = text_matches(Regex::new(r"^order \d+$").unwrap());
+        assert!(filter.check(&text));
+        assert!(!filter.check(&photo));
+        assert!(is_private().check(&text));
+        assert!(!is_private().check(&photo));
+        assert!(from_users(vec![1, 2]).check(&text));
+        assert!(!from_users(vec![2]).check(&text));
+        assert!(has_photo().check(&photo));
+        assert!(!has_photo().check(&text));
+
+        let filter = is_private().and(from_users(vec![1]));
+        assert!(filter.check(&text));
+        assert!(!filter.check(&photo));
+        let filter = Not(is_private()).or(|message: &Message| message.get_text().is_some());
+        assert!(filter.check(&text));
+        assert!(filter.check(&photo));
+        assert!(!Not(filter).check(&text));
+    }
+
+    #[test]
+    fn test_data_prefix() {
+        let query: CallbackQuery = serde_json::from_value(serde_json::json!({
+            "id": "query",
+            "from": {"id": 1, "is_bot": false, "first_name": "test"},
+            "chat_instance": "instance",
+            "data": "page:2"
+        }))
+        .unwrap();
+        assert!(data_prefix("page:").check(&query));
+        assert!(!data_prefix("item:").check(&query));
+    }
+}
@@ -0,0 +1,42 @@
+//! Messages and updates shared by tests
+
+use crate::types::{Integer, Update};
+use serde_json::{json, Value as JsonValue};
+
+/// Returns a text message sent by the user in a private chat with the bot
+///
+/// ID of a private chat is the same as ID of the user
+pub(crate) fn private_message(user_id: Integer, text: &str) -> JsonValue {
+    message(
+        json!({"id": user_id, "type": "private", "first_name": "test"}),
+        user_id,
+        text,
+    )
+}
+
+/// Returns a text message sent by the user in a group
+///
+/// Group IDs are negative
+pub(crate) fn group_message(chat_id: Integer, user_id: Integer, text: &str) -> JsonValue {
+    assert!(chat_id < 0, "Group ID must be negative");
+    message(
+        json!({"id": chat_id, "type": "group", "title": "test", "all_members_are_administrators": true}),
+        user_id,
+        text,
+    )
+}
+
+fn message(chat: JsonValue, user_id: Integer, text: &str) -> JsonValue {
+    json!({
+        "message_id": 1,
+        "date": 0,
+        "from": {"id": user_id, "is_bot": false, "first_name": "test"},
+        "chat": chat,
+        "text": text
+    })
+}
+
+/// Returns an update with the message
+pub(crate) fn message_update(message: JsonValue) -> Update {
+    serde_json::from_value(json!({"update_id": 1, "message": message})).unwrap()
+}
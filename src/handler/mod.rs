@@ -1,18 +1,109 @@
-use crate::types::Update;
-use futures::{Future, Stream};
+use crate::{
+    api::Api,
+    methods::{DeleteWebhook, SetWebhook},
+    types::Update,
+};
+use futures::StreamExt;
+#[cfg(feature = "native-tls")]
+use hyper::server::accept;
 use hyper::Server;
-use std::net::SocketAddr;
+use std::{future::Future, net::SocketAddr, pin::Pin};
 
+mod access;
+mod chain;
+mod command;
+mod dedup;
+mod dialogue;
+mod dispatcher;
+pub mod filter;
+#[cfg(test)]
+pub(crate) mod fixtures;
+mod offset;
 mod poll;
+mod pool;
 mod queue;
+#[cfg(feature = "redis")]
+mod redis;
+mod session;
+#[cfg(feature = "native-tls")]
+mod tls;
 mod webhook;
 
-pub use self::{poll::*, webhook::*};
+pub use self::{
+    access::*, chain::*, command::*, dedup::*, dialogue::*, dispatcher::*, offset::*, poll::*, pool::*, session::*,
+    webhook::*,
+};
+
+#[cfg(feature = "redis")]
+pub use self::redis::*;
+
+#[cfg(feature = "native-tls")]
+pub use self::tls::*;
 
 /// An update handler
+///
+/// The next update is not handled until the future returned by handle is completed
 pub trait UpdateHandler {
+    /// A future returned by handle
+    ///
+    /// Output is either () or WebhookReply to answer a webhook request with a method call
+    type Future: Future<Output: IntoWebhookReply> + Send + 'static;
+
     /// Handles an update
-    fn handle(&mut self, update: Update);
+    fn handle(&mut self, update: Update) -> Self::Future;
+}
+
+/// Passes an update to the handler within a span when tracing feature is enabled
+pub(crate) async fn process_update<H: UpdateHandler>(handler: &mut H, update: Update) -> <H::Future as Future>::Output {
+    #[cfg(feature = "tracing")]
+    {
+        use tracing::Instrument;
+        let span = crate::trace::update_span(&update);
+        handler.handle(update).instrument(span).await
+    }
+    #[cfg(not(feature = "tracing"))]
+    handler.handle(update).await
+}
+
+/// Allows to use a closure returning a future as a handler
+///
+/// For example: `handle_updates(method, |update| async move { ... })`
+impl<F, R> UpdateHandler for F
+where
+    F: FnMut(Update) -> R,
+    R: Future + Send + 'static,
+    R::Output: IntoWebhookReply,
+{
+    type Future = R;
+
+    fn handle(&mut self, update: Update) -> Self::Future {
+        self(update)
+    }
+}
+
+/// A source of updates
+///
+/// Implemented by UpdatesStream (long polling), WebhookServer and UpdateMethod,
+/// so a handler runs unchanged against any of them:
+/// `handle_updates(api.get_updates(), handler)` or `handle_updates(WebhookServer::new(addr, "/"), handler)`
+pub trait UpdateSource {
+    /// Passes updates to the handler until the source is exhausted or stopped
+    fn run<H>(self, handler: H) -> Pin<Box<dyn Future<Output = ()> + Send>>
+    where
+        H: UpdateHandler + Send + Sync + 'static;
+}
+
+impl UpdateSource for UpdatesStream {
+    fn run<H>(mut self, mut handler: H) -> Pin<Box<dyn Future<Output = ()> + Send>>
+    where
+        H: UpdateHandler + Send + Sync + 'static,
+    {
+        Box::pin(async move {
+            while let Some(update) = self.next().await {
+                process_update(&mut handler, update).await;
+            }
+        })
+    }
 }
 
 /// Defines how to get updates from Telegram
@@ -40,39 +131,334 @@ impl UpdateMethod {
         S: Into<String>,
     {
         Self {
-            kind: UpdateMethodKind::Webhook {
-                addr: addr.into(),
-                path: path.into(),
-            },
+            kind: UpdateMethodKind::Webhook(WebhookServer::new(addr, path)),
+        }
+    }
+
+    /// Secret token passed to setWebhook
+    ///
+    /// Webhook requests without a matching token are rejected
+    /// Has no effect when using long polling
+    pub fn secret_token<S: Into<String>>(mut self, token: S) -> Self {
+        if let UpdateMethodKind::Webhook(server) = self.kind {
+            self.kind = UpdateMethodKind::Webhook(server.secret_token(token));
+        }
+        self
+    }
+
+    /// Calls setWebhook when the server is bound and ready to receive updates
+    ///
+    /// See WebhookServer::set_webhook()
+    /// Has no effect when using long polling
+    pub fn set_webhook(mut self, api: Api, method: SetWebhook) -> Self {
+        if let UpdateMethodKind::Webhook(server) = self.kind {
+            self.kind = UpdateMethodKind::Webhook(server.set_webhook(api, method));
+        }
+        self
+    }
+
+    /// Serve webhook over HTTPS using the given certificate
+    ///
+    /// See WebhookServer::tls()
+    /// Has no effect when using long polling
+    /// Requires native-tls feature
+    #[cfg(feature = "native-tls")]
+    pub fn tls(mut self, config: TlsConfig) -> Self {
+        if let UpdateMethodKind::Webhook(server) = self.kind {
+            self.kind = UpdateMethodKind::Webhook(server.tls(config));
+        }
+        self
+    }
+}
+
+impl From<UpdatesStream> for UpdateMethod {
+    fn from(stream: UpdatesStream) -> Self {
+        UpdateMethod::poll(stream)
+    }
+}
+
+impl From<WebhookServer> for UpdateMethod {
+    fn from(server: WebhookServer) -> Self {
+        Self {
+            kind: UpdateMethodKind::Webhook(server),
         }
     }
 }
 
+impl UpdateSource for UpdateMethod {
+    fn run<H>(self, handler: H) -> Pin<Box<dyn Future<Output = ()> + Send>>
+    where
+        H: UpdateHandler + Send + Sync + 'static,
+    {
+        match self.kind {
+            UpdateMethodKind::Poll(stream) => stream.run(handler),
+            UpdateMethodKind::Webhook(server) => server.run(handler),
+        }
+    }
+}
+
+#[allow(clippy::large_enum_variant)]
 enum UpdateMethodKind {
     Poll(UpdatesStream),
-    Webhook { addr: SocketAddr, path: String },
+    Webhook(WebhookServer),
+}
+
+/// Receives updates using webhook
+pub struct WebhookServer {
+    addr: SocketAddr,
+    path: String,
+    secret_token: Option<String>,
+    set_webhook: Option<(Api, SetWebhook)>,
+    delete_webhook: bool,
+    shutdown: ShutdownHandle,
+    #[cfg(feature = "native-tls")]
+    tls: Option<TlsConfig>,
+}
+
+impl WebhookServer {
+    /// Creates a new server
+    ///
+    /// # Arguments
+    ///
+    /// - addr - Bind address
+    /// - path - URL path for webhook
+    pub fn new<A, S>(addr: A, path: S) -> Self
+    where
+        A: Into<SocketAddr>,
+        S: Into<String>,
+    {
+        WebhookServer {
+            addr: addr.into(),
+            path: path.into(),
+            secret_token: None,
+            set_webhook: None,
+            delete_webhook: false,
+            shutdown: ShutdownHandle::default(),
+            #[cfg(feature = "native-tls")]
+            tls: None,
+        }
+    }
+
+    /// Secret token passed to setWebhook
+    ///
+    /// Webhook requests without a matching token are rejected
+    pub fn secret_token<S: Into<String>>(mut self, token: S) -> Self {
+        self.secret_token = Some(token.into());
+        self
+    }
+
+    /// Calls setWebhook when the server is bound and ready to receive updates
+    ///
+    /// The server is not started when the method fails
+    /// Use TlsConfig::certificate() to upload a self-signed certificate
+    pub fn set_webhook(mut self, api: Api, method: SetWebhook) -> Self {
+        self.set_webhook = Some((api, method));
+        self
+    }
+
+    /// Calls deleteWebhook when the server is stopped using ShutdownHandle
+    ///
+    /// Uses Api passed to set_webhook(), has no effect without it
+    /// Do not enable it when a new instance of the bot is started before the old one is stopped
+    /// Defaults to false
+    pub fn delete_webhook_on_shutdown(mut self, value: bool) -> Self {
+        self.delete_webhook = value;
+        self
+    }
+
+    /// Returns a handle to stop the server
+    pub fn shutdown_handle(&self) -> ShutdownHandle {
+        self.shutdown.clone()
+    }
+
+    /// Serve webhook over HTTPS using the given certificate
+    ///
+    /// Use it to receive updates without a reverse proxy terminating TLS,
+    /// a self-signed certificate has to be uploaded using SetWebhook::certificate()
+    /// Requires native-tls feature
+    #[cfg(feature = "native-tls")]
+    pub fn tls(mut self, config: TlsConfig) -> Self {
+        self.tls = Some(config);
+        self
+    }
+}
+
+impl UpdateSource for WebhookServer {
+    fn run<H>(self, handler: H) -> Pin<Box<dyn Future<Output = ()> + Send>>
+    where
+        H: UpdateHandler + Send + Sync + 'static,
+    {
+        Box::pin(async move {
+            let WebhookServer {
+                addr,
+                path,
+                secret_token,
+                set_webhook,
+                delete_webhook,
+                shutdown,
+                #[cfg(feature = "native-tls")]
+                tls,
+            } = self;
+            let delete_api = match set_webhook {
+                Some((ref api, _)) if delete_webhook => Some(api.clone()),
+                _ => None,
+            };
+            let mut factory = WebhookServiceFactory::new(path, handler);
+            if let Some(secret_token) = secret_token {
+                factory = factory.secret_token(secret_token);
+            }
+            #[cfg(feature = "native-tls")]
+            let served = match tls {
+                Some(tls) => match tls.bind(addr).await {
+                    Ok(incoming) => {
+                        let server = Server::builder(accept::from_stream(incoming))
+                            .serve(factory)
+                            .with_graceful_shutdown(shutdown.wait());
+                        run_server(server, set_webhook).await
+                    }
+                    Err(err) => {
+                        log::error!("Failed to bind {}: {}", addr, err);
+                        false
+                    }
+                },
+                None => serve_plain(addr, factory, shutdown, set_webhook).await,
+            };
+            #[cfg(not(feature = "native-tls"))]
+            let served = serve_plain(addr, factory, shutdown, set_webhook).await;
+            if !served {
+                return;
+            }
+            if let Some(api) = delete_api {
+                if let Err(err) = api.execute(&DeleteWebhook::new()).await {
+                    log::error!("Failed to delete webhook: {}", err);
+                }
+            }
+        })
+    }
+}
+
+/// Serves webhook over plain HTTP, returns false when the server has not been started
+async fn serve_plain(
+    addr: SocketAddr,
+    factory: WebhookServiceFactory,
+    shutdown: ShutdownHandle,
+    set_webhook: Option<(Api, SetWebhook)>,
+) -> bool {
+    match Server::try_bind(&addr) {
+        Ok(builder) => {
+            let server = builder.serve(factory).with_graceful_shutdown(shutdown.wait());
+            run_server(server, set_webhook).await
+        }
+        Err(err) => {
+            log::error!("Failed to bind {}: {}", addr, err);
+            false
+        }
+    }
+}
+
+/// Registers webhook and runs the server until shutdown
+///
+/// Returns false when the server has not been started because setWebhook has failed
+async fn run_server<F>(server: F, set_webhook: Option<(Api, SetWebhook)>) -> bool
+where
+    F: Future<Output = Result<(), hyper::Error>>,
+{
+    if !register_webhook(set_webhook).await {
+        return false;
+    }
+    if let Err(err) = server.await {
+        log::error!("Server error: {}", err)
+    }
+    true
+}
+
+/// Calls setWebhook if it is required, returns false when the call has failed
+async fn register_webhook(set_webhook: Option<(Api, SetWebhook)>) -> bool {
+    match set_webhook {
+        Some((api, method)) => match api.execute(&method).await {
+            Ok(_) => true,
+            Err(err) => {
+                log::error!("Failed to set webhook: {}", err);
+                false
+            }
+        },
+        None => true,
+    }
 }
 
 /// Start getting updates
-pub fn handle_updates<H>(update_method: UpdateMethod, mut handler: H)
+///
+/// Accepts any UpdateSource: UpdateMethod, UpdatesStream or WebhookServer
+pub async fn handle_updates<S, H>(source: S, handler: H)
 where
+    S: UpdateSource,
     H: UpdateHandler + Send + Sync + 'static,
 {
-    match update_method.kind {
-        UpdateMethodKind::Poll(stream) => {
-            tokio::run(
-                stream
-                    .for_each(move |update| {
-                        handler.handle(update);
-                        Ok(())
-                    })
-                    .then(|_| Ok(())),
-            );
+    source.run(handler).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{executor::MockExecutor, handler::fixtures::private_message};
+    use serde_json::json;
+    use std::sync::{Arc, Mutex};
+
+    #[tokio::test]
+    async fn test_update_source() {
+        let executor = MockExecutor::new().with_result(
+            "getUpdates",
+            json!([{"update_id": 1, "message": private_message(1, "hi")}]),
+        );
+        let api = Api::builder("token").executor(executor).build().unwrap();
+        let stream = api.get_updates();
+        let shutdown = stream.shutdown_handle();
+        let updates = Arc::new(Mutex::new(Vec::new()));
+        let handler = {
+            let updates = updates.clone();
+            move |update: Update| {
+                updates.lock().unwrap().push(update.id);
+                shutdown.shutdown();
+                async {}
+            }
+        };
+        handle_updates(stream, handler).await;
+        assert_eq!(*updates.lock().unwrap(), vec![1]);
+    }
+
+    #[tokio::test]
+    async fn test_webhook_lifecycle() {
+        let executor = MockExecutor::new()
+            .with_result("setWebhook", true)
+            .with_result("deleteWebhook", true);
+        let api = Api::builder("token").executor(executor.clone()).build().unwrap();
+        let server = WebhookServer::new(([127, 0, 0, 1], 0), "/")
+            .set_webhook(api, SetWebhook::new("https://example.com/"))
+            .delete_webhook_on_shutdown(true);
+        let shutdown = server.shutdown_handle();
+        let server = tokio::spawn(handle_updates(server, |_update: Update| async {}));
+
+        for _ in 0..100 {
+            if !executor.params("setWebhook").is_empty() {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
         }
-        UpdateMethodKind::Webhook { addr, path } => tokio::run(
-            Server::bind(&addr)
-                .serve(WebhookServiceFactory::new(path, handler))
-                .map_err(|e| log::error!("Server error: {}", e)),
-        ),
+        assert_eq!(executor.params("setWebhook")[0]["url"], "https://example.com/");
+        assert!(executor.params("deleteWebhook").is_empty());
+        shutdown.shutdown();
+        server.await.unwrap();
+        assert_eq!(executor.params("deleteWebhook").len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_webhook_bind_error() {
+        let executor = MockExecutor::new().with_result("setWebhook", true);
+        let api = Api::builder("token").executor(executor.clone()).build().unwrap();
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let server = WebhookServer::new(listener.local_addr().unwrap(), "/")
+            .set_webhook(api, SetWebhook::new("https://example.com/"));
+        handle_updates(server, |_update: Update| async {}).await;
+        assert!(executor.params("setWebhook").is_empty());
     }
 }
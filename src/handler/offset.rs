@@ -0,0 +1,118 @@
+use crate::types::Integer;
+use failure::Error;
+use std::{
+    future::Future,
+    path::PathBuf,
+    pin::Pin,
+    sync::{Arc, Mutex},
+};
+use tokio::fs;
+
+/// A future returned by OffsetStorage
+pub type OffsetFuture<T> = Pin<Box<dyn Future<Output = Result<T, Error>> + Send>>;
+
+/// Keeps the last received update_id between restarts of a bot
+///
+/// UpdatesStream loads the value before the first request
+/// and saves it when all updates of a batch have been taken from the stream
+pub trait OffsetStorage: Send + Sync {
+    /// Returns the saved update_id or None when nothing is saved yet
+    fn load(&self) -> OffsetFuture<Option<Integer>>;
+
+    /// Saves update_id of the last received update
+    fn save(&self, update_id: Integer) -> OffsetFuture<()>;
+}
+
+/// Keeps update_id in memory
+///
+/// Used by default, clones share the same value
+#[derive(Clone, Debug, Default)]
+pub struct InMemoryOffsetStorage {
+    value: Arc<Mutex<Option<Integer>>>,
+}
+
+impl InMemoryOffsetStorage {
+    /// Creates a new storage
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the saved update_id
+    pub fn get(&self) -> Option<Integer> {
+        *self.value.lock().unwrap()
+    }
+}
+
+impl OffsetStorage for InMemoryOffsetStorage {
+    fn load(&self) -> OffsetFuture<Option<Integer>> {
+        let value = self.get();
+        Box::pin(async move { Ok(value) })
+    }
+
+    fn save(&self, update_id: Integer) -> OffsetFuture<()> {
+        *self.value.lock().unwrap() = Some(update_id);
+        Box::pin(async { Ok(()) })
+    }
+}
+
+/// Keeps update_id in a text file
+///
+/// The file is created on the first save, a missing file means that nothing is saved
+#[derive(Clone, Debug)]
+pub struct FileOffsetStorage {
+    path: PathBuf,
+}
+
+impl FileOffsetStorage {
+    /// Creates a new storage
+    pub fn new<P: Into<PathBuf>>(path: P) -> Self {
+        FileOffsetStorage { path: path.into() }
+    }
+}
+
+impl OffsetStorage for FileOffsetStorage {
+    fn load(&self) -> OffsetFuture<Option<Integer>> {
+        let path = self.path.clone();
+        Box::pin(async move {
+            match fs::read_to_string(&path).await {
+                Ok(data) => Ok(Some(data.trim().parse()?)),
+                Err(ref err) if err.kind() == std::io::ErrorKind::NotFound => Ok(None),
+                Err(err) => Err(err.into()),
+            }
+        })
+    }
+
+    fn save(&self, update_id: Integer) -> OffsetFuture<()> {
+        let path = self.path.clone();
+        Box::pin(async move {
+            fs::write(path, update_id.to_string()).await?;
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_in_memory_storage() {
+        let storage = InMemoryOffsetStorage::new();
+        assert_eq!(storage.load().await.unwrap(), None);
+        storage.clone().save(42).await.unwrap();
+        assert_eq!(storage.load().await.unwrap(), Some(42));
+        assert_eq!(storage.get(), Some(42));
+    }
+
+    #[tokio::test]
+    async fn test_file_storage() {
+        let path = std::env::temp_dir().join(format!("tgbot-offset-{}", std::process::id()));
+        let storage = FileOffsetStorage::new(&path);
+        assert_eq!(storage.load().await.unwrap(), None);
+        storage.save(42).await.unwrap();
+        assert_eq!(storage.load().await.unwrap(), Some(42));
+        storage.save(43).await.unwrap();
+        assert_eq!(FileOffsetStorage::new(&path).load().await.unwrap(), Some(43));
+        std::fs::remove_file(&path).unwrap();
+    }
+}
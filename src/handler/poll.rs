@@ -1,28 +1,49 @@
 use crate::{
-    api::Api,
+    api::{Api, ApiError},
+    handler::{InMemoryOffsetStorage, OffsetStorage},
     methods::GetUpdates,
-    types::{AllowedUpdate, Integer, ResponseError, Update},
+    types::{AllowedUpdate, Integer, Update},
 };
-use failure::Error;
-use futures::{task, Async, Future, Poll, Stream};
-use log::error;
+use futures::{future::poll_fn, task::AtomicWaker, Stream};
+use log::{error, warn};
 use std::{
-    cmp::max,
+    cmp::{max, min},
     collections::{HashSet, VecDeque},
+    future::Future,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    task::{Context, Poll},
     time::Duration,
 };
-use tokio_timer::sleep;
+use tokio::time::sleep;
 
 const DEFAULT_LIMIT: Integer = 100;
 const DEFAULT_POLL_TIMEOUT: Integer = 10;
 const DEFAULT_ERROR_TIMEOUT: u64 = 5;
+const DEFAULT_MAX_ERROR_TIMEOUT: u64 = 300;
+const DEFAULT_ERROR_TIMEOUT_FACTOR: u32 = 2;
+const DEFAULT_ERROR_TIMEOUT_JITTER: u32 = 20;
+const DEFAULT_REQUEST_TIMEOUT_MARGIN: u64 = 10;
+
+type UpdatesFuture = Pin<Box<dyn Future<Output = Result<Option<(Integer, Vec<Update>)>, ApiError>> + Send>>;
 
 /// Updates stream used for long polling
 pub struct UpdatesStream {
     api: Api,
     options: UpdatesStreamOptions,
     items: VecDeque<Update>,
-    request: Option<Box<Future<Item = Option<Vec<Update>>, Error = Error> + Send>>,
+    request: Option<UpdatesFuture>,
+    errors: u32,
+    finished: bool,
+    storage: Arc<dyn OffsetStorage>,
+    offset_loaded: bool,
+    saved_offset: Integer,
+    shutdown: ShutdownHandle,
+    retrying: bool,
+    closing: bool,
 }
 
 impl UpdatesStream {
@@ -33,6 +54,14 @@ impl UpdatesStream {
             options: UpdatesStreamOptions::default(),
             items: VecDeque::new(),
             request: None,
+            errors: 0,
+            finished: false,
+            storage: Arc::new(InMemoryOffsetStorage::new()),
+            offset_loaded: false,
+            saved_offset: 0,
+            shutdown: ShutdownHandle::default(),
+            retrying: false,
+            closing: false,
         }
     }
 
@@ -41,6 +70,134 @@ impl UpdatesStream {
         self.options = options;
         self
     }
+
+    /// Set a storage for the offset
+    ///
+    /// The offset is loaded before the first request
+    /// and saved before requesting the next batch, i.e. when all received updates have been taken from the stream
+    /// Failures of the storage are logged and do not stop the stream
+    /// Defaults to InMemoryOffsetStorage
+    pub fn offset_storage<S: OffsetStorage + 'static>(mut self, storage: S) -> Self {
+        self.storage = Arc::new(storage);
+        self
+    }
+
+    pub(crate) fn api(&self) -> &Api {
+        &self.api
+    }
+
+    /// Returns a handle to stop the stream
+    pub fn shutdown_handle(&self) -> ShutdownHandle {
+        self.shutdown.clone()
+    }
+
+    /// Returns the offset to save if it has changed since the last save
+    fn take_offset_to_save(&mut self) -> Option<Integer> {
+        if self.options.offset > self.saved_offset {
+            self.saved_offset = self.options.offset;
+            Some(self.options.offset)
+        } else {
+            None
+        }
+    }
+
+    /// Creates a getUpdates request, loading the offset first time and saving it when it has changed
+    fn get_updates(&mut self) -> UpdatesFuture {
+        let api = self.api.clone();
+        let storage = self.storage.clone();
+        let load = !self.offset_loaded;
+        let save = self.take_offset_to_save();
+        let options = &self.options;
+        let mut offset = options.offset;
+        let method = GetUpdates::default()
+            .limit(options.limit)
+            .timeout(options.poll_timeout)
+            .allowed_updates(options.allowed_updates.clone());
+        let request_timeout = options.get_request_timeout();
+        Box::pin(async move {
+            if load {
+                match storage.load().await {
+                    Ok(Some(saved)) => offset = max(offset, saved),
+                    Ok(None) => {}
+                    Err(err) => error!("Failed to load offset: {}", err),
+                }
+            }
+            if let Some(offset) = save {
+                if let Err(err) = storage.save(offset).await {
+                    error!("Failed to save offset: {}", err);
+                }
+            }
+            let method = method.offset(offset + 1);
+            let items = api.execute_with_timeout(&method, Some(request_timeout)).await?;
+            Ok(Some((offset, items)))
+        })
+    }
+
+    /// Saves the final offset and confirms received updates, so they are not sent again
+    fn close(&mut self) -> UpdatesFuture {
+        let api = self.api.clone();
+        let storage = self.storage.clone();
+        let save = self.take_offset_to_save();
+        let offset = self.options.offset;
+        let request_timeout = self.options.request_timeout_margin;
+        Box::pin(async move {
+            if let Some(offset) = save {
+                if let Err(err) = storage.save(offset).await {
+                    error!("Failed to save offset: {}", err);
+                }
+            }
+            if offset > 0 {
+                let method = GetUpdates::default().offset(offset + 1).limit(1).timeout(0);
+                if let Err(err) = api.execute_with_timeout(&method, Some(request_timeout)).await {
+                    warn!("Failed to confirm received updates: {:?}", err);
+                }
+            }
+            Ok(None)
+        })
+    }
+}
+
+/// Stops UpdatesStream or WebhookServer gracefully
+///
+/// A pending getUpdates request is completed and received updates are yielded,
+/// then the offset is saved and confirmed and the stream ends
+/// A delay after an error is interrupted
+/// WebhookServer stops accepting connections and completes pending requests
+#[derive(Clone, Debug, Default)]
+pub struct ShutdownHandle {
+    inner: Arc<ShutdownState>,
+}
+
+#[derive(Debug, Default)]
+struct ShutdownState {
+    requested: AtomicBool,
+    waker: AtomicWaker,
+}
+
+impl ShutdownHandle {
+    /// Requests the stream to stop
+    pub fn shutdown(&self) {
+        self.inner.requested.store(true, Ordering::SeqCst);
+        self.inner.waker.wake();
+    }
+
+    /// Whether shutdown has been requested
+    pub fn is_shutdown(&self) -> bool {
+        self.inner.requested.load(Ordering::SeqCst)
+    }
+
+    /// Resolves when shutdown has been requested
+    pub(crate) fn wait(&self) -> impl Future<Output = ()> + Send + 'static {
+        let handle = self.clone();
+        poll_fn(move |cx| {
+            handle.inner.waker.register(cx.waker());
+            if handle.is_shutdown() {
+                Poll::Ready(())
+            } else {
+                Poll::Pending
+            }
+        })
+    }
 }
 
 impl From<Api> for UpdatesStream {
@@ -51,65 +208,79 @@ impl From<Api> for UpdatesStream {
 
 impl Stream for UpdatesStream {
     type Item = Update;
-    type Error = Error;
 
-    fn poll(&mut self) -> Poll<Option<Self::Item>, Self::Error> {
-        if let Some(update) = self.items.pop_front() {
-            return Ok(Async::Ready(Some(update)));
-        }
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        loop {
+            if let Some(update) = this.items.pop_front() {
+                return Poll::Ready(Some(update));
+            }
 
-        let options = &mut self.options;
+            if this.finished {
+                return Poll::Ready(None);
+            }
 
-        let should_request = match self.request {
-            Some(ref mut request) => match request.poll() {
-                Ok(Async::Ready(Some(items))) => {
-                    for i in items {
-                        options.offset = max(options.offset, i.id);
-                        self.items.push_back(i);
-                    }
-                    Ok(())
+            this.shutdown.inner.waker.register(cx.waker());
+            if this.shutdown.is_shutdown() && !this.closing && (this.request.is_none() || this.retrying) {
+                this.closing = true;
+                this.retrying = false;
+                this.request = Some(this.close());
+            }
+
+            let request = match this.request {
+                Some(ref mut request) => request,
+                None => {
+                    let request = this.get_updates();
+                    this.request.insert(request)
                 }
-                Ok(Async::Ready(None)) => Ok(()),
-                Ok(Async::NotReady) => return Ok(Async::NotReady),
-                Err(err) => Err(err),
-            },
-            None => Ok(()),
-        };
+            };
+            let result = match request.as_mut().poll(cx) {
+                Poll::Ready(result) => result,
+                Poll::Pending => return Poll::Pending,
+            };
+            this.request = None;
+            this.retrying = false;
 
-        match should_request {
-            Ok(()) => {
-                self.request = Some(Box::new(
-                    self.api
-                        .execute(
-                            &GetUpdates::default()
-                                .offset(options.offset + 1)
-                                .limit(options.limit)
-                                .timeout(options.poll_timeout)
-                                .allowed_updates(options.allowed_updates.clone()),
-                        )
-                        .map(Some),
-                ));
+            if this.closing {
+                this.finished = true;
+                return Poll::Ready(None);
             }
-            Err(err) => {
-                error!("An error has occurred while getting updates: {:?}", err);
-
-                options.error_timeout = Duration::from_secs(
-                    err.downcast::<ResponseError>()
-                        .ok()
-                        .and_then(|err| {
-                            err.parameters
-                                .and_then(|parameters| parameters.retry_after.map(|count| count as u64))
-                        })
-                        .unwrap_or(DEFAULT_ERROR_TIMEOUT),
-                );
-
-                self.request = Some(Box::new(sleep(options.error_timeout).from_err().map(|()| None)));
-            }
-        };
 
-        task::current().notify();
+            let options = &mut this.options;
 
-        Ok(Async::NotReady)
+            match result {
+                Ok(Some((offset, items))) => {
+                    this.errors = 0;
+                    this.offset_loaded = true;
+                    this.saved_offset = max(this.saved_offset, offset);
+                    options.offset = max(options.offset, offset);
+                    for i in items {
+                        options.offset = max(options.offset, i.id);
+                        this.items.push_back(i);
+                    }
+                }
+                Ok(None) => {}
+                Err(err) => {
+                    if is_fatal(&err) {
+                        error!("Stopped getting updates due to a fatal error: {:?}", err);
+                        this.finished = true;
+                        return Poll::Ready(None);
+                    }
+                    this.errors = this.errors.saturating_add(1);
+                    let timeout = options.get_retry_delay(this.errors, &err);
+                    warn!(
+                        "An error has occurred while getting updates, retrying in {:?}: {:?}",
+                        timeout, err
+                    );
+                    // offset is not changed, so updates are requested again from the same position
+                    this.request = Some(Box::pin(async move {
+                        sleep(timeout).await;
+                        Ok(None)
+                    }));
+                    this.retrying = true;
+                }
+            }
+        }
     }
 }
 
@@ -120,6 +291,10 @@ pub struct UpdatesStreamOptions {
     limit: Integer,
     poll_timeout: Integer,
     error_timeout: Duration,
+    max_error_timeout: Duration,
+    error_timeout_factor: u32,
+    error_timeout_jitter: u32,
+    request_timeout_margin: Duration,
     allowed_updates: HashSet<AllowedUpdate>,
 }
 
@@ -145,17 +320,115 @@ impl UpdatesStreamOptions {
 
     /// Timeout in seconds when an error has occurred
     ///
+    /// The timeout is multiplied by error_timeout_factor after each consecutive error up to max_error_timeout
+    /// and reset when updates are received successfully
     /// Defaults to 5
     pub fn error_timeout(mut self, error_timeout: u64) -> Self {
         self.error_timeout = Duration::from_secs(error_timeout);
         self
     }
 
+    /// Maximum timeout in seconds between attempts when errors occur repeatedly
+    ///
+    /// Defaults to 300
+    pub fn max_error_timeout(mut self, max_error_timeout: u64) -> Self {
+        self.max_error_timeout = Duration::from_secs(max_error_timeout);
+        self
+    }
+
+    /// Multiplier applied to the error timeout after each consecutive error
+    ///
+    /// 1 - constant timeout between attempts
+    /// Defaults to 2
+    pub fn error_timeout_factor(mut self, factor: u32) -> Self {
+        self.error_timeout_factor = max(factor, 1);
+        self
+    }
+
+    /// Maximum percentage by which the error timeout is randomly reduced
+    ///
+    /// Spreads retries of several bot instances so that they do not hit the server at the same time
+    /// 0 - no jitter, values above 100 are treated as 100
+    /// retry_after returned by Telegram is never reduced
+    /// Defaults to 20
+    pub fn error_timeout_jitter(mut self, percent: u32) -> Self {
+        self.error_timeout_jitter = min(percent, 100);
+        self
+    }
+
+    /// Number of seconds added to poll timeout to get a request timeout
+    ///
+    /// A request is considered hung when a response is not received in poll_timeout + margin seconds
+    /// Timeout set using Api::with_timeout() is not used for long polling
+    /// Defaults to 10
+    pub fn request_timeout_margin(mut self, margin: u64) -> Self {
+        self.request_timeout_margin = Duration::from_secs(margin);
+        self
+    }
+
     /// Adds a type of updates you want your bot to receive
     pub fn allowed_update(mut self, allowed_update: AllowedUpdate) -> Self {
         self.allowed_updates.insert(allowed_update);
         self
     }
+
+    /// Adds types of updates you want your bot to receive
+    ///
+    /// Updates of other types are not sent by Telegram
+    /// All types except chat_member are received when the list is empty
+    pub fn allowed_updates<I>(mut self, allowed_updates: I) -> Self
+    where
+        I: IntoIterator<Item = AllowedUpdate>,
+    {
+        self.allowed_updates.extend(allowed_updates);
+        self
+    }
+}
+
+impl UpdatesStreamOptions {
+    fn get_request_timeout(&self) -> Duration {
+        Duration::from_secs(max(self.poll_timeout, 0) as u64) + self.request_timeout_margin
+    }
+
+    /// Returns a delay before the next attempt after a number of consecutive errors
+    ///
+    /// retry_after returned by Telegram takes precedence over the exponential backoff
+    fn get_error_timeout(&self, errors: u32, err: &ApiError) -> Duration {
+        if let Some(retry_after) = err.retry_after().filter(|&retry_after| retry_after > 0) {
+            return Duration::from_secs(retry_after as u64);
+        }
+        let factor = self.error_timeout_factor.saturating_pow(errors.saturating_sub(1));
+        let timeout = self.error_timeout.checked_mul(factor).unwrap_or(self.max_error_timeout);
+        min(timeout, self.max_error_timeout)
+    }
+
+    /// Returns the error timeout with jitter applied unless Telegram asked to retry after a specific delay
+    fn get_retry_delay(&self, errors: u32, err: &ApiError) -> Duration {
+        let timeout = self.get_error_timeout(errors, err);
+        match err.retry_after() {
+            Some(retry_after) if retry_after > 0 => timeout,
+            _ => self.apply_jitter(timeout),
+        }
+    }
+
+    /// Randomly reduces a timeout by up to error_timeout_jitter percent
+    fn apply_jitter(&self, timeout: Duration) -> Duration {
+        if self.error_timeout_jitter == 0 {
+            return timeout;
+        }
+        let percent = fastrand::u32(0..=self.error_timeout_jitter);
+        timeout - timeout * percent / 100
+    }
+}
+
+/// Whether polling can not be continued after the error, e.g. when the token is invalid
+fn is_fatal(err: &ApiError) -> bool {
+    let code = match err {
+        ApiError::Response { error, .. } => error.error_code,
+        ApiError::UnexpectedStatus { code, .. } => Integer::from(*code),
+        _ => return false,
+    };
+    code == 401 || code == 404
 }
 
 impl Default for UpdatesStreamOptions {
@@ -165,7 +438,194 @@ impl Default for UpdatesStreamOptions {
             limit: DEFAULT_LIMIT,
             poll_timeout: DEFAULT_POLL_TIMEOUT,
             error_timeout: Duration::from_secs(DEFAULT_ERROR_TIMEOUT),
+            max_error_timeout: Duration::from_secs(DEFAULT_MAX_ERROR_TIMEOUT),
+            error_timeout_factor: DEFAULT_ERROR_TIMEOUT_FACTOR,
+            error_timeout_jitter: DEFAULT_ERROR_TIMEOUT_JITTER,
+            request_timeout_margin: Duration::from_secs(DEFAULT_REQUEST_TIMEOUT_MARGIN),
             allowed_updates: HashSet::new(),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        executor::MockExecutor,
+        handler::fixtures::private_message,
+        types::{ResponseError, ResponseParameters},
+    };
+    use futures::StreamExt;
+    use serde_json::json;
+
+    fn response_error(error_code: Integer) -> ApiError {
+        ApiError::Response {
+            status: error_code as u16,
+            error: ResponseError {
+                description: String::from("error"),
+                error_code,
+                parameters: None,
+            },
+        }
+    }
+
+    #[test]
+    fn test_error_timeout() {
+        let options = UpdatesStreamOptions::default().error_timeout(2).max_error_timeout(10);
+        let err = response_error(502);
+        assert_eq!(options.get_error_timeout(1, &err), Duration::from_secs(2));
+        assert_eq!(options.get_error_timeout(2, &err), Duration::from_secs(4));
+        assert_eq!(options.get_error_timeout(3, &err), Duration::from_secs(8));
+        assert_eq!(options.get_error_timeout(4, &err), Duration::from_secs(10));
+        assert_eq!(options.get_error_timeout(100, &err), Duration::from_secs(10));
+
+        let err = ApiError::Response {
+            status: 429,
+            error: ResponseError {
+                description: String::from("Too Many Requests"),
+                error_code: 429,
+                parameters: Some(ResponseParameters {
+                    migrate_to_chat_id: None,
+                    retry_after: Some(30),
+                }),
+            },
+        };
+        assert_eq!(options.get_error_timeout(1, &err), Duration::from_secs(30));
+        assert_eq!(
+            options.error_timeout_jitter(100).get_retry_delay(1, &err),
+            Duration::from_secs(30)
+        );
+    }
+
+    #[test]
+    fn test_error_timeout_factor() {
+        let options = UpdatesStreamOptions::default()
+            .error_timeout(1)
+            .max_error_timeout(100)
+            .error_timeout_factor(3);
+        let err = response_error(502);
+        assert_eq!(options.get_error_timeout(1, &err), Duration::from_secs(1));
+        assert_eq!(options.get_error_timeout(3, &err), Duration::from_secs(9));
+        assert_eq!(options.get_error_timeout(5, &err), Duration::from_secs(81));
+        assert_eq!(options.get_error_timeout(6, &err), Duration::from_secs(100));
+
+        let options = options.error_timeout_factor(0);
+        assert_eq!(options.get_error_timeout(10, &err), Duration::from_secs(1));
+    }
+
+    #[test]
+    fn test_error_timeout_jitter() {
+        let timeout = Duration::from_secs(10);
+        let options = UpdatesStreamOptions::default().error_timeout_jitter(0);
+        assert_eq!(options.apply_jitter(timeout), timeout);
+
+        let options = options.error_timeout_jitter(50);
+        for _ in 0..100 {
+            let value = options.apply_jitter(timeout);
+            assert!(value >= Duration::from_secs(5) && value <= timeout);
+        }
+
+        let options = options.error_timeout_jitter(1000);
+        assert_eq!(options.error_timeout_jitter, 100);
+        assert!(options.apply_jitter(timeout) <= timeout);
+    }
+
+    #[tokio::test]
+    async fn test_offset_storage() {
+        let executor = MockExecutor::new().with_result(
+            "getUpdates",
+            json!([{"update_id": 11, "message": private_message(1, "hi")}]),
+        );
+        let api = Api::builder("token").executor(executor.clone()).build().unwrap();
+        let storage = InMemoryOffsetStorage::new();
+        storage.save(10).await.unwrap();
+        let mut stream = UpdatesStream::new(api).offset_storage(storage.clone());
+
+        assert_eq!(stream.next().await.unwrap().id, 11);
+        assert_eq!(storage.get(), Some(10));
+        assert_eq!(stream.next().await.unwrap().id, 11);
+        assert_eq!(storage.get(), Some(11));
+
+        let params = executor.params("getUpdates");
+        assert_eq!(params[0]["offset"], 11);
+        assert_eq!(params[1]["offset"], 12);
+    }
+
+    #[tokio::test]
+    async fn test_shutdown() {
+        let executor = MockExecutor::new().with_result(
+            "getUpdates",
+            json!([{"update_id": 11, "message": private_message(1, "hi")}]),
+        );
+        let api = Api::builder("token").executor(executor.clone()).build().unwrap();
+        let storage = InMemoryOffsetStorage::new();
+        let mut stream = UpdatesStream::new(api).offset_storage(storage.clone());
+        let handle = stream.shutdown_handle();
+
+        assert_eq!(stream.next().await.unwrap().id, 11);
+        handle.shutdown();
+        assert!(handle.is_shutdown());
+        assert!(stream.next().await.is_none());
+        assert!(stream.next().await.is_none());
+        assert_eq!(storage.get(), Some(11));
+
+        let params = executor.params("getUpdates");
+        assert_eq!(params.len(), 2);
+        assert_eq!(params[0]["offset"], 1);
+        assert_eq!(params[1]["offset"], 12);
+        assert_eq!(params[1]["limit"], 1);
+        assert_eq!(params[1]["timeout"], 0);
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_interrupts_error_timeout() {
+        let executor = MockExecutor::new().with_error("getUpdates", 500, "Internal Server Error");
+        let api = Api::builder("token").executor(executor.clone()).build().unwrap();
+        let mut stream = UpdatesStream::new(api).options(UpdatesStreamOptions::default().error_timeout(60));
+        let handle = stream.shutdown_handle();
+        tokio::spawn(async move {
+            sleep(Duration::from_millis(50)).await;
+            handle.shutdown();
+        });
+        let result = tokio::time::timeout(Duration::from_secs(5), stream.next()).await;
+        assert!(result.unwrap().is_none());
+        assert_eq!(executor.params("getUpdates").len(), 1);
+    }
+
+    #[test]
+    fn test_is_fatal() {
+        assert!(is_fatal(&response_error(401)));
+        assert!(is_fatal(&response_error(404)));
+        assert!(!is_fatal(&response_error(500)));
+        assert!(!is_fatal(&response_error(429)));
+        assert!(is_fatal(&ApiError::UnexpectedStatus {
+            code: 401,
+            body: Vec::new()
+        }));
+        assert!(!is_fatal(&ApiError::UnexpectedStatus {
+            code: 502,
+            body: Vec::new()
+        }));
+        assert!(!is_fatal(&ApiError::Timeout));
+    }
+
+    #[test]
+    fn test_request_timeout() {
+        let options = UpdatesStreamOptions::default();
+        assert_eq!(options.get_request_timeout(), Duration::from_secs(20));
+        let options = options.poll_timeout(50).request_timeout_margin(5);
+        assert_eq!(options.get_request_timeout(), Duration::from_secs(55));
+        let options = options.poll_timeout(0);
+        assert_eq!(options.get_request_timeout(), Duration::from_secs(5));
+    }
+
+    #[test]
+    fn test_allowed_updates() {
+        let options = UpdatesStreamOptions::default()
+            .allowed_update(AllowedUpdate::Message)
+            .allowed_updates(vec![AllowedUpdate::CallbackQuery, AllowedUpdate::Message]);
+        assert_eq!(options.allowed_updates.len(), 2);
+        assert!(options.allowed_updates.contains(&AllowedUpdate::Message));
+        assert!(options.allowed_updates.contains(&AllowedUpdate::CallbackQuery));
+    }
+}
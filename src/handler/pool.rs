@@ -0,0 +1,124 @@
+use crate::{api::Api, handler::UpdatesStream, types::Update};
+use futures::{stream::select_all, StreamExt};
+use std::future::Future;
+
+/// An update received by one of bots in BotPool
+#[derive(Clone)]
+pub struct BotUpdate {
+    /// Name of the bot given in BotPool::bot()
+    pub bot: String,
+    /// Client of the bot, use it to answer the update
+    pub api: Api,
+    /// The update
+    pub update: Update,
+}
+
+/// Receives updates of several bots in a single process
+///
+/// Updates of all bots are passed to a single handler, tagged with a name of the receiving bot
+/// Create clients using Api::with_token() to share a connection pool between bots
+/// To route updates using Dispatcher, pass `bot_update.update` to it
+#[derive(Default)]
+pub struct BotPool {
+    bots: Vec<(String, UpdatesStream)>,
+}
+
+impl BotPool {
+    /// Creates an empty pool
+    pub fn new() -> Self {
+        BotPool::default()
+    }
+
+    /// Adds a bot
+    ///
+    /// # Arguments
+    ///
+    /// * name - Name of the bot used to tag its updates
+    /// * stream - Api or UpdatesStream with custom options
+    pub fn bot<N, S>(mut self, name: N, stream: S) -> Self
+    where
+        N: Into<String>,
+        S: Into<UpdatesStream>,
+    {
+        self.bots.push((name.into(), stream.into()));
+        self
+    }
+
+    /// Returns a client of the bot with the given name
+    pub fn get(&self, name: &str) -> Option<&Api> {
+        self.bots
+            .iter()
+            .find(|(bot, _)| bot == name)
+            .map(|(_, stream)| stream.api())
+    }
+
+    /// Receives updates until streams of all bots are finished
+    ///
+    /// The next update is not handled until the future returned by the handler is completed
+    pub async fn run<H, F>(self, mut handler: H)
+    where
+        H: FnMut(BotUpdate) -> F,
+        F: Future<Output = ()>,
+    {
+        let streams = self.bots.into_iter().map(|(bot, stream)| {
+            let api = stream.api().clone();
+            stream
+                .map(move |update| BotUpdate {
+                    bot: bot.clone(),
+                    api: api.clone(),
+                    update,
+                })
+                .boxed()
+        });
+        let mut updates = select_all(streams);
+        while let Some(update) = updates.next().await {
+            handler(update).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{executor::MockExecutor, handler::fixtures::private_message};
+    use serde_json::json;
+    use std::{
+        collections::HashMap,
+        sync::{Arc, Mutex},
+    };
+
+    #[tokio::test]
+    async fn test_bot_pool() {
+        let executor = MockExecutor::new().with_result(
+            "getUpdates",
+            json!([{"update_id": 1, "message": private_message(1, "hi")}]),
+        );
+        let api = Api::builder("first-token").executor(executor.clone()).build().unwrap();
+        let first = api.get_updates();
+        let second = api.with_token("second-token").get_updates();
+        let mut handles = HashMap::new();
+        handles.insert("first", first.shutdown_handle());
+        handles.insert("second", second.shutdown_handle());
+        let pool = BotPool::new().bot("first", first).bot("second", second);
+        assert!(pool.get("first").is_some());
+        assert!(pool.get("third").is_none());
+
+        let updates = Arc::new(Mutex::new(Vec::new()));
+        pool.run({
+            let updates = updates.clone();
+            move |bot_update: BotUpdate| {
+                handles[bot_update.bot.as_str()].shutdown();
+                updates.lock().unwrap().push((bot_update.bot, bot_update.update.id));
+                async {}
+            }
+        })
+        .await;
+
+        let mut updates = updates.lock().unwrap().clone();
+        updates.sort();
+        assert_eq!(updates, vec![(String::from("first"), 1), (String::from("second"), 1)]);
+        let requests = executor.requests();
+        assert!(requests.iter().any(|request| request.url.contains("first-token")));
+        assert!(requests.iter().any(|request| request.url.contains("second-token")));
+    }
+}
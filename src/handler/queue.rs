@@ -1,12 +1,22 @@
-use crate::{types::Update, UpdateHandler};
-use futures::{Future, Stream};
-use hyper::rt::spawn;
-use tokio_sync::mpsc;
+use crate::{
+    handler::{process_update, IntoWebhookReply, WebhookReply},
+    types::Update,
+    UpdateHandler,
+};
+use std::{future::Future, pin::Pin};
+use tokio::{
+    spawn,
+    sync::{mpsc, oneshot},
+};
+
+/// An update with a channel to send a webhook reply to
+pub(crate) type QueueItem = (Update, Option<oneshot::Sender<Option<WebhookReply>>>);
 
 /// A lazy updates processing queue.
 pub struct Queue {
-    sender: mpsc::Sender<Update>,
-    prepared_future: Option<Box<Future<Item = (), Error = ()> + Send>>,
+    sender: mpsc::Sender<QueueItem>,
+    has_reply: bool,
+    prepared_future: Option<Pin<Box<dyn Future<Output = ()> + Send>>>,
 }
 
 impl Queue {
@@ -18,24 +28,32 @@ impl Queue {
         H: UpdateHandler + Send + 'static,
     {
         const MAX_UPDATES_IN_QUEUE: usize = 10;
-        let (sender, receiver) = mpsc::channel(MAX_UPDATES_IN_QUEUE);
-        let processing = receiver
-            .map_err(|_| log::error!("Channel receive error"))
-            .for_each(move |update| {
-                update_handler.handle(update);
-                Ok(())
-            });
+        let (sender, mut receiver) = mpsc::channel::<QueueItem>(MAX_UPDATES_IN_QUEUE);
+        let processing = async move {
+            while let Some((update, reply)) = receiver.recv().await {
+                let result = process_update(&mut update_handler, update).await;
+                if let Some(reply) = reply {
+                    let _ = reply.send(result.into_webhook_reply());
+                }
+            }
+        };
         Queue {
             sender,
-            prepared_future: Some(Box::new(processing)),
+            has_reply: <<H::Future as Future>::Output as IntoWebhookReply>::HAS_REPLY,
+            prepared_future: Some(Box::pin(processing)),
         }
     }
 
     /// Clones the underlying sender and gives it away.
-    pub fn get_sender(&self) -> mpsc::Sender<Update> {
+    pub fn get_sender(&self) -> mpsc::Sender<QueueItem> {
         self.sender.clone()
     }
 
+    /// Whether the handler can reply to a webhook request
+    pub fn has_reply(&self) -> bool {
+        self.has_reply
+    }
+
     /// Launches processing of an updates queue.
     ///
     /// # Panics
@@ -0,0 +1,120 @@
+use crate::handler::{ChatKey, SessionFuture, SessionStore};
+use failure::Error;
+use redis::{aio::ConnectionManager, AsyncCommands, Client};
+use serde_json::Value as JsonValue;
+use std::time::Duration;
+
+const DEFAULT_PREFIX: &str = "tgbot:session";
+
+/// Keeps session data in Redis
+///
+/// Each value is stored as a JSON string under `<prefix>:<chat_id>:<user_id>:<name>` key,
+/// `_` is used instead of user_id for keys shared by all users of a chat
+/// Requires redis feature
+#[derive(Clone)]
+pub struct RedisSessionStore {
+    connection: ConnectionManager,
+    prefix: String,
+    ttl: Option<Duration>,
+}
+
+impl RedisSessionStore {
+    /// Creates a store using an existing connection
+    pub fn new(connection: ConnectionManager) -> Self {
+        RedisSessionStore {
+            connection,
+            prefix: String::from(DEFAULT_PREFIX),
+            ttl: None,
+        }
+    }
+
+    /// Connects to Redis at the given URL, e.g. redis://127.0.0.1/
+    ///
+    /// The connection is reestablished automatically when it is lost
+    pub async fn open(url: &str) -> Result<Self, Error> {
+        let client = Client::open(url)?;
+        Ok(RedisSessionStore::new(ConnectionManager::new(client).await?))
+    }
+
+    /// Prefix of keys
+    ///
+    /// Use different prefixes for bots sharing a database
+    /// Defaults to "tgbot:session"
+    pub fn prefix<S: Into<String>>(mut self, prefix: S) -> Self {
+        self.prefix = prefix.into();
+        self
+    }
+
+    /// Time to live of values
+    ///
+    /// A value expires when it is not updated within this time
+    /// Values never expire by default
+    pub fn ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = Some(ttl);
+        self
+    }
+}
+
+fn make_key(prefix: &str, key: ChatKey, name: &str) -> String {
+    match key.user_id {
+        Some(user_id) => format!("{}:{}:{}:{}", prefix, key.chat_id, user_id, name),
+        None => format!("{}:{}:_:{}", prefix, key.chat_id, name),
+    }
+}
+
+impl SessionStore for RedisSessionStore {
+    fn get(&self, key: ChatKey, name: &str) -> SessionFuture<Option<JsonValue>> {
+        let key = make_key(&self.prefix, key, name);
+        let mut connection = self.connection.clone();
+        Box::pin(async move {
+            let value: Option<String> = connection.get(key).await?;
+            Ok(match value {
+                Some(value) => Some(serde_json::from_str(&value)?),
+                None => None,
+            })
+        })
+    }
+
+    fn set(&self, key: ChatKey, name: &str, value: JsonValue) -> SessionFuture<()> {
+        let key = make_key(&self.prefix, key, name);
+        let mut connection = self.connection.clone();
+        let ttl = self.ttl.map(|ttl| ttl.as_secs().max(1));
+        Box::pin(async move {
+            let value = value.to_string();
+            match ttl {
+                Some(ttl) => connection.set_ex::<_, _, ()>(key, value, ttl).await?,
+                None => connection.set::<_, _, ()>(key, value).await?,
+            }
+            Ok(())
+        })
+    }
+
+    fn remove(&self, key: ChatKey, name: &str) -> SessionFuture<()> {
+        let key = make_key(&self.prefix, key, name);
+        let mut connection = self.connection.clone();
+        Box::pin(async move {
+            connection.del::<_, ()>(key).await?;
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::UserId;
+
+    #[test]
+    fn test_make_key() {
+        let key = ChatKey {
+            chat_id: -100,
+            user_id: None,
+        };
+        assert_eq!(make_key(DEFAULT_PREFIX, key, "state"), "tgbot:session:-100:_:state");
+        let key = ChatKey {
+            chat_id: 1,
+            user_id: Some(UserId(2)),
+        };
+        assert_eq!(make_key("bot", key, "state"), "bot:1:2:state");
+    }
+}
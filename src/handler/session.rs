@@ -0,0 +1,210 @@
+use crate::types::{Integer, Update, UpdateKind, UserId};
+use failure::Error;
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value as JsonValue;
+use std::{
+    collections::HashMap,
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Mutex, MutexGuard},
+};
+
+/// A future returned by SessionStore
+pub type SessionFuture<T> = Pin<Box<dyn Future<Output = Result<T, Error>> + Send>>;
+
+/// Identifies a chat or a user in a chat
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct ChatKey {
+    /// ID of the chat
+    pub chat_id: Integer,
+    /// ID of the user when the key is specific to a user
+    pub user_id: Option<UserId>,
+}
+
+impl ChatKey {
+    /// Returns a key of the chat where an update comes from
+    ///
+    /// Messages, channel posts and callback queries with a message are supported
+    ///
+    /// # Arguments
+    ///
+    /// * update - Update
+    /// * per_user - Whether to include ID of the user who sent the update
+    pub fn from_update(update: &Update, per_user: bool) -> Option<ChatKey> {
+        let chat_id = match update.kind {
+            UpdateKind::CallbackQuery(ref query) => query.message.as_ref()?.get_chat_id(),
+            _ => update.get_chat_id()?,
+        };
+        let user_id = if per_user { Some(update.get_user()?.id) } else { None };
+        Some(ChatKey { chat_id, user_id })
+    }
+}
+
+/// Keeps arbitrary data of a chat or a user between updates
+///
+/// Values are identified by a key and a name and stored as JSON,
+/// use Session to get and set typed values
+/// Used by Dialogue to keep states
+pub trait SessionStore: Send + Sync {
+    /// Returns a value or None when it is not set
+    fn get(&self, key: ChatKey, name: &str) -> SessionFuture<Option<JsonValue>>;
+
+    /// Sets a value
+    fn set(&self, key: ChatKey, name: &str, value: JsonValue) -> SessionFuture<()>;
+
+    /// Removes a value
+    fn remove(&self, key: ChatKey, name: &str) -> SessionFuture<()>;
+}
+
+/// Keeps session data in memory
+///
+/// Used by default, clones share the same data
+#[derive(Clone, Debug, Default)]
+pub struct InMemorySessionStore {
+    values: Arc<Mutex<HashMap<(ChatKey, String), JsonValue>>>,
+}
+
+impl InMemorySessionStore {
+    /// Creates a new store
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn values(&self) -> MutexGuard<'_, HashMap<(ChatKey, String), JsonValue>> {
+        match self.values.lock() {
+            Ok(values) => values,
+            Err(err) => err.into_inner(),
+        }
+    }
+}
+
+impl SessionStore for InMemorySessionStore {
+    fn get(&self, key: ChatKey, name: &str) -> SessionFuture<Option<JsonValue>> {
+        let value = self.values().get(&(key, name.to_string())).cloned();
+        Box::pin(async move { Ok(value) })
+    }
+
+    fn set(&self, key: ChatKey, name: &str, value: JsonValue) -> SessionFuture<()> {
+        self.values().insert((key, name.to_string()), value);
+        Box::pin(async { Ok(()) })
+    }
+
+    fn remove(&self, key: ChatKey, name: &str) -> SessionFuture<()> {
+        self.values().remove(&(key, name.to_string()));
+        Box::pin(async { Ok(()) })
+    }
+}
+
+/// Typed access to session data of a chat or a user
+#[derive(Clone)]
+pub struct Session {
+    store: Arc<dyn SessionStore>,
+    key: ChatKey,
+}
+
+impl Session {
+    /// Creates a session for the key
+    pub fn new(store: Arc<dyn SessionStore>, key: ChatKey) -> Self {
+        Session { store, key }
+    }
+
+    /// Returns the key of the session
+    pub fn key(&self) -> ChatKey {
+        self.key
+    }
+
+    /// Returns a value or None when it is not set
+    pub async fn get<T: DeserializeOwned>(&self, name: &str) -> Result<Option<T>, Error> {
+        match self.store.get(self.key, name).await? {
+            Some(value) => Ok(Some(serde_json::from_value(value)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Sets a value
+    ///
+    /// The value is serialized immediately, so it is not borrowed by the returned future
+    pub fn set<T: Serialize>(&self, name: &str, value: &T) -> impl Future<Output = Result<(), Error>> + Send {
+        let future = serde_json::to_value(value).map(|value| self.store.set(self.key, name, value));
+        async move { future?.await }
+    }
+
+    /// Removes a value
+    pub async fn remove(&self, name: &str) -> Result<(), Error> {
+        self.store.remove(self.key, name).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::handler::fixtures::{group_message, message_update};
+
+    #[test]
+    fn test_chat_key() {
+        let update = message_update(group_message(-1, 2, "text"));
+        assert_eq!(
+            ChatKey::from_update(&update, false),
+            Some(ChatKey {
+                chat_id: -1,
+                user_id: None
+            })
+        );
+        assert_eq!(
+            ChatKey::from_update(&update, true),
+            Some(ChatKey {
+                chat_id: -1,
+                user_id: Some(UserId(2))
+            })
+        );
+        let update: Update = serde_json::from_value(serde_json::json!({"update_id": 1, "unknown": {}})).unwrap();
+        assert!(ChatKey::from_update(&update, false).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_session() {
+        let store = Arc::new(InMemorySessionStore::new());
+        let key = ChatKey {
+            chat_id: 1,
+            user_id: None,
+        };
+        let session = Session::new(store.clone(), key);
+        let other = Session::new(
+            store,
+            ChatKey {
+                chat_id: 1,
+                user_id: Some(UserId(1)),
+            },
+        );
+        assert_eq!(session.get::<Vec<String>>("items").await.unwrap(), None);
+        session.set("items", &vec!["a", "b"]).await.unwrap();
+        assert_eq!(
+            session.get::<Vec<String>>("items").await.unwrap(),
+            Some(vec![String::from("a"), String::from("b")])
+        );
+        assert!(session.get::<Integer>("items").await.is_err());
+        assert_eq!(other.get::<Vec<String>>("items").await.unwrap(), None);
+        session.remove("items").await.unwrap();
+        assert_eq!(session.get::<Vec<String>>("items").await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_poisoned_store() {
+        let store = InMemorySessionStore::new();
+        let key = ChatKey {
+            chat_id: 1,
+            user_id: None,
+        };
+        store.set(key, "value", JsonValue::from(1)).await.unwrap();
+        let poisoned = store.clone();
+        let _ = std::thread::spawn(move || {
+            let _values = poisoned.values.lock().unwrap();
+            panic!("poison");
+        })
+        .join();
+        assert!(store.values.is_poisoned());
+        assert_eq!(store.get(key, "value").await.unwrap(), Some(JsonValue::from(1)));
+        store.remove(key, "value").await.unwrap();
+        assert_eq!(store.get(key, "value").await.unwrap(), None);
+    }
+}
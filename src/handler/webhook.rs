@@ -1,19 +1,33 @@
-use crate::{handler::queue::Queue, types::Update, UpdateHandler};
-use futures::{
-    future::{ok, Either},
-    Future, Sink, Stream,
+use crate::{
+    handler::{
+        process_update,
+        queue::{Queue, QueueItem},
+    },
+    methods::Method as ApiMethod,
+    UpdateHandler,
 };
+use failure::Error as FailureError;
 use hyper::{
-    header::{HeaderValue, ALLOW},
-    service::{MakeService, Service},
+    body::to_bytes,
+    header::{HeaderValue, ALLOW, CONTENT_TYPE},
+    service::Service,
     Body, Error, Method, Request, Response, StatusCode,
 };
-use std::{error::Error as StdError, fmt};
-use tokio_sync::mpsc;
+use std::{
+    error::Error as StdError,
+    fmt,
+    future::{ready, Future, Ready},
+    pin::Pin,
+    task::{Context, Poll},
+};
+use tokio::sync::{mpsc, oneshot};
+
+const SECRET_TOKEN_HEADER: &str = "X-Telegram-Bot-Api-Secret-Token";
 
 /// Creates a webhook service
 pub struct WebhookServiceFactory {
     path: String,
+    secret_token: Option<String>,
     queue: Queue,
 }
 
@@ -27,7 +41,36 @@ impl WebhookServiceFactory {
         let queue = Queue::prepare(update_handler);
         WebhookServiceFactory {
             path: path.into(),
+            secret_token: None,
+            queue,
+        }
+    }
+
+    /// Secret token passed to setWebhook
+    ///
+    /// Requests without a matching X-Telegram-Bot-Api-Secret-Token header
+    /// are rejected with 401 Unauthorized
+    pub fn secret_token<S: Into<String>>(mut self, secret_token: S) -> Self {
+        self.secret_token = Some(secret_token.into());
+        self
+    }
+
+    /// Creates a service which passes updates to the handler
+    ///
+    /// Use it to mount the webhook in an existing hyper or tower based application,
+    /// e.g. as a route of axum router
+    /// The handler is spawned on the first call, so it must be called within a tokio runtime
+    pub fn create_service(&mut self) -> WebhookService {
+        let path = self.path.clone();
+        let secret_token = self.secret_token.clone();
+        let queue = self.queue.get_sender();
+        let has_reply = self.queue.has_reply();
+        self.queue.launch();
+        WebhookService {
+            path,
+            secret_token,
             queue,
+            has_reply,
         }
     }
 }
@@ -44,76 +87,322 @@ impl fmt::Display for WebhookServiceFactoryError {
 
 impl StdError for WebhookServiceFactoryError {}
 
-impl<Ctx> MakeService<Ctx> for WebhookServiceFactory {
-    type ReqBody = Body;
-    type ResBody = Body;
-    type Error = Error;
-    type Service = WebhookService;
-    type Future = Box<Future<Item = Self::Service, Error = Self::MakeError> + Send>;
-    type MakeError = WebhookServiceFactoryError;
+impl<T> Service<T> for WebhookServiceFactory {
+    type Response = WebhookService;
+    type Error = WebhookServiceFactoryError;
+    type Future = Ready<Result<Self::Response, Self::Error>>;
 
-    fn make_service(&mut self, _ctx: Ctx) -> Self::Future {
-        let path = self.path.clone();
-        let queue = self.queue.get_sender();
-        self.queue.launch();
-        Box::new(ok(WebhookService { path, queue }))
+    fn poll_ready(&mut self, _cx: &mut Context) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, _target: T) -> Self::Future {
+        ready(Ok(self.create_service()))
     }
 }
 
 /// Webhook service
+///
+/// Clones share the same handler
+#[derive(Clone)]
 pub struct WebhookService {
     path: String,
-    queue: mpsc::Sender<Update>,
+    secret_token: Option<String>,
+    queue: mpsc::Sender<QueueItem>,
+    has_reply: bool,
+}
+
+impl WebhookService {
+    fn is_authorized(&self, req: &Request<Body>) -> bool {
+        match self.secret_token {
+            Some(ref expected) => req
+                .headers()
+                .get(SECRET_TOKEN_HEADER)
+                .map(|actual| constant_time_eq(expected.as_bytes(), actual.as_bytes()))
+                .unwrap_or(false),
+            None => true,
+        }
+    }
+}
+
+/// A method call sent in response to a webhook request
+///
+/// Saves a request to Bot API, but the result of the method is not available
+/// and the webhook request is not answered until the handler completes
+#[derive(Clone, Debug)]
+pub struct WebhookReply {
+    body: Vec<u8>,
+}
+
+impl WebhookReply {
+    /// Creates a reply with the given method
+    ///
+    /// Fails when the method is invalid or uploads files
+    pub fn new<M: ApiMethod>(method: &M) -> Result<Self, FailureError> {
+        Ok(WebhookReply {
+            body: method.get_request()?.into_webhook_reply()?,
+        })
+    }
+
+    /// Returns a JSON body of the reply
+    ///
+    /// Send it with application/json content type
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.body
+    }
+}
+
+/// Handles a single update received in a webhook request body
+///
+/// Use it in serverless functions (AWS Lambda, Cloud Functions, etc) where no long-running server exists:
+/// the future resolves when the handler has completed
+/// Returns a reply of the handler to send in response body, see WebhookReply::into_bytes()
+/// Fails when the body is not a valid update
+pub async fn handle_webhook_body<H: UpdateHandler>(
+    handler: &mut H,
+    body: &[u8],
+) -> Result<Option<WebhookReply>, serde_json::Error> {
+    let update = serde_json::from_slice(body)?;
+    Ok(process_update(handler, update).await.into_webhook_reply())
+}
+
+/// Output of UpdateHandler
+pub trait IntoWebhookReply: Send + 'static {
+    /// Whether a webhook request has to wait for a reply of the handler
+    const HAS_REPLY: bool;
+
+    /// Returns a method call to send in response to a webhook request
+    fn into_webhook_reply(self) -> Option<WebhookReply>;
+}
+
+impl IntoWebhookReply for () {
+    const HAS_REPLY: bool = false;
+
+    fn into_webhook_reply(self) -> Option<WebhookReply> {
+        None
+    }
+}
+
+impl IntoWebhookReply for WebhookReply {
+    const HAS_REPLY: bool = true;
+
+    fn into_webhook_reply(self) -> Option<WebhookReply> {
+        Some(self)
+    }
 }
 
-fn put_on_a_queue(
+impl IntoWebhookReply for Option<WebhookReply> {
+    const HAS_REPLY: bool = true;
+
+    fn into_webhook_reply(self) -> Option<WebhookReply> {
+        self
+    }
+}
+
+/// Compares two byte strings in a time which does not depend on their contents
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b.iter()).fold(0, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+async fn put_on_a_queue(
     request: Request<Body>,
-    queue: mpsc::Sender<Update>,
-) -> impl Future<Item = Response<Body>, Error = Error> {
-    request
-        .into_body()
-        .concat2()
-        .and_then(move |body| match serde_json::from_slice(&body) {
-            Ok(update) => Either::A(queue.send(update).then(|res| {
-                if res.is_err() {
-                    log::warn!("The receiving end has been dropped");
-                    Ok(Response::builder()
-                        .status(StatusCode::INTERNAL_SERVER_ERROR)
-                        .body(Body::empty())
-                        .expect("Can't construct an INTERNAL_SERVER_ERROR response"))
-                } else {
-                    Ok(Response::new(Body::empty()))
-                }
-            })),
-            Err(err) => Either::B(ok(Response::builder()
+    queue: mpsc::Sender<QueueItem>,
+    has_reply: bool,
+) -> Result<Response<Body>, Error> {
+    let body = to_bytes(request.into_body()).await?;
+    let update = match serde_json::from_slice(&body) {
+        Ok(update) => update,
+        Err(err) => {
+            return Ok(Response::builder()
                 .status(StatusCode::BAD_REQUEST)
                 .body(Body::from(err.to_string()))
-                .expect("Can't construct a BAD_REQUEST response"))),
-        })
+                .expect("Can't construct a BAD_REQUEST response"))
+        }
+    };
+    let (reply_sender, reply_receiver) = if has_reply {
+        let (sender, receiver) = oneshot::channel();
+        (Some(sender), Some(receiver))
+    } else {
+        (None, None)
+    };
+    if queue.send((update, reply_sender)).await.is_err() {
+        log::warn!("The receiving end has been dropped");
+        return Ok(internal_server_error());
+    }
+    Ok(match reply_receiver {
+        Some(receiver) => match receiver.await {
+            Ok(Some(reply)) => Response::builder()
+                .header(CONTENT_TYPE, HeaderValue::from_static("application/json"))
+                .body(Body::from(reply.body))
+                .expect("Can't construct a response with a reply"),
+            Ok(None) => Response::new(Body::empty()),
+            Err(_) => {
+                log::warn!("The handler has been dropped before replying");
+                internal_server_error()
+            }
+        },
+        None => Response::new(Body::empty()),
+    })
 }
 
-impl Service for WebhookService {
-    type ReqBody = Body;
-    type ResBody = Body;
+fn internal_server_error() -> Response<Body> {
+    Response::builder()
+        .status(StatusCode::INTERNAL_SERVER_ERROR)
+        .body(Body::empty())
+        .expect("Can't construct an INTERNAL_SERVER_ERROR response")
+}
+
+impl Service<Request<Body>> for WebhookService {
+    type Response = Response<Body>;
     type Error = Error;
-    type Future = Box<Future<Item = Response<Body>, Error = Error> + Send>;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
 
-    fn call(&mut self, req: Request<Self::ReqBody>) -> Self::Future {
+    fn call(&mut self, req: Request<Body>) -> Self::Future {
         if let Method::POST = *req.method() {
-            if req.uri().path() == self.path {
-                Box::new(put_on_a_queue(req, self.queue.clone()))
-            } else {
-                Box::new(ok(Response::builder()
+            if req.uri().path() != self.path {
+                Box::pin(ready(Ok(Response::builder()
                     .status(StatusCode::NOT_FOUND)
                     .body(Body::empty())
-                    .expect("Can't construct a NOT_FOUND response")))
+                    .expect("Can't construct a NOT_FOUND response"))))
+            } else if !self.is_authorized(&req) {
+                Box::pin(ready(Ok(Response::builder()
+                    .status(StatusCode::UNAUTHORIZED)
+                    .body(Body::empty())
+                    .expect("Can't construct an UNAUTHORIZED response"))))
+            } else {
+                Box::pin(put_on_a_queue(req, self.queue.clone(), self.has_reply))
             }
         } else {
-            Box::new(ok(Response::builder()
+            Box::pin(ready(Ok(Response::builder()
                 .status(StatusCode::METHOD_NOT_ALLOWED)
                 .header(ALLOW, HeaderValue::from_static("POST"))
                 .body(Body::empty())
-                .expect("Can't construct a METHOD_NOT_ALLOWED response")))
+                .expect("Can't construct a METHOD_NOT_ALLOWED response"))))
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{methods::SendMessage, types::Update};
+
+    #[test]
+    fn test_constant_time_eq() {
+        assert!(constant_time_eq(b"token", b"token"));
+        assert!(!constant_time_eq(b"token", b"tokem"));
+        assert!(!constant_time_eq(b"token", b"token1"));
+        assert!(!constant_time_eq(b"token", b""));
+    }
+
+    #[tokio::test]
+    async fn test_create_service() {
+        let (sender, mut receiver) = mpsc::channel(1);
+        let mut factory = WebhookServiceFactory::new("/webhook", move |update: Update| {
+            let sender = sender.clone();
+            async move {
+                sender.send(update.id).await.unwrap();
+            }
+        });
+        let mut service = factory.create_service().clone();
+        let create_request = |path| {
+            Request::post(path)
+                .body(Body::from(r#"{"update_id": 1, "unknown": {}}"#))
+                .unwrap()
+        };
+
+        let rep = service.call(create_request("/")).await.unwrap();
+        assert_eq!(rep.status(), StatusCode::NOT_FOUND);
+        let rep = service.call(create_request("/webhook")).await.unwrap();
+        assert_eq!(rep.status(), StatusCode::OK);
+        assert_eq!(receiver.recv().await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_secret_token() {
+        let (queue, mut receiver) = mpsc::channel(1);
+        let mut service = WebhookService {
+            path: String::from("/"),
+            secret_token: Some(String::from("secret")),
+            queue,
+            has_reply: false,
+        };
+        let create_request = |token: Option<&str>| {
+            let mut builder = Request::post("/");
+            if let Some(token) = token {
+                builder = builder.header(SECRET_TOKEN_HEADER, token);
+            }
+            builder.body(Body::from(r#"{"update_id": 1, "unknown": {}}"#)).unwrap()
+        };
+
+        let rep = service.call(create_request(None)).await.unwrap();
+        assert_eq!(rep.status(), StatusCode::UNAUTHORIZED);
+        let rep = service.call(create_request(Some("wrong"))).await.unwrap();
+        assert_eq!(rep.status(), StatusCode::UNAUTHORIZED);
+        assert!(receiver.try_recv().is_err());
+
+        let rep = service.call(create_request(Some("secret"))).await.unwrap();
+        assert_eq!(rep.status(), StatusCode::OK);
+        assert_eq!(receiver.recv().await.unwrap().0.id, 1);
+    }
+
+    #[tokio::test]
+    async fn test_reply() {
+        let mut factory = WebhookServiceFactory::new("/", |update: Update| async move {
+            if update.id == 1 {
+                Some(WebhookReply::new(&SendMessage::new(1, "hi")).unwrap())
+            } else {
+                None
+            }
+        });
+        let mut service = factory.create_service();
+        let create_request = |id: i64| {
+            Request::post("/")
+                .body(Body::from(format!(r#"{{"update_id": {}, "unknown": {{}}}}"#, id)))
+                .unwrap()
+        };
+
+        let rep = service.call(create_request(1)).await.unwrap();
+        assert_eq!(rep.status(), StatusCode::OK);
+        assert_eq!(rep.headers()[CONTENT_TYPE], "application/json");
+        let body: serde_json::Value = serde_json::from_slice(&to_bytes(rep.into_body()).await.unwrap()).unwrap();
+        assert_eq!(
+            body,
+            serde_json::json!({"method": "sendMessage", "chat_id": 1, "text": "hi"})
+        );
+
+        let rep = service.call(create_request(2)).await.unwrap();
+        assert_eq!(rep.status(), StatusCode::OK);
+        assert!(to_bytes(rep.into_body()).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_handle_webhook_body() {
+        let mut handler = |update: Update| async move {
+            if update.id == 1 {
+                Some(WebhookReply::new(&SendMessage::new(1, "hi")).unwrap())
+            } else {
+                None
+            }
+        };
+
+        let reply = handle_webhook_body(&mut handler, br#"{"update_id": 1, "unknown": {}}"#)
+            .await
+            .unwrap()
+            .unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&reply.into_bytes()).unwrap();
+        assert_eq!(
+            body,
+            serde_json::json!({"method": "sendMessage", "chat_id": 1, "text": "hi"})
+        );
+        assert!(handle_webhook_body(&mut handler, br#"{"update_id": 2, "unknown": {}}"#)
+            .await
+            .unwrap()
+            .is_none());
+        assert!(handle_webhook_body(&mut handler, b"{}").await.is_err());
+    }
+}
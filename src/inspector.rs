@@ -0,0 +1,146 @@
+use crate::{
+    methods::{FormValue, Request, RequestBody},
+    types::{InputFile, InputFileKind},
+};
+
+const REDACTED_TOKEN: &str = "<token>";
+
+/// Inspects requests sent by Api and raw responses
+///
+/// Use it to see what was actually sent when a method fails unexpectedly
+/// Bot token in request URL and body is replaced with `<token>`
+pub trait Inspector: Send + Sync {
+    /// Called before a request is sent, including retries
+    fn inspect_request(&self, _request: &Request) {}
+
+    /// Called when a response is received, before it is parsed
+    fn inspect_response(&self, _request: &Request, _status: u16, _body: &[u8]) {}
+}
+
+/// Logs requests and responses at debug level
+#[derive(Clone, Copy, Debug, Default)]
+pub struct LogInspector;
+
+impl Inspector for LogInspector {
+    fn inspect_request(&self, request: &Request) {
+        log::debug!("Sending request: {}", format_request(request));
+    }
+
+    fn inspect_response(&self, request: &Request, status: u16, body: &[u8]) {
+        log::debug!(
+            "Got response ({}) for {}: {}",
+            status,
+            request.url,
+            String::from_utf8_lossy(body)
+        );
+    }
+}
+
+/// Formats a request as method, URL and body
+///
+/// JSON is printed as is, multipart fields are listed as name=value,
+/// contents of uploaded files are never printed
+fn format_request(request: &Request) -> String {
+    let body = match request.body {
+        RequestBody::Json(ref data) => String::from_utf8_lossy(data).into_owned(),
+        RequestBody::Multipart(ref form) => form
+            .fields()
+            .iter()
+            .map(|(name, value)| match value {
+                FormValue::Text(value) => format!("{}={}", name, value),
+                FormValue::File(file) => format!("{}={}", name, format_file(file)),
+            })
+            .collect::<Vec<_>>()
+            .join(" "),
+        RequestBody::Empty => return format!("{:?} {}", request.method, request.url),
+    };
+    format!("{:?} {} {}", request.method, request.url, body)
+}
+
+fn format_file(file: &InputFile) -> String {
+    match file.kind {
+        InputFileKind::Id(ref file_id) => file_id.clone(),
+        InputFileKind::Url(ref url) => url.clone(),
+        InputFileKind::Path(ref file) => format!("<file {}>", file.file_name),
+        InputFileKind::Bytes(ref file) => format!("<file {}, {} bytes>", file.file_name, file.data.len()),
+        InputFileKind::Reader(ref file) => format!("<file {}>", file.file_name),
+    }
+}
+
+/// Returns a copy of the request safe to be logged
+pub(crate) fn redact_request(request: &Request, token: &str) -> Request {
+    let mut request = request.clone();
+    request.url = redact_token(&request.url, token);
+    match request.body {
+        RequestBody::Json(ref mut data) => {
+            if let Ok(json) = std::str::from_utf8(data) {
+                *data = redact_token(json, token).into_bytes();
+            }
+        }
+        RequestBody::Multipart(ref mut form) => {
+            for (_, value) in form.fields.iter_mut() {
+                if let FormValue::Text(ref mut text) = value {
+                    *text = redact_token(text, token);
+                }
+            }
+        }
+        RequestBody::Empty => {}
+    }
+    request
+}
+
+/// Replaces all occurrences of the token in a string
+pub(crate) fn redact_token(value: &str, token: &str) -> String {
+    if token.is_empty() {
+        value.to_string()
+    } else {
+        value.replace(token, REDACTED_TOKEN)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::methods::{GetMe, Method, SendMessage, SendPhoto, DEFAULT_BASE_URL};
+
+    #[test]
+    fn test_redact_token() {
+        assert_eq!(
+            redact_token("https://api.telegram.org/bot123:abc/getMe", "123:abc"),
+            "https://api.telegram.org/bot<token>/getMe"
+        );
+        assert_eq!(
+            redact_token("https://api.telegram.org/file/bot123:abc/photo.jpg", "123:abc"),
+            "https://api.telegram.org/file/bot<token>/photo.jpg"
+        );
+        assert_eq!(redact_token("https://example.com", ""), "https://example.com");
+    }
+
+    #[test]
+    fn test_format_request() {
+        let request = GetMe.get_request().unwrap().build(DEFAULT_BASE_URL, "123:abc");
+        assert_eq!(
+            format_request(&redact_request(&request, "123:abc")),
+            "Get https://api.telegram.org/bot<token>/getMe"
+        );
+
+        let request = SendMessage::new(1, "token is 123:abc")
+            .get_request()
+            .unwrap()
+            .build(DEFAULT_BASE_URL, "123:abc");
+        assert_eq!(
+            format_request(&redact_request(&request, "123:abc")),
+            r#"Post https://api.telegram.org/bot<token>/sendMessage {"chat_id":1,"text":"token is <token>"}"#
+        );
+
+        let request = SendPhoto::new(1, InputFile::bytes("photo.png", vec![1, 2, 3]))
+            .caption("123:abc")
+            .get_request()
+            .unwrap()
+            .build(DEFAULT_BASE_URL, "123:abc");
+        assert_eq!(
+            format_request(&redact_request(&request, "123:abc")),
+            "Post https://api.telegram.org/bot<token>/sendPhoto caption=<token> chat_id=1 photo=<file photo.png, 3 bytes>"
+        );
+    }
+}
@@ -1,9 +1,24 @@
 //! A Telegram Bot API client library
+//!
+//! Bot API evolves quickly, so enums which are likely to get new variants
+//! (such as `UpdateKind` and `MessageData`) are marked as `#[non_exhaustive]`
+//! A match on them must contain a wildcard arm
 #![warn(missing_docs)]
+// failure_derive generates impl blocks inside of constants
+#![allow(non_local_definitions)]
+#![allow(clippy::doc_overindented_list_items)]
 
 mod api;
-mod executor;
 mod handler;
+mod inspector;
+mod metrics;
+mod middleware;
+mod rate_limit;
+#[cfg(feature = "tracing")]
+mod trace;
+
+/// HTTP executors used to send requests
+pub mod executor;
 
 /// Methods available in the Bot API
 pub mod methods;
@@ -14,4 +29,11 @@ pub mod types;
 /// A "prelude" for users of the library
 pub mod prelude;
 
-pub use self::{api::*, handler::*};
+pub use self::{
+    api::*,
+    handler::*,
+    inspector::{Inspector, LogInspector},
+    metrics::{Metrics, RequestOutcome},
+    middleware::Middleware,
+    rate_limit::RateLimit,
+};
@@ -2,6 +2,9 @@ use crate::{methods::method::*, types::Integer};
 use failure::Error;
 use serde::Serialize;
 
+/// Maximum length of a callback query answer text
+pub const MAX_CALLBACK_ANSWER_TEXT_LENGTH: usize = 200;
+
 /// Send answer to callback query sent from inline keyboard
 ///
 /// The answer will be displayed to the user as a notification at the top of the chat screen or as an alert
@@ -37,6 +40,26 @@ impl AnswerCallbackQuery {
         }
     }
 
+    /// Creates a new AnswerCallbackQuery which shows a notification at the top of the chat screen
+    ///
+    /// # Arguments
+    ///
+    /// * callback_query_id - Unique identifier for the query to be answered
+    /// * text - Text of the notification, 0-200 characters
+    pub fn toast<I: Into<String>, T: Into<String>>(callback_query_id: I, text: T) -> Self {
+        AnswerCallbackQuery::new(callback_query_id).text(text)
+    }
+
+    /// Creates a new AnswerCallbackQuery which shows an alert
+    ///
+    /// # Arguments
+    ///
+    /// * callback_query_id - Unique identifier for the query to be answered
+    /// * text - Text of the alert, 0-200 characters
+    pub fn alert<I: Into<String>, T: Into<String>>(callback_query_id: I, text: T) -> Self {
+        AnswerCallbackQuery::new(callback_query_id).text(text).show_alert(true)
+    }
+
     /// Text of the notification
     ///
     /// If not specified, nothing will be shown to the user, 0-200 characters
@@ -79,6 +102,46 @@ impl Method for AnswerCallbackQuery {
     type Response = bool;
 
     fn get_request(&self) -> Result<RequestBuilder, Error> {
+        if let Some(ref text) = self.text {
+            check_length("text", text, MAX_CALLBACK_ANSWER_TEXT_LENGTH)?;
+        }
         RequestBuilder::json("answerCallbackQuery", &self)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::methods::DEFAULT_BASE_URL;
+    use serde_json::Value;
+
+    fn request_data(method: AnswerCallbackQuery) -> Value {
+        let req = method.get_request().unwrap().build(DEFAULT_BASE_URL, "token");
+        assert_eq!(req.url, "https://api.telegram.org/bottoken/answerCallbackQuery");
+        match req.body {
+            RequestBody::Json(data) => serde_json::from_slice(&data).unwrap(),
+            data => panic!("Unexpected request data: {:?}", data),
+        }
+    }
+
+    #[test]
+    fn test_answer_callback_query() {
+        let data = request_data(AnswerCallbackQuery::toast("id", "text"));
+        assert_eq!(data["callback_query_id"], "id");
+        assert_eq!(data["text"], "text");
+        assert!(data.get("show_alert").is_none());
+
+        let data = request_data(AnswerCallbackQuery::alert("id", "text").cache_time(10));
+        assert_eq!(data["text"], "text");
+        assert_eq!(data["show_alert"], true);
+        assert_eq!(data["cache_time"], 10);
+
+        let data = request_data(AnswerCallbackQuery::new("id").url("https://t.me/bot?start=game"));
+        assert_eq!(data["url"], "https://t.me/bot?start=game");
+        assert!(data.get("text").is_none());
+
+        let text = "a".repeat(MAX_CALLBACK_ANSWER_TEXT_LENGTH + 1);
+        let err = AnswerCallbackQuery::toast("id", text).get_request().unwrap_err();
+        assert_eq!(err.to_string(), "Field \"text\" is longer than 200 characters");
+    }
+}
@@ -1,10 +1,13 @@
 use crate::{
     methods::method::*,
-    types::{InlineQueryResult, Integer},
+    types::{InlineQueryResult, InlineQueryResultsButton, Integer},
 };
 use failure::Error;
 use serde::Serialize;
 
+/// Maximum number of results in an answer to an inline query
+pub const MAX_INLINE_QUERY_RESULTS: usize = 50;
+
 /// Use this method to send answers to an inline query
 ///
 /// No more than 50 results per query are allowed
@@ -19,6 +22,8 @@ pub struct AnswerInlineQuery {
     #[serde(skip_serializing_if = "Option::is_none")]
     next_offset: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    button: Option<InlineQueryResultsButton>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     switch_pm_text: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     switch_pm_parameter: Option<String>,
@@ -38,6 +43,7 @@ impl AnswerInlineQuery {
             cache_time: None,
             is_personal: None,
             next_offset: None,
+            button: None,
             switch_pm_text: None,
             switch_pm_parameter: None,
         }
@@ -59,8 +65,10 @@ impl AnswerInlineQuery {
         self
     }
 
-    /// Offset that a clien should send in the next query with the same text to receive more results
+    /// Offset that a client should send in the next query with the same text to receive more results
     ///
+    /// The offset is passed back in InlineQuery::offset,
+    /// e.g. pass an identifier of the last result to continue from it
     /// Pass an empty string if there are no more results or if you don‘t support pagination
     /// Offset length can’t exceed 64 bytes
     pub fn next_offset<S: Into<String>>(mut self, next_offset: S) -> Self {
@@ -68,6 +76,14 @@ impl AnswerInlineQuery {
         self
     }
 
+    /// Button to be shown above inline query results
+    ///
+    /// Supersedes switch_pm_text and switch_pm_parameter
+    pub fn button(mut self, button: InlineQueryResultsButton) -> Self {
+        self.button = Some(button);
+        self
+    }
+
     /// Clients will display a button with specified text that switches the user
     /// to a private chat with the bot and sends the bot a
     /// start message with the parameter switch_pm_parameter
@@ -98,6 +114,65 @@ impl Method for AnswerInlineQuery {
     type Response = bool;
 
     fn get_request(&self) -> Result<RequestBuilder, Error> {
+        if self.results.len() > MAX_INLINE_QUERY_RESULTS {
+            return Err(RequestError::TooMany {
+                field: "results",
+                max: MAX_INLINE_QUERY_RESULTS,
+            }
+            .into());
+        }
         RequestBuilder::json("answerInlineQuery", &self)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        methods::{RequestBody, DEFAULT_BASE_URL},
+        types::{InlineQueryResultArticle, InputMessageContent, InputMessageContentText, WebAppInfo},
+    };
+    use serde_json::Value;
+
+    fn article(id: usize) -> InlineQueryResult {
+        InlineQueryResult::Article(InlineQueryResultArticle::new(
+            id.to_string(),
+            String::from("title"),
+            InputMessageContent::Text(InputMessageContentText::new("text")),
+        ))
+    }
+
+    #[test]
+    fn test_answer_inline_query() {
+        let results = (0..MAX_INLINE_QUERY_RESULTS).map(article).collect();
+        let req = AnswerInlineQuery::new("query-id", results)
+            .cache_time(10)
+            .personal(true)
+            .next_offset("49")
+            .button(InlineQueryResultsButton::with_web_app(
+                "open",
+                WebAppInfo::new("https://example.com"),
+            ))
+            .get_request()
+            .unwrap()
+            .build(DEFAULT_BASE_URL, "token");
+        assert_eq!(req.url, "https://api.telegram.org/bottoken/answerInlineQuery");
+        if let RequestBody::Json(data) = req.body {
+            let data: Value = serde_json::from_slice(&data).unwrap();
+            assert_eq!(data["inline_query_id"], "query-id");
+            assert_eq!(data["results"].as_array().unwrap().len(), MAX_INLINE_QUERY_RESULTS);
+            assert_eq!(data["cache_time"], 10);
+            assert_eq!(data["is_personal"], true);
+            assert_eq!(data["next_offset"], "49");
+            assert_eq!(data["button"]["text"], "open");
+            assert_eq!(data["button"]["web_app"]["url"], "https://example.com");
+            assert!(data["button"].get("start_parameter").is_none());
+        } else {
+            panic!("Unexpected request body: {:?}", req.body);
+        }
+
+        let results = (0..=MAX_INLINE_QUERY_RESULTS).map(article).collect();
+        let err = AnswerInlineQuery::new("query-id", results).get_request().unwrap_err();
+        assert_eq!(err.to_string(), "Field \"results\" contains more than 50 items");
+    }
+}
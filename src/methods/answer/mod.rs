@@ -2,5 +2,6 @@ mod callback_query;
 mod inline_query;
 mod pre_checkout_query;
 mod shipping_query;
+mod web_app_query;
 
-pub use self::{callback_query::*, inline_query::*, pre_checkout_query::*, shipping_query::*};
+pub use self::{callback_query::*, inline_query::*, pre_checkout_query::*, shipping_query::*, web_app_query::*};
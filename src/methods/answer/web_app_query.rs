@@ -0,0 +1,38 @@
+use crate::{
+    methods::method::*,
+    types::{InlineQueryResult, SentWebAppMessage},
+};
+use failure::Error;
+use serde::Serialize;
+
+/// Use this method to set the result of an interaction with a Web App
+/// and send a corresponding message on behalf of the user
+/// to the chat from which the query originated
+#[derive(Clone, Debug, Serialize)]
+pub struct AnswerWebAppQuery {
+    web_app_query_id: String,
+    result: InlineQueryResult,
+}
+
+impl AnswerWebAppQuery {
+    /// Creates a new AnswerWebAppQuery
+    ///
+    /// # Arguments
+    ///
+    /// * web_app_query_id - Unique identifier for the query to be answered
+    /// * result - An object describing the message to be sent
+    pub fn new<S: Into<String>, R: Into<InlineQueryResult>>(web_app_query_id: S, result: R) -> Self {
+        AnswerWebAppQuery {
+            web_app_query_id: web_app_query_id.into(),
+            result: result.into(),
+        }
+    }
+}
+
+impl Method for AnswerWebAppQuery {
+    type Response = SentWebAppMessage;
+
+    fn get_request(&self) -> Result<RequestBuilder, Error> {
+        RequestBuilder::json("answerWebAppQuery", &self)
+    }
+}
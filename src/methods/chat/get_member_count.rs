@@ -7,27 +7,31 @@ use serde::Serialize;
 
 /// Get the number of members in a chat
 #[derive(Clone, Debug, Serialize)]
-pub struct GetChatMembersCount {
+pub struct GetChatMemberCount {
     chat_id: ChatId,
 }
 
-impl GetChatMembersCount {
-    /// Creates a new GetChatMembersCount
+impl GetChatMemberCount {
+    /// Creates a new GetChatMemberCount
     ///
     /// # Arguments
     ///
     /// * chat_id - Unique identifier for the target chat
     pub fn new<C: Into<ChatId>>(chat_id: C) -> Self {
-        GetChatMembersCount {
+        GetChatMemberCount {
             chat_id: chat_id.into(),
         }
     }
 }
 
-impl Method for GetChatMembersCount {
+/// Former name of GetChatMemberCount
+#[deprecated(note = "Use GetChatMemberCount instead")]
+pub type GetChatMembersCount = GetChatMemberCount;
+
+impl Method for GetChatMemberCount {
     type Response = Integer;
 
     fn get_request(&self) -> Result<RequestBuilder, Error> {
-        RequestBuilder::json("getChatMembersCount", &self)
+        RequestBuilder::json("getChatMemberCount", &self)
     }
 }
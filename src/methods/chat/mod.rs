@@ -3,7 +3,7 @@ mod delete_sticker_set;
 mod export_invite_link;
 mod get;
 mod get_administrators;
-mod get_members_count;
+mod get_member_count;
 mod leave;
 mod pin_message;
 mod set_description;
@@ -13,6 +13,6 @@ mod set_title;
 mod unpin_message;
 
 pub use self::{
-    delete_photo::*, delete_sticker_set::*, export_invite_link::*, get::*, get_administrators::*, get_members_count::*,
+    delete_photo::*, delete_sticker_set::*, export_invite_link::*, get::*, get_administrators::*, get_member_count::*,
     leave::*, pin_message::*, set_description::*, set_photo::*, set_sticker_set::*, set_title::*, unpin_message::*,
 };
@@ -1,6 +1,6 @@
 use crate::{
     methods::method::*,
-    types::{ChatId, Integer},
+    types::{ChatId, MessageId},
 };
 use failure::Error;
 use serde::Serialize;
@@ -13,7 +13,7 @@ use serde::Serialize;
 #[derive(Clone, Debug, Serialize)]
 pub struct PinChatMessage {
     chat_id: ChatId,
-    message_id: Integer,
+    message_id: MessageId,
     #[serde(skip_serializing_if = "Option::is_none")]
     disable_notification: Option<bool>,
 }
@@ -25,10 +25,10 @@ impl PinChatMessage {
     ///
     /// * chat_id - Unique identifier for the target chat
     /// * message_id - Identifier of a message to pin
-    pub fn new<C: Into<ChatId>>(chat_id: C, message_id: Integer) -> Self {
+    pub fn new<C: Into<ChatId>, M: Into<MessageId>>(chat_id: C, message_id: M) -> Self {
         PinChatMessage {
             chat_id: chat_id.into(),
-            message_id,
+            message_id: message_id.into(),
             disable_notification: None,
         }
     }
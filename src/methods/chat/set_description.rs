@@ -2,6 +2,9 @@ use crate::{methods::method::*, types::ChatId};
 use failure::Error;
 use serde::Serialize;
 
+/// Maximum length of a chat description
+pub const MAX_CHAT_DESCRIPTION_LENGTH: usize = 255;
+
 /// Change the description of a supergroup or a channel
 ///
 /// The bot must be an administrator in the chat for this to work
@@ -37,6 +40,9 @@ impl Method for SetChatDescription {
     type Response = bool;
 
     fn get_request(&self) -> Result<RequestBuilder, Error> {
+        if let Some(ref description) = self.description {
+            check_length("description", description, MAX_CHAT_DESCRIPTION_LENGTH)?;
+        }
         RequestBuilder::json("setChatDescription", &self)
     }
 }
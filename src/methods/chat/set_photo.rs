@@ -1,4 +1,7 @@
-use crate::{methods::method::*, types::ChatId};
+use crate::{
+    methods::method::*,
+    types::{ChatId, InputFile},
+};
 use failure::Error;
 use serde::Serialize;
 
@@ -13,7 +16,7 @@ use serde::Serialize;
 #[derive(Clone, Debug, Serialize)]
 pub struct SetChatPhoto {
     chat_id: ChatId,
-    photo: String,
+    photo: InputFile,
 }
 
 impl SetChatPhoto {
@@ -22,11 +25,12 @@ impl SetChatPhoto {
     /// # Arguments
     ///
     /// * chat_id - Unique identifier for the target chat
-    /// * photo - New chat photo, uploaded using multipart/form-data
-    pub fn new<C: Into<ChatId>, S: Into<String>>(chat_id: C, photo: S) -> Self {
+    /// * photo - New chat photo, pass InputFile::path() to upload it
+    ///           file_id and URL are not accepted by Telegram
+    pub fn new<C: Into<ChatId>>(chat_id: C, photo: InputFile) -> Self {
         SetChatPhoto {
             chat_id: chat_id.into(),
-            photo: photo.into(),
+            photo,
         }
     }
 }
@@ -35,6 +39,40 @@ impl Method for SetChatPhoto {
     type Response = bool;
 
     fn get_request(&self) -> Result<RequestBuilder, Error> {
-        RequestBuilder::json("setChatPhoto", &self)
+        if !self.photo.is_upload() {
+            return Err(RequestError::UploadRequired { field: "photo" }.into());
+        }
+        RequestBuilder::with_files("setChatPhoto", &self, &[("photo", Some(&self.photo))])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::methods::DEFAULT_BASE_URL;
+    use std::{env, fs};
+
+    #[test]
+    fn test_set_chat_photo() {
+        let path = env::temp_dir().join("tgbot-set-chat-photo.png");
+        fs::write(&path, b"png").unwrap();
+        let req = SetChatPhoto::new(1, InputFile::path(&path).unwrap())
+            .get_request()
+            .unwrap()
+            .build(DEFAULT_BASE_URL, "token");
+        fs::remove_file(&path).unwrap();
+        assert_eq!(req.url, "https://api.telegram.org/bottoken/setChatPhoto");
+        match req.body {
+            RequestBody::Multipart(form) => {
+                let names: Vec<&str> = form.fields.iter().map(|(name, _)| name.as_str()).collect();
+                assert_eq!(names, vec!["chat_id", "photo"]);
+            }
+            data => panic!("Unexpected request data: {:?}", data),
+        }
+
+        let err = SetChatPhoto::new(1, InputFile::file_id("file-id"))
+            .get_request()
+            .unwrap_err();
+        assert_eq!(err.to_string(), "Field \"photo\" accepts only uploaded files");
     }
 }
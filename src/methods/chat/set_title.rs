@@ -2,6 +2,9 @@ use crate::{methods::method::*, types::ChatId};
 use failure::Error;
 use serde::Serialize;
 
+/// Maximum length of a chat title
+pub const MAX_CHAT_TITLE_LENGTH: usize = 255;
+
 /// Change the title of a chat
 ///
 /// Titles can't be changed for private chats
@@ -35,6 +38,41 @@ impl Method for SetChatTitle {
     type Response = bool;
 
     fn get_request(&self) -> Result<RequestBuilder, Error> {
+        if self.title.is_empty() {
+            return Err(RequestError::Empty { field: "title" }.into());
+        }
+        check_length("title", &self.title, MAX_CHAT_TITLE_LENGTH)?;
         RequestBuilder::json("setChatTitle", &self)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::methods::DEFAULT_BASE_URL;
+    use serde_json::Value;
+
+    #[test]
+    fn test_set_chat_title() {
+        let req = SetChatTitle::new(1, "title")
+            .get_request()
+            .unwrap()
+            .build(DEFAULT_BASE_URL, "token");
+        assert_eq!(req.url, "https://api.telegram.org/bottoken/setChatTitle");
+        match req.body {
+            RequestBody::Json(data) => {
+                let data: Value = serde_json::from_slice(&data).unwrap();
+                assert_eq!(data["chat_id"], 1);
+                assert_eq!(data["title"], "title");
+            }
+            data => panic!("Unexpected request data: {:?}", data),
+        }
+
+        let err = SetChatTitle::new(1, "").get_request().unwrap_err();
+        assert_eq!(err.to_string(), "Field \"title\" is empty");
+        let err = SetChatTitle::new(1, "a".repeat(MAX_CHAT_TITLE_LENGTH + 1))
+            .get_request()
+            .unwrap_err();
+        assert_eq!(err.to_string(), "Field \"title\" is longer than 255 characters");
+    }
+}
@@ -0,0 +1,100 @@
+use crate::{
+    methods::method::*,
+    types::{ChatId, Integer, UserId},
+};
+use failure::Error;
+use serde::Serialize;
+
+/// Ban a user in a group, a supergroup or a channel
+///
+/// In the case of supergroups and channels, the user will not be able to return
+/// to the chat on their own using invite links, etc., unless unbanned first
+///
+/// The bot must be an administrator in the chat
+/// for this to work and must have the appropriate admin rights
+#[derive(Clone, Debug, Serialize)]
+pub struct BanChatMember {
+    chat_id: ChatId,
+    user_id: UserId,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    until_date: Option<Integer>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    revoke_messages: Option<bool>,
+}
+
+impl BanChatMember {
+    /// Creates a new BanChatMember
+    ///
+    /// # Arguments
+    ///
+    /// * chat_id - Unique identifier for the target chat
+    /// * user_id - Unique identifier of the target user
+    pub fn new<C: Into<ChatId>, U: Into<UserId>>(chat_id: C, user_id: U) -> Self {
+        BanChatMember {
+            chat_id: chat_id.into(),
+            user_id: user_id.into(),
+            until_date: None,
+            revoke_messages: None,
+        }
+    }
+
+    /// Date when the user will be unbanned, unix time
+    ///
+    /// If user is banned for more than 366 days or less than 30 seconds
+    /// from the current time they are considered to be banned forever
+    /// Applied for supergroups and channels only
+    pub fn until_date(mut self, until_date: Integer) -> Self {
+        self.until_date = Some(until_date);
+        self
+    }
+
+    /// Delete all messages from the chat for the user that is being removed
+    ///
+    /// If false, the user will be able to see messages in the group that were sent before the user was removed
+    /// Always true for supergroups and channels
+    pub fn revoke_messages(mut self, revoke_messages: bool) -> Self {
+        self.revoke_messages = Some(revoke_messages);
+        self
+    }
+}
+
+/// Former name of BanChatMember
+#[deprecated(note = "Use BanChatMember instead")]
+pub type KickChatMember = BanChatMember;
+
+impl Method for BanChatMember {
+    type Response = bool;
+
+    fn get_request(&self) -> Result<RequestBuilder, Error> {
+        RequestBuilder::json("banChatMember", &self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::methods::DEFAULT_BASE_URL;
+    use serde_json::Value;
+
+    #[test]
+    fn test_ban_chat_member() {
+        let req = BanChatMember::new(1, 2)
+            .until_date(100)
+            .revoke_messages(true)
+            .get_request()
+            .unwrap()
+            .build(DEFAULT_BASE_URL, "token");
+        assert_eq!(req.method, RequestMethod::Post);
+        assert_eq!(req.url, "https://api.telegram.org/bottoken/banChatMember");
+        match req.body {
+            RequestBody::Json(data) => {
+                let data: Value = serde_json::from_slice(&data).unwrap();
+                assert_eq!(data["chat_id"], 1);
+                assert_eq!(data["user_id"], 2);
+                assert_eq!(data["until_date"], 100);
+                assert_eq!(data["revoke_messages"], true);
+            }
+            data => panic!("Unexpected request data: {:?}", data),
+        }
+    }
+}
@@ -1,6 +1,6 @@
 use crate::{
     methods::method::*,
-    types::{ChatId, ChatMember, Integer},
+    types::{ChatId, ChatMember, UserId},
 };
 use failure::Error;
 use serde::Serialize;
@@ -9,7 +9,7 @@ use serde::Serialize;
 #[derive(Clone, Debug, Serialize)]
 pub struct GetChatMember {
     chat_id: ChatId,
-    user_id: Integer,
+    user_id: UserId,
 }
 
 impl GetChatMember {
@@ -19,10 +19,10 @@ impl GetChatMember {
     ///
     /// * chat_id - Unique identifier for the target chat
     /// * user_id - Unique identifier of the target user
-    pub fn new<C: Into<ChatId>>(chat_id: C, user_id: Integer) -> Self {
+    pub fn new<C: Into<ChatId>, U: Into<UserId>>(chat_id: C, user_id: U) -> Self {
         GetChatMember {
             chat_id: chat_id.into(),
-            user_id,
+            user_id: user_id.into(),
         }
     }
 }
@@ -1,7 +1,7 @@
+mod ban;
 mod get;
-mod kick;
 mod promote;
 mod restrict;
 mod unban;
 
-pub use self::{get::*, kick::*, promote::*, restrict::*, unban::*};
+pub use self::{ban::*, get::*, promote::*, restrict::*, unban::*};
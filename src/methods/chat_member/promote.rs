@@ -1,6 +1,6 @@
 use crate::{
     methods::method::*,
-    types::{ChatId, Integer},
+    types::{ChatId, UserId},
 };
 use failure::Error;
 use serde::Serialize;
@@ -13,7 +13,7 @@ use serde::Serialize;
 #[derive(Clone, Debug, Serialize)]
 pub struct PromoteChatMember {
     chat_id: ChatId,
-    user_id: Integer,
+    user_id: UserId,
     #[serde(skip_serializing_if = "Option::is_none")]
     can_change_info: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -39,10 +39,10 @@ impl PromoteChatMember {
     ///
     /// * chat_id - Unique identifier for the target chat
     /// * user_id - Unique identifier of the target user
-    pub fn new<C: Into<ChatId>>(chat_id: C, user_id: Integer) -> Self {
+    pub fn new<C: Into<ChatId>, U: Into<UserId>>(chat_id: C, user_id: U) -> Self {
         PromoteChatMember {
             chat_id: chat_id.into(),
-            user_id,
+            user_id: user_id.into(),
             can_change_info: None,
             can_post_messages: None,
             can_edit_messages: None,
@@ -1,6 +1,6 @@
 use crate::{
     methods::method::*,
-    types::{ChatId, Integer},
+    types::{ChatId, ChatPermissions, Integer, UserId},
 };
 use failure::Error;
 use serde::Serialize;
@@ -10,58 +10,55 @@ use serde::Serialize;
 /// The bot must be an administrator in the supergroup
 /// for this to work and must have the appropriate admin rights.
 ///
-/// Pass True for all boolean parameters to lift restrictions from a user
+/// Pass ChatPermissions::all_allowed() to lift restrictions from a user
 #[derive(Clone, Debug, Serialize)]
 pub struct RestrictChatMember {
     chat_id: ChatId,
-    user_id: Integer,
+    user_id: UserId,
+    permissions: ChatPermissions,
     #[serde(skip_serializing_if = "Option::is_none")]
     until_date: Option<Integer>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    can_send_messages: Option<bool>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    can_send_media_messages: Option<bool>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    can_send_other_messages: Option<bool>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    can_add_web_page_previews: Option<bool>,
 }
 
 impl RestrictChatMember {
-    /// Creates a new RestrictChatMember with empty optional parameters
+    /// Creates a new RestrictChatMember
     ///
     /// # Arguments
     ///
     /// * chat_id - Unique identifier for the target chat
     /// * user_id - Unique identifier of the target user
-    pub fn new<C: Into<ChatId>>(chat_id: C, user_id: Integer) -> Self {
+    /// * permissions - New user permissions
+    pub fn new<C: Into<ChatId>, U: Into<UserId>>(chat_id: C, user_id: U, permissions: ChatPermissions) -> Self {
         RestrictChatMember {
             chat_id: chat_id.into(),
-            user_id,
+            user_id: user_id.into(),
+            permissions,
             until_date: None,
-            can_send_messages: None,
-            can_send_media_messages: None,
-            can_send_other_messages: None,
-            can_add_web_page_previews: None,
         }
     }
 
-    /// Restrict everything
-    pub fn restrict_all(mut self) -> Self {
-        self.can_send_messages = Some(false);
-        self.can_send_media_messages = Some(false);
-        self.can_send_other_messages = Some(false);
-        self.can_add_web_page_previews = Some(false);
-        self
+    /// Creates a new RestrictChatMember which mutes a user
+    ///
+    /// # Arguments
+    ///
+    /// * chat_id - Unique identifier for the target chat
+    /// * user_id - Unique identifier of the target user
+    /// * until_date - Date when the user will be unmuted, unix time
+    ///                User is muted forever when None
+    pub fn mute<C: Into<ChatId>, U: Into<UserId>>(chat_id: C, user_id: U, until_date: Option<Integer>) -> Self {
+        let mut method = RestrictChatMember::new(chat_id, user_id, ChatPermissions::none());
+        method.until_date = until_date;
+        method
     }
 
-    /// Allow everything
-    pub fn allow_all(mut self) -> Self {
-        self.can_send_messages = Some(true);
-        self.can_send_media_messages = Some(true);
-        self.can_send_other_messages = Some(true);
-        self.can_add_web_page_previews = Some(true);
-        self
+    /// Creates a new RestrictChatMember which unmutes a user
+    ///
+    /// # Arguments
+    ///
+    /// * chat_id - Unique identifier for the target chat
+    /// * user_id - Unique identifier of the target user
+    pub fn unmute<C: Into<ChatId>, U: Into<UserId>>(chat_id: C, user_id: U) -> Self {
+        RestrictChatMember::new(chat_id, user_id, ChatPermissions::all_allowed())
     }
 
     /// Date when restrictions will be lifted for the user, unix time
@@ -72,33 +69,6 @@ impl RestrictChatMember {
         self.until_date = Some(until_date);
         self
     }
-
-    /// Pass True, if the user can send text messages, contacts, locations and venues
-    pub fn can_send_messages(mut self, can_send_messages: bool) -> Self {
-        self.can_send_messages = Some(can_send_messages);
-        self
-    }
-
-    /// Pass True, if the user can send audios, documents, photos,
-    /// videos, video notes and voice notes, implies can_send_messages
-    pub fn can_send_media_messages(mut self, can_send_media_messages: bool) -> Self {
-        self.can_send_media_messages = Some(can_send_media_messages);
-        self
-    }
-
-    /// Pass True, if the user can send animations, games, stickers and
-    /// use inline bots, implies can_send_media_messages
-    pub fn can_send_other_messages(mut self, can_send_other_messages: bool) -> Self {
-        self.can_send_other_messages = Some(can_send_other_messages);
-        self
-    }
-
-    /// Pass True, if the user may add web page previews to their messages,
-    /// implies can_send_media_messages
-    pub fn can_add_web_page_previews(mut self, can_add_web_page_previews: bool) -> Self {
-        self.can_add_web_page_previews = Some(can_add_web_page_previews);
-        self
-    }
 }
 
 impl Method for RestrictChatMember {
@@ -108,3 +78,79 @@ impl Method for RestrictChatMember {
         RequestBuilder::json("restrictChatMember", &self)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::methods::DEFAULT_BASE_URL;
+    use serde_json::Value;
+
+    #[test]
+    fn test_serialize_mute_unmute() {
+        let req = RestrictChatMember::mute(1, 2, Some(100))
+            .get_request()
+            .unwrap()
+            .build(DEFAULT_BASE_URL, "token");
+        assert_eq!(req.method, RequestMethod::Post);
+        assert_eq!(
+            req.url,
+            String::from("https://api.telegram.org/bottoken/restrictChatMember")
+        );
+        match req.body {
+            RequestBody::Json(data) => {
+                let data: Value = serde_json::from_slice(&data).unwrap();
+                assert_eq!(data["chat_id"], 1);
+                assert_eq!(data["user_id"], 2);
+                assert_eq!(data["until_date"], 100);
+                let permissions = &data["permissions"];
+                assert_eq!(permissions["can_send_messages"], false);
+                assert_eq!(permissions["can_send_media_messages"], false);
+                assert_eq!(permissions["can_send_polls"], false);
+                assert_eq!(permissions["can_send_other_messages"], false);
+                assert_eq!(permissions["can_add_web_page_previews"], false);
+                assert_eq!(permissions["can_change_info"], false);
+                assert_eq!(permissions["can_invite_users"], false);
+                assert_eq!(permissions["can_pin_messages"], false);
+            }
+            data => panic!("Unexpected request data: {:?}", data),
+        }
+
+        let req = RestrictChatMember::unmute(1, 2)
+            .get_request()
+            .unwrap()
+            .build(DEFAULT_BASE_URL, "token");
+        match req.body {
+            RequestBody::Json(data) => {
+                let data: Value = serde_json::from_slice(&data).unwrap();
+                assert!(data.get("until_date").is_none());
+                let permissions = &data["permissions"];
+                assert_eq!(permissions["can_send_messages"], true);
+                assert_eq!(permissions["can_send_media_messages"], true);
+                assert_eq!(permissions["can_send_polls"], true);
+                assert_eq!(permissions["can_send_other_messages"], true);
+                assert_eq!(permissions["can_add_web_page_previews"], true);
+                assert_eq!(permissions["can_change_info"], true);
+                assert_eq!(permissions["can_invite_users"], true);
+                assert_eq!(permissions["can_pin_messages"], true);
+            }
+            data => panic!("Unexpected request data: {:?}", data),
+        }
+
+        let permissions = ChatPermissions {
+            can_send_messages: Some(true),
+            ..Default::default()
+        };
+        let req = RestrictChatMember::new(1, UserId(2), permissions)
+            .get_request()
+            .unwrap()
+            .build(DEFAULT_BASE_URL, "token");
+        match req.body {
+            RequestBody::Json(data) => {
+                let data: Value = serde_json::from_slice(&data).unwrap();
+                assert_eq!(data["user_id"], 2);
+                assert_eq!(data["permissions"], serde_json::json!({"can_send_messages": true}));
+            }
+            data => panic!("Unexpected request data: {:?}", data),
+        }
+    }
+}
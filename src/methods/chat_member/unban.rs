@@ -1,20 +1,26 @@
 use crate::{
     methods::method::*,
-    types::{ChatId, Integer},
+    types::{ChatId, UserId},
 };
 use failure::Error;
 use serde::Serialize;
 
-/// Unban a previously kicked user in a supergroup or channel
+/// Unban a previously banned user in a supergroup or channel
 ///
 /// The user will not return to the group or channel
 /// automatically, but will be able to join via link, etc.
 ///
 /// The bot must be an administrator for this to work
+///
+/// By default, this method guarantees that after the call the user is not a member of the chat,
+/// but will be able to join it. So if the user is a member of the chat they will also be removed
+/// from the chat. Use only_if_banned to avoid this
 #[derive(Clone, Debug, Serialize)]
 pub struct UnbanChatMember {
     chat_id: ChatId,
-    user_id: Integer,
+    user_id: UserId,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    only_if_banned: Option<bool>,
 }
 
 impl UnbanChatMember {
@@ -24,12 +30,19 @@ impl UnbanChatMember {
     ///
     /// * chat_id - Unique identifier for the target chat
     /// * user_id - Unique identifier of the target user
-    pub fn new<C: Into<ChatId>>(chat_id: C, user_id: Integer) -> Self {
+    pub fn new<C: Into<ChatId>, U: Into<UserId>>(chat_id: C, user_id: U) -> Self {
         UnbanChatMember {
             chat_id: chat_id.into(),
-            user_id,
+            user_id: user_id.into(),
+            only_if_banned: None,
         }
     }
+
+    /// Do nothing if the user is not banned
+    pub fn only_if_banned(mut self, only_if_banned: bool) -> Self {
+        self.only_if_banned = Some(only_if_banned);
+        self
+    }
 }
 
 impl Method for UnbanChatMember {
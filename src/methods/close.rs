@@ -0,0 +1,18 @@
+use crate::methods::method::*;
+use failure::Error;
+
+/// Close the bot instance before moving it from one local server to another
+///
+/// You need to delete the webhook before calling this method to ensure
+/// that the bot isn't launched again after server restart
+/// The method will return error 429 in the first 10 minutes after the bot is launched
+#[derive(Clone, Copy, Debug)]
+pub struct Close;
+
+impl Method for Close {
+    type Response = bool;
+
+    fn get_request(&self) -> Result<RequestBuilder, Error> {
+        RequestBuilder::empty("close")
+    }
+}
@@ -0,0 +1,43 @@
+use crate::types::InputFile;
+
+/// Fields of a multipart/form-data request
+#[derive(Clone, Debug, Default)]
+pub struct Form {
+    pub(crate) fields: Vec<(String, FormValue)>,
+}
+
+impl Form {
+    /// Returns names and values of fields in order of insertion
+    pub fn fields(&self) -> &[(String, FormValue)] {
+        &self.fields
+    }
+
+    pub(crate) fn insert_field<N, V>(&mut self, name: N, value: V)
+    where
+        N: Into<String>,
+        V: Into<FormValue>,
+    {
+        self.fields.push((name.into(), value.into()));
+    }
+}
+
+/// Value of a form field
+#[derive(Clone, Debug)]
+pub enum FormValue {
+    /// Text value
+    Text(String),
+    /// File, local files are read when the form is encoded
+    File(InputFile),
+}
+
+impl From<String> for FormValue {
+    fn from(value: String) -> Self {
+        FormValue::Text(value)
+    }
+}
+
+impl From<InputFile> for FormValue {
+    fn from(value: InputFile) -> Self {
+        FormValue::File(value)
+    }
+}
@@ -1,6 +1,6 @@
 use crate::{
     methods::method::*,
-    types::{GameHighScore, Integer},
+    types::{GameHighScore, Integer, MessageId, UserId},
 };
 use failure::Error;
 use serde::Serialize;
@@ -14,11 +14,11 @@ use serde::Serialize;
 /// Please note that this behavior is subject to change
 #[derive(Clone, Debug, Serialize)]
 pub struct GetGameHighScores {
-    user_id: Integer,
+    user_id: UserId,
     #[serde(skip_serializing_if = "Option::is_none")]
     chat_id: Option<Integer>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    message_id: Option<Integer>,
+    message_id: Option<MessageId>,
     #[serde(skip_serializing_if = "Option::is_none")]
     inline_message_id: Option<String>,
 }
@@ -31,11 +31,11 @@ impl GetGameHighScores {
     /// * user_id - Target user id
     /// * chat_id - Unique identifier for the target chat
     /// * message_id - Identifier of the sent message
-    pub fn new(user_id: Integer, chat_id: Integer, message_id: Integer) -> Self {
+    pub fn new<U: Into<UserId>, M: Into<MessageId>>(user_id: U, chat_id: Integer, message_id: M) -> Self {
         GetGameHighScores {
-            user_id,
+            user_id: user_id.into(),
             chat_id: Some(chat_id),
-            message_id: Some(message_id),
+            message_id: Some(message_id.into()),
             inline_message_id: None,
         }
     }
@@ -46,9 +46,9 @@ impl GetGameHighScores {
     ///
     /// * user_id - Target user id
     /// * inline_message_id - Identifier of the inline message
-    pub fn with_inline_message_id<S: Into<String>>(user_id: Integer, inline_message_id: S) -> Self {
+    pub fn with_inline_message_id<S: Into<String>, U: Into<UserId>>(user_id: U, inline_message_id: S) -> Self {
         GetGameHighScores {
-            user_id,
+            user_id: user_id.into(),
             chat_id: None,
             message_id: None,
             inline_message_id: Some(inline_message_id.into()),
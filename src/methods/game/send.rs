@@ -1,6 +1,6 @@
 use crate::{
     methods::method::*,
-    types::{InlineKeyboardMarkup, Integer, Message},
+    types::{InlineKeyboardMarkup, Integer, Message, MessageId},
 };
 use failure::Error;
 use serde::Serialize;
@@ -13,7 +13,7 @@ pub struct SendGame {
     #[serde(skip_serializing_if = "Option::is_none")]
     disable_notification: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    reply_to_message_id: Option<Integer>,
+    reply_to_message_id: Option<MessageId>,
     #[serde(skip_serializing_if = "Option::is_none")]
     reply_markup: Option<InlineKeyboardMarkup>,
 }
@@ -44,8 +44,8 @@ impl SendGame {
     }
 
     /// If the message is a reply, ID of the original message
-    pub fn reply_to_message_id(mut self, reply_to_message_id: Integer) -> Self {
-        self.reply_to_message_id = Some(reply_to_message_id);
+    pub fn reply_to_message_id<M: Into<MessageId>>(mut self, reply_to_message_id: M) -> Self {
+        self.reply_to_message_id = Some(reply_to_message_id.into());
         self
     }
 
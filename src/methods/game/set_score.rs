@@ -1,6 +1,6 @@
 use crate::{
     methods::method::*,
-    types::{EditMessageResult, Integer},
+    types::{EditMessageResult, Integer, MessageId, UserId},
 };
 use failure::Error;
 use serde::Serialize;
@@ -11,7 +11,7 @@ use serde::Serialize;
 /// than the user's current score in the chat and force is False
 #[derive(Clone, Debug, Serialize)]
 pub struct SetGameScore {
-    user_id: Integer,
+    user_id: UserId,
     score: Integer,
     #[serde(skip_serializing_if = "Option::is_none")]
     force: Option<bool>,
@@ -20,7 +20,7 @@ pub struct SetGameScore {
     #[serde(skip_serializing_if = "Option::is_none")]
     chat_id: Option<Integer>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    message_id: Option<Integer>,
+    message_id: Option<MessageId>,
     #[serde(skip_serializing_if = "Option::is_none")]
     inline_message_id: Option<String>,
 }
@@ -34,14 +34,19 @@ impl SetGameScore {
     /// * message_id - Identifier of the sent message
     /// * user_id - User identifier
     /// * score - New score, must be non-negative
-    pub fn new(chat_id: Integer, message_id: Integer, user_id: Integer, score: Integer) -> Self {
+    pub fn new<U: Into<UserId>, M: Into<MessageId>>(
+        chat_id: Integer,
+        message_id: M,
+        user_id: U,
+        score: Integer,
+    ) -> Self {
         SetGameScore {
-            user_id,
+            user_id: user_id.into(),
             score,
             force: None,
             disable_edit_message: None,
             chat_id: Some(chat_id),
-            message_id: Some(message_id),
+            message_id: Some(message_id.into()),
             inline_message_id: None,
         }
     }
@@ -53,9 +58,13 @@ impl SetGameScore {
     /// * inline_message_id - Identifier of the inline message
     /// * user_id - User identifier
     /// * score - New score, must be non-negative
-    pub fn with_inline_message_id<S: Into<String>>(inline_message_id: S, user_id: Integer, score: Integer) -> Self {
+    pub fn with_inline_message_id<S: Into<String>, U: Into<UserId>>(
+        inline_message_id: S,
+        user_id: U,
+        score: Integer,
+    ) -> Self {
         SetGameScore {
-            user_id,
+            user_id: user_id.into(),
             score,
             force: None,
             disable_edit_message: None,
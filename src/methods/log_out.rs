@@ -0,0 +1,19 @@
+use crate::methods::method::*;
+use failure::Error;
+
+/// Log out from the cloud Bot API server before launching the bot locally
+///
+/// You must log out the bot before running it locally,
+/// otherwise there is no guarantee that the bot will receive updates
+/// After a successful call, you can immediately log in on a local server,
+/// but will not be able to log in back to the cloud Bot API server for 10 minutes
+#[derive(Clone, Copy, Debug)]
+pub struct LogOut;
+
+impl Method for LogOut {
+    type Response = bool;
+
+    fn get_request(&self) -> Result<RequestBuilder, Error> {
+        RequestBuilder::empty("logOut")
+    }
+}
@@ -0,0 +1,143 @@
+use crate::{
+    methods::method::*,
+    types::{ChatId, MessageId, ParseMode, ReplyMarkup},
+};
+use failure::Error;
+use serde::Serialize;
+
+/// Copy message of any kind
+///
+/// The copied message doesn't have a link to the original message.
+/// Service messages and invoice messages can't be copied.
+#[derive(Clone, Debug, Serialize)]
+pub struct CopyMessage {
+    chat_id: ChatId,
+    from_chat_id: ChatId,
+    message_id: MessageId,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    caption: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    parse_mode: Option<ParseMode>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    disable_notification: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reply_markup: Option<ReplyMarkup>,
+}
+
+impl CopyMessage {
+    /// Creates a new CopyMessage with empty optional parameters
+    ///
+    /// # Arguments
+    ///
+    /// * chat_id - Unique identifier for the target chat
+    /// * from_chat_id - Unique identifier for the chat where the original message was sent
+    /// * message_id - Message identifier in the chat specified in from_chat_id
+    pub fn new<C, F, M>(chat_id: C, from_chat_id: F, message_id: M) -> Self
+    where
+        C: Into<ChatId>,
+        F: Into<ChatId>,
+        M: Into<MessageId>,
+    {
+        CopyMessage {
+            chat_id: chat_id.into(),
+            from_chat_id: from_chat_id.into(),
+            message_id: message_id.into(),
+            caption: None,
+            parse_mode: None,
+            disable_notification: None,
+            reply_markup: None,
+        }
+    }
+
+    /// New caption for media
+    ///
+    /// 0-1024 characters
+    /// If not specified, the original caption is kept
+    pub fn caption<S: Into<String>>(mut self, caption: S) -> Self {
+        self.caption = Some(caption.into());
+        self
+    }
+
+    /// Sets a parse mode for the new caption
+    pub fn parse_mode(mut self, parse_mode: ParseMode) -> Self {
+        self.parse_mode = Some(parse_mode);
+        self
+    }
+
+    /// Sends the message silently
+    ///
+    /// Users will receive a notification with no sound
+    pub fn disable_notification(mut self, disable_notification: bool) -> Self {
+        self.disable_notification = Some(disable_notification);
+        self
+    }
+
+    /// Additional interface options
+    pub fn reply_markup<R: Into<ReplyMarkup>>(mut self, reply_markup: R) -> Self {
+        self.reply_markup = Some(reply_markup.into());
+        self
+    }
+}
+
+impl Method for CopyMessage {
+    /// Only the identifier of the sent message is returned
+    type Response = MessageId;
+
+    fn get_request(&self) -> Result<RequestBuilder, Error> {
+        check_caption(&self.caption, self.parse_mode)?;
+        RequestBuilder::json("copyMessage", &self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::methods::DEFAULT_BASE_URL;
+    use serde_json::Value;
+
+    #[test]
+    fn test_copy_message() {
+        let req = CopyMessage::new(1, 2, 3)
+            .caption("caption")
+            .parse_mode(ParseMode::Markdown)
+            .disable_notification(true)
+            .get_request()
+            .unwrap()
+            .build(DEFAULT_BASE_URL, "token");
+        assert_eq!(req.method, RequestMethod::Post);
+        assert_eq!(req.url, String::from("https://api.telegram.org/bottoken/copyMessage"));
+        match req.body {
+            RequestBody::Json(data) => {
+                let data: Value = serde_json::from_slice(&data).unwrap();
+                assert_eq!(data["chat_id"], 1);
+                assert_eq!(data["from_chat_id"], 2);
+                assert_eq!(data["message_id"], 3);
+                assert_eq!(data["caption"], "caption");
+                assert_eq!(data["parse_mode"], "Markdown");
+                assert_eq!(data["disable_notification"], true);
+            }
+            data => panic!("Unexpected request data: {:?}", data),
+        }
+
+        let response: MessageId = serde_json::from_value(serde_json::json!({"message_id": 4})).unwrap();
+        assert_eq!(response, 4);
+        let response: MessageId = serde_json::from_value(serde_json::json!(4)).unwrap();
+        assert_eq!(response, MessageId(4));
+    }
+
+    #[test]
+    fn test_copy_from_channel() {
+        let req = CopyMessage::new(1, "@source_channel", 3)
+            .get_request()
+            .unwrap()
+            .build(DEFAULT_BASE_URL, "token");
+        match req.body {
+            RequestBody::Json(data) => {
+                let data: Value = serde_json::from_slice(&data).unwrap();
+                assert_eq!(data["chat_id"], 1);
+                assert_eq!(data["from_chat_id"], "@source_channel");
+            }
+            data => panic!("Unexpected request data: {:?}", data),
+        }
+    }
+}
@@ -1,10 +1,13 @@
 use crate::{
     methods::method::*,
-    types::{ChatId, Integer},
+    types::{ChatId, MessageId},
 };
 use failure::Error;
 use serde::Serialize;
 
+/// Maximum number of messages that can be deleted with a single DeleteMessages request
+pub const MAX_DELETE_MESSAGES: usize = 100;
+
 /// Delete a message, including service messages
 ///
 /// Limitations:
@@ -17,7 +20,7 @@ use serde::Serialize;
 #[derive(Clone, Debug, Serialize)]
 pub struct DeleteMessage {
     chat_id: ChatId,
-    message_id: Integer,
+    message_id: MessageId,
 }
 
 impl DeleteMessage {
@@ -27,10 +30,10 @@ impl DeleteMessage {
     ///
     /// * chat_id - Unique identifier for the target chat
     /// * message_id - Identifier of the message to delete
-    pub fn new<C: Into<ChatId>>(chat_id: C, message_id: Integer) -> Self {
+    pub fn new<C: Into<ChatId>, M: Into<MessageId>>(chat_id: C, message_id: M) -> Self {
         DeleteMessage {
             chat_id: chat_id.into(),
-            message_id,
+            message_id: message_id.into(),
         }
     }
 }
@@ -42,3 +45,85 @@ impl Method for DeleteMessage {
         RequestBuilder::json("deleteMessage", &self)
     }
 }
+
+/// Delete multiple messages simultaneously
+///
+/// Messages that can't be found are skipped.
+/// Limitations are the same as for DeleteMessage,
+/// in particular messages sent more than 48 hours ago can't be deleted.
+#[derive(Clone, Debug, Serialize)]
+pub struct DeleteMessages {
+    chat_id: ChatId,
+    message_ids: Vec<MessageId>,
+}
+
+impl DeleteMessages {
+    /// Creates a new DeleteMessages
+    ///
+    /// # Arguments
+    ///
+    /// * chat_id - Unique identifier for the target chat
+    /// * message_ids - Identifiers of 1-100 messages to delete
+    pub fn new<C, I, M>(chat_id: C, message_ids: I) -> Self
+    where
+        C: Into<ChatId>,
+        I: IntoIterator<Item = M>,
+        M: Into<MessageId>,
+    {
+        DeleteMessages {
+            chat_id: chat_id.into(),
+            message_ids: message_ids.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl Method for DeleteMessages {
+    type Response = bool;
+
+    fn get_request(&self) -> Result<RequestBuilder, Error> {
+        if self.message_ids.is_empty() {
+            return Err(RequestError::Empty { field: "message_ids" }.into());
+        }
+        if self.message_ids.len() > MAX_DELETE_MESSAGES {
+            return Err(RequestError::TooMany {
+                field: "message_ids",
+                max: MAX_DELETE_MESSAGES,
+            }
+            .into());
+        }
+        RequestBuilder::json("deleteMessages", &self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::methods::DEFAULT_BASE_URL;
+    use serde_json::Value;
+
+    #[test]
+    fn test_delete_messages() {
+        let req = DeleteMessages::new(1, vec![2, 3])
+            .get_request()
+            .unwrap()
+            .build(DEFAULT_BASE_URL, "token");
+        assert_eq!(req.method, RequestMethod::Post);
+        assert_eq!(req.url, "https://api.telegram.org/bottoken/deleteMessages");
+        match req.body {
+            RequestBody::Json(data) => {
+                let data: Value = serde_json::from_slice(&data).unwrap();
+                assert_eq!(data["chat_id"], 1);
+                assert_eq!(data["message_ids"], serde_json::json!([2, 3]));
+            }
+            data => panic!("Unexpected request data: {:?}", data),
+        }
+
+        let err = DeleteMessages::new(1, 0..=MAX_DELETE_MESSAGES as i64)
+            .get_request()
+            .unwrap_err();
+        assert_eq!(err.to_string(), "Field \"message_ids\" contains more than 100 items");
+
+        let err = DeleteMessages::new(1, Vec::<i64>::new()).get_request().unwrap_err();
+        assert_eq!(err.to_string(), "Field \"message_ids\" is empty");
+    }
+}
@@ -1,6 +1,6 @@
 use crate::{
-    methods::method::*,
-    types::{ChatId, EditMessageResult, InlineKeyboardMarkup, Integer, ParseMode},
+    methods::{message::EditMessageTarget, method::*},
+    types::{ChatId, EditMessageResult, InlineKeyboardMarkup, MessageId, ParseMode},
 };
 use failure::Error;
 use serde::Serialize;
@@ -8,12 +8,8 @@ use serde::Serialize;
 /// Edit caption of message sent by the bot or via the bot (for inline bots)
 #[derive(Clone, Debug, Serialize)]
 pub struct EditMessageCaption {
-    #[serde(skip_serializing_if = "Option::is_none")]
-    chat_id: Option<ChatId>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    message_id: Option<Integer>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    inline_message_id: Option<String>,
+    #[serde(flatten)]
+    target: EditMessageTarget,
     #[serde(skip_serializing_if = "Option::is_none")]
     caption: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -29,15 +25,8 @@ impl EditMessageCaption {
     ///
     /// * chat_id - Unique identifier for the target chat
     /// * message_id - Identifier of the sent message
-    pub fn new<C: Into<ChatId>>(chat_id: C, message_id: Integer) -> Self {
-        EditMessageCaption {
-            chat_id: Some(chat_id.into()),
-            message_id: Some(message_id),
-            inline_message_id: None,
-            caption: None,
-            parse_mode: None,
-            reply_markup: None,
-        }
+    pub fn new<C: Into<ChatId>, M: Into<MessageId>>(chat_id: C, message_id: M) -> Self {
+        EditMessageCaption::with_target(EditMessageTarget::chat(chat_id, message_id))
     }
 
     /// Creates a new EditMessageCaption
@@ -46,10 +35,17 @@ impl EditMessageCaption {
     ///
     /// * inline_message_id - Identifier of the inline message
     pub fn with_inline_message_id<S: Into<String>>(inline_message_id: S) -> Self {
+        EditMessageCaption::with_target(EditMessageTarget::inline(inline_message_id))
+    }
+
+    /// Creates a new EditMessageCaption for a chat or an inline message
+    ///
+    /// # Arguments
+    ///
+    /// * target - Message to be edited
+    pub fn with_target(target: EditMessageTarget) -> Self {
         EditMessageCaption {
-            chat_id: None,
-            message_id: None,
-            inline_message_id: Some(inline_message_id.into()),
+            target,
             caption: None,
             parse_mode: None,
             reply_markup: None,
@@ -79,6 +75,7 @@ impl Method for EditMessageCaption {
     type Response = EditMessageResult;
 
     fn get_request(&self) -> Result<RequestBuilder, Error> {
+        check_caption(&self.caption, self.parse_mode)?;
         RequestBuilder::json("editMessageCaption", &self)
     }
 }
@@ -1,6 +1,6 @@
 use crate::{
-    methods::method::*,
-    types::{ChatId, EditMessageResult, Float, InlineKeyboardMarkup, Integer},
+    methods::{message::EditMessageTarget, method::*},
+    types::{ChatId, EditMessageResult, Float, InlineKeyboardMarkup, Integer, MessageId},
 };
 use failure::Error;
 use serde::Serialize;
@@ -11,14 +11,16 @@ use serde::Serialize;
 /// is explicitly disabled by a call to stopMessageLiveLocation
 #[derive(Clone, Debug, Serialize)]
 pub struct EditMessageLiveLocation {
+    #[serde(flatten)]
+    target: EditMessageTarget,
+    latitude: Float,
+    longitude: Float,
     #[serde(skip_serializing_if = "Option::is_none")]
-    chat_id: Option<ChatId>,
+    horizontal_accuracy: Option<Float>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    message_id: Option<Integer>,
+    heading: Option<Integer>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    inline_message_id: Option<String>,
-    latitude: Float,
-    longitude: Float,
+    proximity_alert_radius: Option<Integer>,
     #[serde(skip_serializing_if = "Option::is_none")]
     reply_markup: Option<InlineKeyboardMarkup>,
 }
@@ -32,15 +34,13 @@ impl EditMessageLiveLocation {
     /// * message_id - Identifier of the sent message
     /// * latitude - Latitude of new location
     /// * longitude Longitude of new location
-    pub fn new<C: Into<ChatId>>(chat_id: C, message_id: Integer, latitude: Float, longitude: Float) -> Self {
-        EditMessageLiveLocation {
-            chat_id: Some(chat_id.into()),
-            message_id: Some(message_id),
-            inline_message_id: None,
-            latitude,
-            longitude,
-            reply_markup: None,
-        }
+    pub fn new<C: Into<ChatId>, M: Into<MessageId>>(
+        chat_id: C,
+        message_id: M,
+        latitude: Float,
+        longitude: Float,
+    ) -> Self {
+        EditMessageLiveLocation::with_target(EditMessageTarget::chat(chat_id, message_id), latitude, longitude)
     }
 
     /// Creates a new EditMessageLiveLocation
@@ -51,16 +51,46 @@ impl EditMessageLiveLocation {
     /// * latitude - Latitude of new location
     /// * longitude Longitude of new location
     pub fn with_inline_message_id<S: Into<String>>(inline_message_id: S, latitude: Float, longitude: Float) -> Self {
+        EditMessageLiveLocation::with_target(EditMessageTarget::inline(inline_message_id), latitude, longitude)
+    }
+
+    /// Creates a new EditMessageLiveLocation for a chat or an inline message
+    ///
+    /// # Arguments
+    ///
+    /// * target - Message to be edited
+    /// * latitude - Latitude of new location
+    /// * longitude Longitude of new location
+    pub fn with_target(target: EditMessageTarget, latitude: Float, longitude: Float) -> Self {
         EditMessageLiveLocation {
-            chat_id: None,
-            message_id: None,
-            inline_message_id: Some(inline_message_id.into()),
+            target,
             latitude,
             longitude,
+            horizontal_accuracy: None,
+            heading: None,
+            proximity_alert_radius: None,
             reply_markup: None,
         }
     }
 
+    /// The radius of uncertainty for the location, measured in meters; 0-1500
+    pub fn horizontal_accuracy(mut self, horizontal_accuracy: Float) -> Self {
+        self.horizontal_accuracy = Some(horizontal_accuracy);
+        self
+    }
+
+    /// Direction in which the user is moving, in degrees; 1-360
+    pub fn heading(mut self, heading: Integer) -> Self {
+        self.heading = Some(heading);
+        self
+    }
+
+    /// Maximum distance in meters for proximity alerts about approaching another chat member; 1-100000
+    pub fn proximity_alert_radius(mut self, proximity_alert_radius: Integer) -> Self {
+        self.proximity_alert_radius = Some(proximity_alert_radius);
+        self
+    }
+
     /// New inline keyboard
     pub fn reply_markup<I: Into<InlineKeyboardMarkup>>(mut self, reply_markup: I) -> Self {
         self.reply_markup = Some(reply_markup.into());
@@ -81,9 +111,8 @@ impl Method for EditMessageLiveLocation {
 /// before live_period expires
 #[derive(Clone, Debug, Serialize)]
 pub struct StopMessageLiveLocation {
-    chat_id: Option<ChatId>,
-    message_id: Option<Integer>,
-    inline_message_id: Option<String>,
+    #[serde(flatten)]
+    target: EditMessageTarget,
     reply_markup: Option<InlineKeyboardMarkup>,
 }
 
@@ -94,13 +123,8 @@ impl StopMessageLiveLocation {
     ///
     /// * chat_id - Unique identifier for the target chat
     /// * message_id - Identifier of the sent message
-    pub fn new<C: Into<ChatId>>(chat_id: C, message_id: Integer) -> Self {
-        StopMessageLiveLocation {
-            chat_id: Some(chat_id.into()),
-            message_id: Some(message_id),
-            inline_message_id: None,
-            reply_markup: None,
-        }
+    pub fn new<C: Into<ChatId>, M: Into<MessageId>>(chat_id: C, message_id: M) -> Self {
+        StopMessageLiveLocation::with_target(EditMessageTarget::chat(chat_id, message_id))
     }
 
     /// Creates a new StopMessageLiveLocation
@@ -109,10 +133,17 @@ impl StopMessageLiveLocation {
     ///
     /// * inline_message_id - Identifier of the inline message
     pub fn with_inline_message_id<S: Into<String>>(inline_message_id: S) -> Self {
+        StopMessageLiveLocation::with_target(EditMessageTarget::inline(inline_message_id))
+    }
+
+    /// Creates a new StopMessageLiveLocation for a chat or an inline message
+    ///
+    /// # Arguments
+    ///
+    /// * target - Message to be edited
+    pub fn with_target(target: EditMessageTarget) -> Self {
         StopMessageLiveLocation {
-            chat_id: None,
-            message_id: None,
-            inline_message_id: Some(inline_message_id.into()),
+            target,
             reply_markup: None,
         }
     }
@@ -1,6 +1,6 @@
 use crate::{
-    methods::method::*,
-    types::{ChatId, EditMessageResult, InlineKeyboardMarkup, InputMedia, Integer},
+    methods::{message::EditMessageTarget, method::*},
+    types::{ChatId, EditMessageResult, InlineKeyboardMarkup, InputFile, InputMedia, MessageId},
 };
 use failure::Error;
 use serde::Serialize;
@@ -13,12 +13,8 @@ use serde::Serialize;
 /// Use previously uploaded file via its file_id or specify a URL
 #[derive(Clone, Debug, Serialize)]
 pub struct EditMessageMedia {
-    #[serde(skip_serializing_if = "Option::is_none")]
-    chat_id: Option<ChatId>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    message_id: Option<Integer>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    inline_message_id: Option<String>,
+    #[serde(flatten)]
+    target: EditMessageTarget,
     media: InputMedia,
     #[serde(skip_serializing_if = "Option::is_none")]
     reply_markup: Option<InlineKeyboardMarkup>,
@@ -32,14 +28,8 @@ impl EditMessageMedia {
     /// * chat_id - Unique identifier for the target chat
     /// * message_id - Identifier of the sent message
     /// * media - New media content of the message
-    pub fn new<C: Into<ChatId>>(chat_id: C, message_id: Integer, media: InputMedia) -> Self {
-        EditMessageMedia {
-            chat_id: Some(chat_id.into()),
-            message_id: Some(message_id),
-            inline_message_id: None,
-            media,
-            reply_markup: None,
-        }
+    pub fn new<C: Into<ChatId>, M: Into<MessageId>>(chat_id: C, message_id: M, media: InputMedia) -> Self {
+        EditMessageMedia::with_target(EditMessageTarget::chat(chat_id, message_id), media)
     }
 
     /// Creates a new EditMessageMedia
@@ -49,10 +39,18 @@ impl EditMessageMedia {
     /// * inline_message_id - Identifier of the inline message
     /// * media - New media content of the message
     pub fn with_inline_message_id<S: Into<String>>(inline_message_id: S, media: InputMedia) -> Self {
+        EditMessageMedia::with_target(EditMessageTarget::inline(inline_message_id), media)
+    }
+
+    /// Creates a new EditMessageMedia for a chat or an inline message
+    ///
+    /// # Arguments
+    ///
+    /// * target - Message to be edited
+    /// * media - New media content of the message
+    pub fn with_target(target: EditMessageTarget, media: InputMedia) -> Self {
         EditMessageMedia {
-            chat_id: None,
-            message_id: None,
-            inline_message_id: Some(inline_message_id.into()),
+            target,
             media,
             reply_markup: None,
         }
@@ -69,6 +67,12 @@ impl Method for EditMessageMedia {
     type Response = EditMessageResult;
 
     fn get_request(&self) -> Result<RequestBuilder, Error> {
-        RequestBuilder::json("editMessageMedia", &self)
+        let files: Vec<(String, &InputFile)> = self
+            .media
+            .files()
+            .into_iter()
+            .map(|(field, file)| (format!("/media/{}", field), file))
+            .collect();
+        RequestBuilder::with_attachments("editMessageMedia", &self, &files)
     }
 }
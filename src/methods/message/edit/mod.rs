@@ -2,6 +2,7 @@ mod caption;
 mod live_location;
 mod media;
 mod reply_markup;
+mod target;
 mod text;
 
-pub use self::{caption::*, live_location::*, media::*, reply_markup::*, text::*};
+pub use self::{caption::*, live_location::*, media::*, reply_markup::*, target::*, text::*};
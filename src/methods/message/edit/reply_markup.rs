@@ -1,6 +1,6 @@
 use crate::{
-    methods::method::*,
-    types::{ChatId, EditMessageResult, InlineKeyboardMarkup, Integer},
+    methods::{message::EditMessageTarget, method::*},
+    types::{ChatId, EditMessageResult, InlineKeyboardMarkup, MessageId},
 };
 use failure::Error;
 use serde::Serialize;
@@ -8,12 +8,8 @@ use serde::Serialize;
 /// Edit only the reply markup of messages sent by the bot or via the bot (for inline bots)
 #[derive(Clone, Debug, Serialize)]
 pub struct EditMessageReplyMarkup {
-    #[serde(skip_serializing_if = "Option::is_none")]
-    chat_id: Option<ChatId>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    message_id: Option<Integer>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    inline_message_id: Option<String>,
+    #[serde(flatten)]
+    target: EditMessageTarget,
     #[serde(skip_serializing_if = "Option::is_none")]
     reply_markup: Option<InlineKeyboardMarkup>,
 }
@@ -25,13 +21,8 @@ impl EditMessageReplyMarkup {
     ///
     /// * chat_id - Unique identifier for the target chat
     /// * message_id - Identifier of the sent message
-    pub fn new<C: Into<ChatId>>(chat_id: C, message_id: Integer) -> Self {
-        EditMessageReplyMarkup {
-            chat_id: Some(chat_id.into()),
-            message_id: Some(message_id),
-            inline_message_id: None,
-            reply_markup: None,
-        }
+    pub fn new<C: Into<ChatId>, M: Into<MessageId>>(chat_id: C, message_id: M) -> Self {
+        EditMessageReplyMarkup::with_target(EditMessageTarget::chat(chat_id, message_id))
     }
 
     /// Creates a new EditMessageReplyMarkup
@@ -40,10 +31,17 @@ impl EditMessageReplyMarkup {
     ///
     /// * inline_message_id - Identifier of the inline message
     pub fn with_inline_message_id<S: Into<String>>(inline_message_id: S) -> Self {
+        EditMessageReplyMarkup::with_target(EditMessageTarget::inline(inline_message_id))
+    }
+
+    /// Creates a new EditMessageReplyMarkup for a chat or an inline message
+    ///
+    /// # Arguments
+    ///
+    /// * target - Message to be edited
+    pub fn with_target(target: EditMessageTarget) -> Self {
         EditMessageReplyMarkup {
-            chat_id: None,
-            message_id: None,
-            inline_message_id: Some(inline_message_id.into()),
+            target,
             reply_markup: None,
         }
     }
@@ -0,0 +1,43 @@
+use crate::types::{ChatId, MessageId};
+use serde::Serialize;
+
+/// Message to be edited
+///
+/// Either a message in a chat or an inline message sent via the bot
+#[derive(Clone, Debug, Serialize)]
+#[serde(untagged)]
+pub enum EditMessageTarget {
+    /// Message sent to a chat
+    Chat {
+        /// Unique identifier for the target chat
+        chat_id: ChatId,
+        /// Identifier of the sent message
+        message_id: MessageId,
+    },
+    /// Inline message
+    Inline {
+        /// Identifier of the inline message
+        inline_message_id: String,
+    },
+}
+
+impl EditMessageTarget {
+    /// Message sent to a chat
+    ///
+    /// EditMessageResult::Message is returned when it's edited
+    pub fn chat<C: Into<ChatId>, M: Into<MessageId>>(chat_id: C, message_id: M) -> Self {
+        EditMessageTarget::Chat {
+            chat_id: chat_id.into(),
+            message_id: message_id.into(),
+        }
+    }
+
+    /// Inline message
+    ///
+    /// EditMessageResult::Bool is returned when it's edited
+    pub fn inline<S: Into<String>>(inline_message_id: S) -> Self {
+        EditMessageTarget::Inline {
+            inline_message_id: inline_message_id.into(),
+        }
+    }
+}
@@ -1,6 +1,6 @@
 use crate::{
-    methods::method::*,
-    types::{ChatId, EditMessageResult, InlineKeyboardMarkup, Integer, ParseMode},
+    methods::{message::EditMessageTarget, method::*},
+    types::{ChatId, EditMessageResult, InlineKeyboardMarkup, MessageId, ParseMode},
 };
 use failure::Error;
 use serde::Serialize;
@@ -8,12 +8,8 @@ use serde::Serialize;
 /// Edit text and game messages sent by the bot or via the bot (for inline bots)
 #[derive(Clone, Debug, Serialize)]
 pub struct EditMessageText {
-    #[serde(skip_serializing_if = "Option::is_none")]
-    chat_id: Option<ChatId>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    message_id: Option<Integer>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    inline_message_id: Option<String>,
+    #[serde(flatten)]
+    target: EditMessageTarget,
     text: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     parse_mode: Option<ParseMode>,
@@ -31,16 +27,8 @@ impl EditMessageText {
     /// * chat_id - Unique identifier for the target chat
     /// * message_id - Identifier of the sent message
     /// * text - New text of the message
-    pub fn new<C: Into<ChatId>, S: Into<String>>(chat_id: C, message_id: Integer, text: S) -> Self {
-        EditMessageText {
-            chat_id: Some(chat_id.into()),
-            message_id: Some(message_id),
-            inline_message_id: None,
-            text: text.into(),
-            parse_mode: None,
-            disable_web_page_preview: None,
-            reply_markup: None,
-        }
+    pub fn new<C: Into<ChatId>, S: Into<String>, M: Into<MessageId>>(chat_id: C, message_id: M, text: S) -> Self {
+        EditMessageText::with_target(EditMessageTarget::chat(chat_id, message_id), text)
     }
 
     /// Creates a new EditMessageText
@@ -50,10 +38,18 @@ impl EditMessageText {
     /// * inline_message_id - Identifier of the inline message
     /// * text - New text of the message
     pub fn with_inline_message_id<S: Into<String>>(inline_message_id: S, text: S) -> Self {
+        EditMessageText::with_target(EditMessageTarget::inline(inline_message_id), text)
+    }
+
+    /// Creates a new EditMessageText for a chat or an inline message
+    ///
+    /// # Arguments
+    ///
+    /// * target - Message to be edited
+    /// * text - New text of the message
+    pub fn with_target<S: Into<String>>(target: EditMessageTarget, text: S) -> Self {
         EditMessageText {
-            chat_id: None,
-            message_id: None,
-            inline_message_id: Some(inline_message_id.into()),
+            target,
             text: text.into(),
             parse_mode: None,
             disable_web_page_preview: None,
@@ -87,3 +83,37 @@ impl Method for EditMessageText {
         RequestBuilder::json("editMessageText", &self)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::methods::{RequestBody, DEFAULT_BASE_URL};
+    use serde_json::Value;
+
+    fn get_data(method: EditMessageText) -> Value {
+        let req = method.get_request().unwrap().build(DEFAULT_BASE_URL, "token");
+        assert_eq!(req.url, "https://api.telegram.org/bottoken/editMessageText");
+        match req.body {
+            RequestBody::Json(data) => serde_json::from_slice(&data).unwrap(),
+            body => panic!("Unexpected request body: {:?}", body),
+        }
+    }
+
+    #[test]
+    fn test_edit_message_text() {
+        let data = get_data(EditMessageText::new(1, 2, "text").parse_mode(ParseMode::Html));
+        assert_eq!(data["chat_id"], 1);
+        assert_eq!(data["message_id"], 2);
+        assert!(data.get("inline_message_id").is_none());
+        assert_eq!(data["text"], "text");
+        assert_eq!(data["parse_mode"], "HTML");
+
+        let data = get_data(EditMessageText::with_target(
+            EditMessageTarget::inline("inline-id"),
+            "text",
+        ));
+        assert_eq!(data["inline_message_id"], "inline-id");
+        assert!(data.get("chat_id").is_none());
+        assert!(data.get("message_id").is_none());
+    }
+}
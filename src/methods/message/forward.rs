@@ -1,6 +1,6 @@
 use crate::{
     methods::method::*,
-    types::{ChatId, Integer, Message},
+    types::{ChatId, Message, MessageId},
 };
 use failure::Error;
 use serde::Serialize;
@@ -12,7 +12,7 @@ pub struct ForwardMessage {
     from_chat_id: ChatId,
     #[serde(skip_serializing_if = "Option::is_none")]
     disable_notification: Option<bool>,
-    message_id: Integer,
+    message_id: MessageId,
 }
 
 impl ForwardMessage {
@@ -23,11 +23,16 @@ impl ForwardMessage {
     /// * chat_id - Unique identifier for the target chat
     /// * from_chat_id - Unique identifier for the chat where the original message was sent
     /// * message_id - Message identifier in the chat specified in from_chat_id
-    pub fn new<C: Into<ChatId>>(chat_id: C, from_chat_id: C, message_id: Integer) -> Self {
+    pub fn new<C, F, M>(chat_id: C, from_chat_id: F, message_id: M) -> Self
+    where
+        C: Into<ChatId>,
+        F: Into<ChatId>,
+        M: Into<MessageId>,
+    {
         ForwardMessage {
             chat_id: chat_id.into(),
             from_chat_id: from_chat_id.into(),
-            message_id,
+            message_id: message_id.into(),
             disable_notification: None,
         }
     }
@@ -48,3 +53,31 @@ impl Method for ForwardMessage {
         RequestBuilder::json("forwardMessage", &self)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::methods::DEFAULT_BASE_URL;
+    use serde_json::Value;
+
+    #[test]
+    fn test_forward_message() {
+        let req = ForwardMessage::new("@target_channel", -100, 3)
+            .disable_notification(true)
+            .get_request()
+            .unwrap()
+            .build(DEFAULT_BASE_URL, "token");
+        assert_eq!(req.method, RequestMethod::Post);
+        assert_eq!(req.url, "https://api.telegram.org/bottoken/forwardMessage");
+        match req.body {
+            RequestBody::Json(data) => {
+                let data: Value = serde_json::from_slice(&data).unwrap();
+                assert_eq!(data["chat_id"], "@target_channel");
+                assert_eq!(data["from_chat_id"], -100);
+                assert_eq!(data["message_id"], 3);
+                assert_eq!(data["disable_notification"], true);
+            }
+            data => panic!("Unexpected request data: {:?}", data),
+        }
+    }
+}
@@ -1,6 +1,7 @@
+mod copy;
 mod delete;
 mod edit;
 mod forward;
 mod send;
 
-pub use self::{delete::*, edit::*, forward::*, send::*};
+pub use self::{copy::*, delete::*, edit::*, forward::*, send::*};
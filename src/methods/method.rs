@@ -1,8 +1,17 @@
+use crate::{
+    methods::form::{Form, FormValue},
+    types::{ChatId, InputFile, Integer, ParseMode},
+};
 use failure::Error;
-use serde::ser::Serialize;
-use std::fmt::Display;
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use std::{borrow::Cow, fmt::Display};
 
-const BASE_URL: &str = "https://api.telegram.org";
+/// URL of the public Bot API server
+pub const DEFAULT_BASE_URL: &str = "https://api.telegram.org";
+
+/// Maximum length of a caption in UTF-16 code units after entities parsing
+pub const MAX_CAPTION_LENGTH: usize = 1024;
 
 /// Represents an API method
 pub trait Method {
@@ -26,7 +35,95 @@ impl RequestBuilder {
         Ok(RequestBuilder {
             method: RequestMethod::Post,
             body: RequestBody::Json(serde_json::to_vec(s)?),
-            url: RequestUrl(path),
+            url: RequestUrl(Cow::Borrowed(path)),
+        })
+    }
+
+    /// Creates a multipart/form-data request when some of files have to be uploaded
+    ///
+    /// Otherwise a JSON request is created, the same as RequestBuilder::json does
+    pub(crate) fn with_files(
+        path: &'static str,
+        s: &impl Serialize,
+        files: &[(&'static str, Option<&InputFile>)],
+    ) -> Result<RequestBuilder, Error> {
+        let uploads: Vec<(&'static str, &InputFile)> = files
+            .iter()
+            .filter_map(|&(name, file)| file.filter(|file| file.is_upload()).map(|file| (name, file)))
+            .collect();
+        if uploads.is_empty() {
+            return RequestBuilder::json(path, s);
+        }
+        Ok(RequestBuilder::multipart(path, serde_json::to_value(s)?, uploads))
+    }
+
+    /// Creates a multipart/form-data request where files are referenced using attach://<file_attach_name>
+    ///
+    /// Each file is identified by a JSON pointer to a value in serialized method (e.g. "/media/0/media"),
+    /// the value of a file to be uploaded is replaced by a reference to the corresponding part
+    /// A JSON request is created when there is nothing to upload
+    pub(crate) fn with_attachments(
+        path: &'static str,
+        s: &impl Serialize,
+        files: &[(String, &InputFile)],
+    ) -> Result<RequestBuilder, Error> {
+        let mut value = serde_json::to_value(s)?;
+        let mut uploads = Vec::new();
+        for (pointer, file) in files.iter().filter(|(_, file)| file.is_upload()) {
+            let name = format!("tgbot_attachment_{}", uploads.len());
+            match value.pointer_mut(pointer) {
+                Some(target) => *target = JsonValue::String(format!("attach://{}", name)),
+                None => return Err(failure::format_err!("Can not find a file at {}", pointer)),
+            }
+            uploads.push((name, *file));
+        }
+        if uploads.is_empty() {
+            return RequestBuilder::json(path, s);
+        }
+        Ok(RequestBuilder::multipart(path, value, uploads))
+    }
+
+    fn multipart<N: Into<String>>(
+        path: &'static str,
+        value: JsonValue,
+        uploads: Vec<(N, &InputFile)>,
+    ) -> RequestBuilder {
+        let mut form = Form::default();
+        if let JsonValue::Object(fields) = value {
+            for (name, value) in fields {
+                match value {
+                    JsonValue::Null => {}
+                    JsonValue::String(value) => form.insert_field(name, value),
+                    value => form.insert_field(name, value.to_string()),
+                }
+            }
+        }
+        for (name, file) in uploads {
+            form.insert_field(name, file.clone());
+        }
+        RequestBuilder {
+            method: RequestMethod::Post,
+            body: RequestBody::Multipart(form),
+            url: RequestUrl(Cow::Borrowed(path)),
+        }
+    }
+
+    /// Creates a request for a method which is not known at compile time
+    ///
+    /// A GET request without a body is created when params is null
+    pub(crate) fn raw(name: String, params: &JsonValue) -> Result<RequestBuilder, Error> {
+        let url = RequestUrl(Cow::Owned(name));
+        Ok(match params {
+            JsonValue::Null => RequestBuilder {
+                method: RequestMethod::Get,
+                body: RequestBody::Empty,
+                url,
+            },
+            params => RequestBuilder {
+                method: RequestMethod::Post,
+                body: RequestBody::Json(serde_json::to_vec(params)?),
+                url,
+            },
         })
     }
 
@@ -34,44 +131,412 @@ impl RequestBuilder {
         Ok(RequestBuilder {
             method: RequestMethod::Get,
             body: RequestBody::Empty,
-            url: RequestUrl(path),
+            url: RequestUrl(Cow::Borrowed(path)),
         })
     }
 
-    pub(crate) fn build(self, token: &str) -> Request {
+    /// Returns name of the method
+    pub(crate) fn method_name(&self) -> &str {
+        &self.url.0
+    }
+
+    /// Returns identifier of a target chat when the method has chat_id parameter
+    pub(crate) fn chat_id(&self) -> Option<ChatId> {
+        #[derive(Deserialize)]
+        struct ChatIdField {
+            chat_id: Option<JsonValue>,
+        }
+
+        match self.body {
+            RequestBody::Json(ref data) => match serde_json::from_slice::<ChatIdField>(data).ok()?.chat_id? {
+                JsonValue::Number(value) => value.as_i64().map(ChatId::Id),
+                JsonValue::String(value) => Some(parse_chat_id(&value)),
+                _ => None,
+            },
+            RequestBody::Multipart(ref form) => form.fields.iter().find_map(|(name, value)| match value {
+                FormValue::Text(value) if name == "chat_id" => Some(parse_chat_id(value)),
+                _ => None,
+            }),
+            RequestBody::Empty => None,
+        }
+    }
+
+    /// Returns a JSON object with method parameters and method field set to the name of the method
+    ///
+    /// Used to answer a webhook request, files can not be uploaded this way
+    pub(crate) fn into_webhook_reply(self) -> Result<Vec<u8>, Error> {
+        let mut value = match self.body {
+            RequestBody::Json(data) => serde_json::from_slice(&data)?,
+            RequestBody::Empty => JsonValue::Object(Default::default()),
+            RequestBody::Multipart(_) => {
+                return Err(failure::format_err!(
+                    "Method {} uploads files and can not be used as a webhook reply",
+                    self.url.0
+                ))
+            }
+        };
+        match value {
+            JsonValue::Object(ref mut fields) => {
+                fields.insert(String::from("method"), JsonValue::String(self.url.0.into_owned()));
+            }
+            _ => return Err(failure::format_err!("Parameters of {} are not an object", self.url.0)),
+        }
+        Ok(serde_json::to_vec(&value)?)
+    }
+
+    pub(crate) fn build(self, base_url: &str, token: &str) -> Request {
         Request {
             method: self.method,
-            url: self.url.build(token),
+            url: self.url.build(base_url, token),
+            headers: Vec::new(),
             body: self.body,
         }
     }
 }
 
 /// Information about HTTP request
+///
+/// Passed to an executor to be sent
 #[derive(Clone, Debug)]
-pub(crate) struct Request {
-    pub(crate) method: RequestMethod,
-    pub(crate) url: String,
-    pub(crate) body: RequestBody,
+pub struct Request {
+    /// HTTP method
+    pub method: RequestMethod,
+    /// URL including bot token
+    pub url: String,
+    /// Additional HTTP headers, set by middlewares
+    pub headers: Vec<(String, String)>,
+    /// Request body
+    pub body: RequestBody,
 }
 
+impl Request {
+    /// Returns a copy of the request sent to another chat
+    ///
+    /// Returns None when the request has no chat_id parameter
+    pub(crate) fn with_chat_id(&self, chat_id: Integer) -> Option<Request> {
+        let body = match self.body {
+            RequestBody::Json(ref data) => {
+                let mut value: JsonValue = serde_json::from_slice(data).ok()?;
+                *value.get_mut("chat_id")? = JsonValue::from(chat_id);
+                RequestBody::Json(serde_json::to_vec(&value).ok()?)
+            }
+            RequestBody::Multipart(ref form) => {
+                let mut form = form.clone();
+                let (_, value) = form.fields.iter_mut().find(|(name, _)| name == "chat_id")?;
+                *value = FormValue::Text(chat_id.to_string());
+                RequestBody::Multipart(form)
+            }
+            RequestBody::Empty => return None,
+        };
+        Some(Request {
+            method: self.method,
+            url: self.url.clone(),
+            headers: self.headers.clone(),
+            body,
+        })
+    }
+}
+
+/// HTTP method of a request
 #[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
-pub(crate) enum RequestMethod {
+pub enum RequestMethod {
+    /// GET
     Get,
+    /// POST
     Post,
 }
 
 #[derive(Clone, Debug)]
-struct RequestUrl(&'static str);
+struct RequestUrl(Cow<'static, str>);
 
 impl RequestUrl {
-    fn build(&self, token: impl Display) -> String {
-        format!("{}/bot{}/{}", BASE_URL, token, self.0)
+    fn build(&self, base_url: &str, token: impl Display) -> String {
+        format!("{}/bot{}/{}", base_url.trim_end_matches('/'), token, self.0)
     }
 }
 
+/// Returns URL to download a file
+pub(crate) fn build_file_url(base_url: &str, token: &str, file_path: &str) -> String {
+    format!("{}/file/bot{}/{}", base_url.trim_end_matches('/'), token, file_path)
+}
+
+/// Body of a request
 #[derive(Clone, Debug)]
-pub(crate) enum RequestBody {
+pub enum RequestBody {
+    /// Serialized JSON, sent with application/json content type
     Json(Vec<u8>),
+    /// Form to be sent as multipart/form-data, see executor::encode_form()
+    Multipart(Form),
+    /// Request without a body
     Empty,
 }
+
+/// An error when building a request
+#[derive(Debug, failure::Fail)]
+pub enum RequestError {
+    /// Value of a field is longer than allowed
+    ///
+    /// Length is measured in UTF-16 code units
+    #[fail(display = "Field \"{}\" is longer than {} characters", field, max)]
+    TooLong {
+        /// Name of the field
+        field: &'static str,
+        /// Maximum allowed length
+        max: usize,
+    },
+    /// A list contains more items than allowed
+    #[fail(display = "Field \"{}\" contains more than {} items", field, max)]
+    TooMany {
+        /// Name of the field
+        field: &'static str,
+        /// Maximum allowed number of items
+        max: usize,
+    },
+    /// A number is out of the allowed range
+    #[fail(display = "Field \"{}\" must be between {} and {}", field, min, max)]
+    OutOfRange {
+        /// Name of the field
+        field: &'static str,
+        /// Minimum allowed value
+        min: Integer,
+        /// Maximum allowed value
+        max: Integer,
+    },
+    /// A required field is empty
+    #[fail(display = "Field \"{}\" is empty", field)]
+    Empty {
+        /// Name of the field
+        field: &'static str,
+    },
+    /// A file must be uploaded, file_id and URL are not accepted
+    #[fail(display = "Field \"{}\" accepts only uploaded files", field)]
+    UploadRequired {
+        /// Name of the field
+        field: &'static str,
+    },
+}
+
+/// Form fields are strings, so a numeric ID is recognized here
+fn parse_chat_id(value: &str) -> ChatId {
+    match value.parse() {
+        Ok(id) => ChatId::Id(id),
+        Err(_) => ChatId::Username(value.to_string()),
+    }
+}
+
+/// Checks length of a caption
+///
+/// Telegram does not count markup, so a caption with parse_mode is left to the server
+pub(crate) fn check_caption(caption: &Option<String>, parse_mode: Option<ParseMode>) -> Result<(), RequestError> {
+    match (caption, parse_mode) {
+        (Some(caption), None) => check_length("caption", caption, MAX_CAPTION_LENGTH),
+        _ => Ok(()),
+    }
+}
+
+pub(crate) fn check_range(field: &'static str, value: Integer, min: Integer, max: Integer) -> Result<(), RequestError> {
+    if value < min || value > max {
+        Err(RequestError::OutOfRange { field, min, max })
+    } else {
+        Ok(())
+    }
+}
+
+pub(crate) fn check_length(field: &'static str, value: &str, max: usize) -> Result<(), RequestError> {
+    if value.encode_utf16().count() > max {
+        Err(RequestError::TooLong { field, max })
+    } else {
+        Ok(())
+    }
+}
+
+/// Truncates a caption to MAX_CAPTION_LENGTH UTF-16 code units
+///
+/// Characters are never split, so the result may be slightly shorter than the limit
+pub fn truncate_caption<S: Into<String>>(caption: S) -> String {
+    let mut caption = caption.into();
+    let mut length = 0;
+    let end = caption.char_indices().find_map(|(idx, c)| {
+        length += c.len_utf16();
+        if length > MAX_CAPTION_LENGTH {
+            Some(idx)
+        } else {
+            None
+        }
+    });
+    if let Some(end) = end {
+        caption.truncate(end);
+    }
+    caption
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::methods::{GetMe, SendPhoto};
+    use std::{env, fs};
+
+    #[test]
+    fn test_into_webhook_reply() {
+        let reply = GetMe.get_request().unwrap().into_webhook_reply().unwrap();
+        assert_eq!(reply, br#"{"method":"getMe"}"#);
+        let reply = SendPhoto::new(1, "photo")
+            .get_request()
+            .unwrap()
+            .into_webhook_reply()
+            .unwrap();
+        let reply: JsonValue = serde_json::from_slice(&reply).unwrap();
+        assert_eq!(reply["method"], "sendPhoto");
+        assert_eq!(reply["photo"], "photo");
+        assert!(SendPhoto::new(1, InputFile::bytes("photo.png", vec![1]))
+            .get_request()
+            .unwrap()
+            .into_webhook_reply()
+            .is_err());
+    }
+
+    #[test]
+    fn test_caption_length() {
+        let caption = "a".repeat(MAX_CAPTION_LENGTH);
+        assert!(check_caption(&Some(caption.clone()), None).is_ok());
+        assert_eq!(truncate_caption(caption.as_str()), caption);
+
+        let caption = format!("{}b", caption);
+        match check_caption(&Some(caption.clone()), None) {
+            Err(RequestError::TooLong { field, max }) => {
+                assert_eq!(field, "caption");
+                assert_eq!(max, MAX_CAPTION_LENGTH);
+            }
+            res => panic!("Unexpected result: {:?}", res),
+        }
+        assert!(SendPhoto::new(1, "photo")
+            .caption(caption.as_str())
+            .get_request()
+            .is_err());
+        let markup = format!("<b>{}</b>", "a".repeat(MAX_CAPTION_LENGTH));
+        assert!(check_caption(&Some(markup.clone()), Some(ParseMode::Html)).is_ok());
+        assert!(SendPhoto::new(1, "photo")
+            .caption(markup)
+            .parse_mode(ParseMode::Html)
+            .get_request()
+            .is_ok());
+        assert_eq!(truncate_caption(caption).encode_utf16().count(), MAX_CAPTION_LENGTH);
+
+        // surrogate pair must not be split
+        let caption = format!("{}\u{1F600}", "a".repeat(MAX_CAPTION_LENGTH - 1));
+        assert!(check_caption(&Some(caption.clone()), None).is_err());
+        assert_eq!(truncate_caption(caption), "a".repeat(MAX_CAPTION_LENGTH - 1));
+    }
+
+    #[test]
+    fn test_with_files() {
+        let request = SendPhoto::new(1, "file-id")
+            .get_request()
+            .unwrap()
+            .build(DEFAULT_BASE_URL, "token");
+        if let RequestBody::Json(data) = request.body {
+            let data: JsonValue = serde_json::from_slice(&data).unwrap();
+            assert_eq!(data["photo"], "file-id");
+        } else {
+            panic!("Unexpected request body: {:?}", request.body);
+        }
+
+        let path = env::temp_dir().join("tgbot-with-files-test.jpg");
+        fs::write(&path, b"test").unwrap();
+        let request = SendPhoto::new(1, InputFile::path(&path).unwrap())
+            .caption("test")
+            .get_request()
+            .unwrap()
+            .build(DEFAULT_BASE_URL, "token");
+        fs::remove_file(&path).unwrap();
+        assert_eq!(request.method, RequestMethod::Post);
+        assert_eq!(request.url, "https://api.telegram.org/bottoken/sendPhoto");
+        if let RequestBody::Multipart(form) = request.body {
+            let names: Vec<&str> = form.fields.iter().map(|(name, _)| name.as_str()).collect();
+            assert_eq!(names, vec!["caption", "chat_id", "photo"]);
+            match form.fields[1].1 {
+                FormValue::Text(ref value) => assert_eq!(value, "1"),
+                ref value => panic!("Unexpected value: {:?}", value),
+            }
+            match form.fields[2].1 {
+                FormValue::File(ref file) => assert!(file.is_upload()),
+                ref value => panic!("Unexpected value: {:?}", value),
+            }
+        } else {
+            panic!("Unexpected request body: {:?}", request.body);
+        }
+    }
+
+    #[test]
+    fn test_file_url() {
+        assert_eq!(
+            build_file_url(DEFAULT_BASE_URL, "token", "photos/file_1.jpg"),
+            "https://api.telegram.org/file/bottoken/photos/file_1.jpg"
+        );
+        assert_eq!(
+            build_file_url("http://localhost:8081/", "token", "photos/file_1.jpg"),
+            "http://localhost:8081/file/bottoken/photos/file_1.jpg"
+        );
+    }
+
+    #[test]
+    fn test_chat_id() {
+        let builder = SendPhoto::new(1, "photo-id").get_request().unwrap();
+        assert!(matches!(builder.chat_id(), Some(ChatId::Id(1))));
+        let path = env::current_dir().unwrap().join("Cargo.toml");
+        let builder = SendPhoto::new("@channel", InputFile::path(path.clone()).unwrap())
+            .get_request()
+            .unwrap();
+        assert!(matches!(builder.chat_id(), Some(ChatId::Username(ref username)) if username == "@channel"));
+        let builder = SendPhoto::new(-100, InputFile::path(path).unwrap())
+            .get_request()
+            .unwrap();
+        assert!(matches!(builder.chat_id(), Some(ChatId::Id(-100))));
+        assert!(GetMe.get_request().unwrap().chat_id().is_none());
+    }
+
+    #[test]
+    fn test_request_with_chat_id() {
+        let request = SendPhoto::new(1, "photo-id")
+            .get_request()
+            .unwrap()
+            .build(DEFAULT_BASE_URL, "token")
+            .with_chat_id(-1001)
+            .unwrap();
+        assert_eq!(request.url, "https://api.telegram.org/bottoken/sendPhoto");
+        if let RequestBody::Json(data) = request.body {
+            let data: JsonValue = serde_json::from_slice(&data).unwrap();
+            assert_eq!(data["chat_id"], -1001);
+            assert_eq!(data["photo"], "photo-id");
+        } else {
+            panic!("Unexpected request body: {:?}", request.body);
+        }
+
+        let path = env::current_dir().unwrap().join("Cargo.toml");
+        let request = SendPhoto::new(1, InputFile::path(path).unwrap())
+            .get_request()
+            .unwrap()
+            .build(DEFAULT_BASE_URL, "token")
+            .with_chat_id(-1001)
+            .unwrap();
+        if let RequestBody::Multipart(form) = request.body {
+            match form.fields[0] {
+                (ref name, FormValue::Text(ref value)) => {
+                    assert_eq!((name.as_str(), value.as_str()), ("chat_id", "-1001"))
+                }
+                ref field => panic!("Unexpected field: {:?}", field),
+            }
+        } else {
+            panic!("Unexpected request body: {:?}", request.body);
+        }
+
+        let request = GetMe.get_request().unwrap().build(DEFAULT_BASE_URL, "token");
+        assert!(request.with_chat_id(-1001).is_none());
+    }
+
+    #[test]
+    fn test_base_url() {
+        let request = GetMe.get_request().unwrap().build("http://localhost:8081", "token");
+        assert_eq!(request.url, "http://localhost:8081/bottoken/getMe");
+    }
+}
@@ -1,17 +1,23 @@
 mod answer;
 mod chat;
 mod chat_member;
+mod close;
+mod form;
 mod game;
 mod get_file;
+mod log_out;
 mod message;
 mod method;
 mod passport;
+mod raw;
 mod send;
 mod sticker;
 mod updates;
 mod user;
 
+pub use self::form::{Form, FormValue};
+
 pub use self::{
-    answer::*, chat::*, chat_member::*, game::*, get_file::*, message::*, method::*, passport::*, send::*, sticker::*,
-    updates::*, user::*,
+    answer::*, chat::*, chat_member::*, close::*, game::*, get_file::*, log_out::*, message::*, method::*, passport::*,
+    raw::*, send::*, sticker::*, updates::*, user::*,
 };
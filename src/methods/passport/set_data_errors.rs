@@ -1,6 +1,6 @@
 use crate::{
     methods::method::*,
-    types::{Integer, PassportElementError},
+    types::{PassportElementError, UserId},
 };
 use failure::Error;
 use serde::Serialize;
@@ -19,7 +19,7 @@ use serde::Serialize;
 /// Supply some details in the error message to make sure the user knows how to correct the issues
 #[derive(Clone, Debug, Serialize)]
 pub struct SetPassportDataErrors {
-    user_id: Integer,
+    user_id: UserId,
     errors: Vec<PassportElementError>,
 }
 
@@ -30,8 +30,11 @@ impl SetPassportDataErrors {
     ///
     /// * user_id - User identifier
     /// * errors - Array describing the errors
-    pub fn new(user_id: Integer, errors: Vec<PassportElementError>) -> Self {
-        SetPassportDataErrors { user_id, errors }
+    pub fn new<U: Into<UserId>>(user_id: U, errors: Vec<PassportElementError>) -> Self {
+        SetPassportDataErrors {
+            user_id: user_id.into(),
+            errors,
+        }
     }
 }
 
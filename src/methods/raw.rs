@@ -0,0 +1,67 @@
+use crate::methods::method::*;
+use failure::Error;
+use serde_json::Value;
+
+/// Calls a method by its name with arbitrary parameters
+///
+/// Use it to call methods which are not supported by the library yet,
+/// the result is returned as is
+#[derive(Clone, Debug)]
+pub struct RawMethod {
+    name: String,
+    params: Value,
+}
+
+impl RawMethod {
+    /// Creates a new RawMethod
+    ///
+    /// # Arguments
+    ///
+    /// * name - Name of a method, e.g. "getMe"
+    /// * params - JSON object with parameters, pass Value::Null when there are no parameters
+    pub fn new<N: Into<String>>(name: N, params: Value) -> Self {
+        RawMethod {
+            name: name.into(),
+            params,
+        }
+    }
+}
+
+impl Method for RawMethod {
+    type Response = Value;
+
+    fn get_request(&self) -> Result<RequestBuilder, Error> {
+        RequestBuilder::raw(self.name.clone(), &self.params)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::methods::DEFAULT_BASE_URL;
+    use serde_json::json;
+
+    #[test]
+    fn test_raw_method() {
+        let request = RawMethod::new("sendDice", json!({"chat_id": 1, "emoji": "🎲"}))
+            .get_request()
+            .unwrap()
+            .build(DEFAULT_BASE_URL, "token");
+        assert_eq!(request.method, RequestMethod::Post);
+        assert_eq!(request.url, "https://api.telegram.org/bottoken/sendDice");
+        if let RequestBody::Json(data) = request.body {
+            let data: Value = serde_json::from_slice(&data).unwrap();
+            assert_eq!(data, json!({"chat_id": 1, "emoji": "🎲"}));
+        } else {
+            panic!("Unexpected request body: {:?}", request.body);
+        }
+
+        let request = RawMethod::new("getMe", Value::Null)
+            .get_request()
+            .unwrap()
+            .build(DEFAULT_BASE_URL, "token");
+        assert_eq!(request.method, RequestMethod::Get);
+        assert_eq!(request.url, "https://api.telegram.org/bottoken/getMe");
+        assert!(matches!(request.body, RequestBody::Empty));
+    }
+}
@@ -1,6 +1,6 @@
 use crate::{
     methods::method::*,
-    types::{ChatId, Integer, Message, ParseMode, ReplyMarkup},
+    types::{ChatId, InputFile, Integer, Message, MessageId, ParseMode, ReplyMarkup},
 };
 use failure::Error;
 use serde::Serialize;
@@ -11,7 +11,7 @@ use serde::Serialize;
 #[derive(Clone, Debug, Serialize)]
 pub struct SendAnimation {
     chat_id: ChatId,
-    animation: String,
+    animation: InputFile,
     #[serde(skip_serializing_if = "Option::is_none")]
     duration: Option<Integer>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -19,7 +19,7 @@ pub struct SendAnimation {
     #[serde(skip_serializing_if = "Option::is_none")]
     height: Option<Integer>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    thumb: Option<String>,
+    thumb: Option<InputFile>,
     #[serde(skip_serializing_if = "Option::is_none")]
     caption: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -27,7 +27,7 @@ pub struct SendAnimation {
     #[serde(skip_serializing_if = "Option::is_none")]
     disable_notification: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    reply_to_message_id: Option<Integer>,
+    reply_to_message_id: Option<MessageId>,
     #[serde(skip_serializing_if = "Option::is_none")]
     reply_markup: Option<ReplyMarkup>,
 }
@@ -41,8 +41,8 @@ impl SendAnimation {
     /// * animation - Animation to send
     ///               Pass a file_id as String to send an animation that exists on the Telegram servers (recommended),
     ///               pass an HTTP URL as a String for Telegram to get an animation from the Internet,
-    ///               or upload a new animation using multipart/form-data
-    pub fn new<C: Into<ChatId>, S: Into<String>>(chat_id: C, animation: S) -> Self {
+    ///               or pass InputFile::path() to upload a new animation
+    pub fn new<C: Into<ChatId>, F: Into<InputFile>>(chat_id: C, animation: F) -> Self {
         SendAnimation {
             chat_id: chat_id.into(),
             animation: animation.into(),
@@ -82,9 +82,8 @@ impl SendAnimation {
     /// A thumbnail‘s width and height should not exceed 90
     /// Ignored if the file is not uploaded using multipart/form-data
     /// Thumbnails can’t be reused and can be only uploaded as a new file,
-    /// so you can pass “attach://<file_attach_name>”
-    /// if the thumbnail was uploaded using multipart/form-data under <file_attach_name>
-    pub fn thumb<S: Into<String>>(mut self, thumb: S) -> Self {
+    /// so pass a file created using InputFile::path()
+    pub fn thumb<F: Into<InputFile>>(mut self, thumb: F) -> Self {
         self.thumb = Some(thumb.into());
         self
     }
@@ -113,8 +112,8 @@ impl SendAnimation {
     }
 
     /// If the message is a reply, ID of the original message
-    pub fn reply_to_message_id(mut self, message_id: Integer) -> Self {
-        self.reply_to_message_id = Some(message_id);
+    pub fn reply_to_message_id<M: Into<MessageId>>(mut self, message_id: M) -> Self {
+        self.reply_to_message_id = Some(message_id.into());
         self
     }
 
@@ -129,6 +128,11 @@ impl Method for SendAnimation {
     type Response = Message;
 
     fn get_request(&self) -> Result<RequestBuilder, Error> {
-        RequestBuilder::json("sendAnimation", &self)
+        check_caption(&self.caption, self.parse_mode)?;
+        RequestBuilder::with_files(
+            "sendAnimation",
+            &self,
+            &[("animation", Some(&self.animation)), ("thumb", self.thumb.as_ref())],
+        )
     }
 }
@@ -1,6 +1,6 @@
 use crate::{
     methods::method::*,
-    types::{ChatId, Integer, Message, ParseMode, ReplyMarkup},
+    types::{ChatId, InputFile, Integer, Message, MessageId, ParseMode, ReplyMarkup},
 };
 use failure::Error;
 use serde::Serialize;
@@ -14,7 +14,7 @@ use serde::Serialize;
 #[derive(Clone, Debug, Serialize)]
 pub struct SendAudio {
     chat_id: ChatId,
-    audio: String,
+    audio: InputFile,
     #[serde(skip_serializing_if = "Option::is_none")]
     caption: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -26,11 +26,11 @@ pub struct SendAudio {
     #[serde(skip_serializing_if = "Option::is_none")]
     title: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    thumb: Option<String>,
+    thumb: Option<InputFile>,
     #[serde(skip_serializing_if = "Option::is_none")]
     disable_notification: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    reply_to_message_id: Option<Integer>,
+    reply_to_message_id: Option<MessageId>,
     #[serde(skip_serializing_if = "Option::is_none")]
     reply_markup: Option<ReplyMarkup>,
 }
@@ -44,8 +44,8 @@ impl SendAudio {
     /// * audio - Audio file to send
     ///           Pass a file_id as String to send an audio file that exists on the Telegram servers (recommended),
     ///           pass an HTTP URL as a String for Telegram to get an audio file from the Internet,
-    ///           or upload a new one using multipart/form-data
-    pub fn new<C: Into<ChatId>, S: Into<String>>(chat_id: C, audio: S) -> Self {
+    ///           or pass InputFile::path() to upload a new one
+    pub fn new<C: Into<ChatId>, F: Into<InputFile>>(chat_id: C, audio: F) -> Self {
         SendAudio {
             chat_id: chat_id.into(),
             audio: audio.into(),
@@ -98,9 +98,8 @@ impl SendAudio {
     ///
     /// Ignored if the file is not uploaded using multipart/form-data
     /// Thumbnails can’t be reused and can be only uploaded as a new file,
-    /// so you can pass “attach://<file_attach_name>” if the thumbnail
-    /// was uploaded using multipart/form-data under <file_attach_name>
-    pub fn thumb<S: Into<String>>(mut self, thumb: S) -> Self {
+    /// so pass a file created using InputFile::path()
+    pub fn thumb<F: Into<InputFile>>(mut self, thumb: F) -> Self {
         self.thumb = Some(thumb.into());
         self
     }
@@ -114,8 +113,8 @@ impl SendAudio {
     }
 
     /// If the message is a reply, ID of the original message
-    pub fn reply_to_message_id(mut self, reply_to_message_id: Integer) -> Self {
-        self.reply_to_message_id = Some(reply_to_message_id);
+    pub fn reply_to_message_id<M: Into<MessageId>>(mut self, reply_to_message_id: M) -> Self {
+        self.reply_to_message_id = Some(reply_to_message_id.into());
         self
     }
 
@@ -130,6 +129,11 @@ impl Method for SendAudio {
     type Response = Message;
 
     fn get_request(&self) -> Result<RequestBuilder, Error> {
-        RequestBuilder::json("sendAudio", &self)
+        check_caption(&self.caption, self.parse_mode)?;
+        RequestBuilder::with_files(
+            "sendAudio",
+            &self,
+            &[("audio", Some(&self.audio)), ("thumb", self.thumb.as_ref())],
+        )
     }
 }
@@ -44,3 +44,34 @@ impl Method for SendChatAction {
         RequestBuilder::json("sendChatAction", &self)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::methods::{RequestBody, DEFAULT_BASE_URL};
+    use serde_json::Value;
+
+    #[test]
+    fn test_send_chat_action() {
+        for (action, expected) in [
+            (ChatAction::ChooseSticker, "choose_sticker"),
+            (ChatAction::RecordVoice, "record_voice"),
+            (ChatAction::Typing, "typing"),
+            (ChatAction::UploadVideoNote, "upload_video_note"),
+            (ChatAction::UploadVoice, "upload_voice"),
+        ] {
+            let req = SendChatAction::new(1, action)
+                .get_request()
+                .unwrap()
+                .build(DEFAULT_BASE_URL, "token");
+            assert_eq!(req.url, "https://api.telegram.org/bottoken/sendChatAction");
+            if let RequestBody::Json(data) = req.body {
+                let data: Value = serde_json::from_slice(&data).unwrap();
+                assert_eq!(data["chat_id"], 1);
+                assert_eq!(data["action"], expected);
+            } else {
+                panic!("Unexpected request body: {:?}", req.body);
+            }
+        }
+    }
+}
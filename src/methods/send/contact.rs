@@ -1,6 +1,6 @@
 use crate::{
     methods::method::*,
-    types::{ChatId, Integer, Message, ReplyMarkup},
+    types::{ChatId, Message, MessageId, ReplyMarkup},
 };
 use failure::Error;
 use serde::Serialize;
@@ -18,7 +18,7 @@ pub struct SendContact {
     #[serde(skip_serializing_if = "Option::is_none")]
     disable_notification: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    reply_to_message_id: Option<Integer>,
+    reply_to_message_id: Option<MessageId>,
     #[serde(skip_serializing_if = "Option::is_none")]
     reply_markup: Option<ReplyMarkup>,
 }
@@ -65,8 +65,8 @@ impl SendContact {
     }
 
     /// If the message is a reply, ID of the original message
-    pub fn reply_to_message_id(mut self, reply_to_message_id: Integer) -> Self {
-        self.reply_to_message_id = Some(reply_to_message_id);
+    pub fn reply_to_message_id<M: Into<MessageId>>(mut self, reply_to_message_id: M) -> Self {
+        self.reply_to_message_id = Some(reply_to_message_id.into());
         self
     }
 
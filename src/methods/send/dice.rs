@@ -0,0 +1,74 @@
+use crate::{
+    methods::method::*,
+    types::{ChatId, Message, MessageId, ReplyMarkup},
+};
+use failure::Error;
+use serde::Serialize;
+
+/// Send a dice, which will have a random value
+#[derive(Clone, Debug, Serialize)]
+pub struct SendDice {
+    chat_id: ChatId,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    emoji: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    disable_notification: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reply_to_message_id: Option<MessageId>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reply_markup: Option<ReplyMarkup>,
+}
+
+impl SendDice {
+    /// Creates a new SendDice with empty optional parameters
+    ///
+    /// # Arguments
+    ///
+    /// * chat_id - Unique identifier for the target chat
+    pub fn new<C: Into<ChatId>>(chat_id: C) -> Self {
+        SendDice {
+            chat_id: chat_id.into(),
+            emoji: None,
+            disable_notification: None,
+            reply_to_message_id: None,
+            reply_markup: None,
+        }
+    }
+
+    /// Emoji on which the dice throw animation is based
+    ///
+    /// Currently, must be one of “🎲”, “🎯”, or “🏀”
+    /// Defaults to “🎲”
+    pub fn emoji<S: Into<String>>(mut self, emoji: S) -> Self {
+        self.emoji = Some(emoji.into());
+        self
+    }
+
+    /// Sends the message silently
+    ///
+    /// Users will receive a notification with no sound
+    pub fn disable_notification(mut self, disable_notification: bool) -> Self {
+        self.disable_notification = Some(disable_notification);
+        self
+    }
+
+    /// If the message is a reply, ID of the original message
+    pub fn reply_to_message_id<M: Into<MessageId>>(mut self, reply_to_message_id: M) -> Self {
+        self.reply_to_message_id = Some(reply_to_message_id.into());
+        self
+    }
+
+    /// Additional interface options
+    pub fn reply_markup<R: Into<ReplyMarkup>>(mut self, reply_markup: R) -> Self {
+        self.reply_markup = Some(reply_markup.into());
+        self
+    }
+}
+
+impl Method for SendDice {
+    type Response = Message;
+
+    fn get_request(&self) -> Result<RequestBuilder, Error> {
+        RequestBuilder::json("sendDice", &self)
+    }
+}
@@ -1,6 +1,6 @@
 use crate::{
     methods::method::*,
-    types::{ChatId, Float, Integer, Message, ReplyMarkup},
+    types::{ChatId, Float, Integer, Message, MessageId, ReplyMarkup},
 };
 use failure::Error;
 use serde::Serialize;
@@ -14,9 +14,15 @@ pub struct SendLocation {
     #[serde(skip_serializing_if = "Option::is_none")]
     live_period: Option<Integer>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    horizontal_accuracy: Option<Float>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    heading: Option<Integer>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    proximity_alert_radius: Option<Integer>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     disable_notification: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    reply_to_message_id: Option<Integer>,
+    reply_to_message_id: Option<MessageId>,
     #[serde(skip_serializing_if = "Option::is_none")]
     reply_markup: Option<ReplyMarkup>,
 }
@@ -35,6 +41,9 @@ impl SendLocation {
             latitude,
             longitude,
             live_period: None,
+            horizontal_accuracy: None,
+            heading: None,
+            proximity_alert_radius: None,
             disable_notification: None,
             reply_to_message_id: None,
             reply_markup: None,
@@ -49,6 +58,28 @@ impl SendLocation {
         self
     }
 
+    /// The radius of uncertainty for the location, measured in meters; 0-1500
+    pub fn horizontal_accuracy(mut self, horizontal_accuracy: Float) -> Self {
+        self.horizontal_accuracy = Some(horizontal_accuracy);
+        self
+    }
+
+    /// Direction in which the user is moving, in degrees; 1-360
+    ///
+    /// For live locations only
+    pub fn heading(mut self, heading: Integer) -> Self {
+        self.heading = Some(heading);
+        self
+    }
+
+    /// Maximum distance in meters for proximity alerts about approaching another chat member; 1-100000
+    ///
+    /// For live locations only
+    pub fn proximity_alert_radius(mut self, proximity_alert_radius: Integer) -> Self {
+        self.proximity_alert_radius = Some(proximity_alert_radius);
+        self
+    }
+
     /// Sends the message silently
     ///
     /// Users will receive a notification with no sound
@@ -58,8 +89,8 @@ impl SendLocation {
     }
 
     /// If the message is a reply, ID of the original message
-    pub fn reply_to_message_id(mut self, reply_to_message_id: Integer) -> Self {
-        self.reply_to_message_id = Some(reply_to_message_id);
+    pub fn reply_to_message_id<M: Into<MessageId>>(mut self, reply_to_message_id: M) -> Self {
+        self.reply_to_message_id = Some(reply_to_message_id.into());
         self
     }
 
@@ -77,3 +108,51 @@ impl Method for SendLocation {
         RequestBuilder::json("sendLocation", &self)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::methods::DEFAULT_BASE_URL;
+    use serde_json::Value;
+
+    #[test]
+    fn test_send_location() {
+        let req = SendLocation::new(1, 2.0, 3.0)
+            .live_period(60)
+            .horizontal_accuracy(1.5)
+            .heading(90)
+            .proximity_alert_radius(100)
+            .get_request()
+            .unwrap()
+            .build(DEFAULT_BASE_URL, "token");
+        assert_eq!(req.url, "https://api.telegram.org/bottoken/sendLocation");
+        match req.body {
+            RequestBody::Json(data) => {
+                let data: Value = serde_json::from_slice(&data).unwrap();
+                assert_eq!(data["chat_id"], 1);
+                assert_eq!(data["latitude"], 2.0);
+                assert_eq!(data["longitude"], 3.0);
+                assert_eq!(data["live_period"], 60);
+                assert_eq!(data["horizontal_accuracy"], 1.5);
+                assert_eq!(data["heading"], 90);
+                assert_eq!(data["proximity_alert_radius"], 100);
+            }
+            data => panic!("Unexpected request data: {:?}", data),
+        }
+
+        let req = SendLocation::new(1, 2.0, 3.0)
+            .get_request()
+            .unwrap()
+            .build(DEFAULT_BASE_URL, "token");
+        match req.body {
+            RequestBody::Json(data) => {
+                let data: Value = serde_json::from_slice(&data).unwrap();
+                assert_eq!(
+                    data,
+                    serde_json::json!({"chat_id": 1, "latitude": 2.0, "longitude": 3.0})
+                );
+            }
+            data => panic!("Unexpected request data: {:?}", data),
+        }
+    }
+}
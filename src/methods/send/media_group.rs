@@ -1,11 +1,11 @@
 use crate::{
     methods::method::*,
-    types::{ChatId, Integer, MediaGroupItem, Message},
+    types::{ChatId, InputFile, MediaGroupItem, Message, MessageId},
 };
 use failure::Error;
 use serde::Serialize;
 
-/// Send a group of photos or videos as an album
+/// Send a group of photos, videos, documents or audios as an album
 #[derive(Clone, Debug, Serialize)]
 pub struct SendMediaGroup {
     chat_id: ChatId,
@@ -13,14 +13,14 @@ pub struct SendMediaGroup {
     #[serde(skip_serializing_if = "Option::is_none")]
     disable_notification: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    reply_to_message_id: Option<Integer>,
+    reply_to_message_id: Option<MessageId>,
 }
 
 impl SendMediaGroup {
     /// Creates a new SendMediaGroup with empty optional parameters
     ///
     /// * chat_id - Unique identifier for the target chat
-    /// * media - Photos and videos to be sent, must include 2–10 items
+    /// * media - Photos, videos, documents or audios to be sent, must include 2–10 items
     pub fn new<C: Into<ChatId>>(chat_id: C, media: Vec<MediaGroupItem>) -> Self {
         SendMediaGroup {
             chat_id: chat_id.into(),
@@ -39,8 +39,8 @@ impl SendMediaGroup {
     }
 
     /// If the messages are a reply, ID of the original message
-    pub fn reply_to_message_id(mut self, reply_to_message_id: Integer) -> Self {
-        self.reply_to_message_id = Some(reply_to_message_id);
+    pub fn reply_to_message_id<M: Into<MessageId>>(mut self, reply_to_message_id: M) -> Self {
+        self.reply_to_message_id = Some(reply_to_message_id.into());
         self
     }
 }
@@ -49,6 +49,88 @@ impl Method for SendMediaGroup {
     type Response = Vec<Message>;
 
     fn get_request(&self) -> Result<RequestBuilder, Error> {
-        RequestBuilder::json("sendMediaGroup", &self)
+        let files: Vec<(String, &InputFile)> = self
+            .media
+            .iter()
+            .enumerate()
+            .flat_map(|(idx, item)| {
+                item.files()
+                    .into_iter()
+                    .map(move |(field, file)| (format!("/media/{}/{}", idx, field), file))
+            })
+            .collect();
+        RequestBuilder::with_attachments("sendMediaGroup", &self, &files)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        methods::{FormValue, RequestBody, DEFAULT_BASE_URL},
+        types::{InputMediaPhoto, InputMediaVideo},
+    };
+    use serde_json::Value;
+    use std::env;
+
+    #[test]
+    fn test_send_media_group() {
+        let req = SendMediaGroup::new(
+            1,
+            vec![
+                InputMediaPhoto::new("photo-id").caption("photo").into(),
+                InputMediaVideo::new("https://example.com/video.mp4").into(),
+            ],
+        )
+        .get_request()
+        .unwrap()
+        .build(DEFAULT_BASE_URL, "token");
+        assert_eq!(req.url, "https://api.telegram.org/bottoken/sendMediaGroup");
+        if let RequestBody::Json(data) = req.body {
+            let data: Value = serde_json::from_slice(&data).unwrap();
+            assert_eq!(data["media"][0]["type"], "photo");
+            assert_eq!(data["media"][0]["media"], "photo-id");
+            assert_eq!(data["media"][0]["caption"], "photo");
+            assert_eq!(data["media"][1]["type"], "video");
+            assert_eq!(data["media"][1]["media"], "https://example.com/video.mp4");
+        } else {
+            panic!("Unexpected request body: {:?}", req.body);
+        }
+
+        let path = env::current_dir().unwrap().join("Cargo.toml");
+        let req = SendMediaGroup::new(
+            1,
+            vec![
+                InputMediaPhoto::new("photo-id").into(),
+                InputMediaVideo::new(InputFile::path(&path).unwrap())
+                    .thumb(InputFile::path(&path).unwrap())
+                    .into(),
+            ],
+        )
+        .get_request()
+        .unwrap()
+        .build(DEFAULT_BASE_URL, "token");
+        if let RequestBody::Multipart(form) = req.body {
+            let names: Vec<&str> = form.fields.iter().map(|(name, _)| name.as_str()).collect();
+            assert_eq!(
+                names,
+                vec!["chat_id", "media", "tgbot_attachment_0", "tgbot_attachment_1"]
+            );
+            match form.fields[1].1 {
+                FormValue::Text(ref media) => {
+                    let media: Value = serde_json::from_str(media).unwrap();
+                    assert_eq!(media[0]["media"], "photo-id");
+                    assert_eq!(media[1]["media"], "attach://tgbot_attachment_0");
+                    assert_eq!(media[1]["thumb"], "attach://tgbot_attachment_1");
+                }
+                ref value => panic!("Unexpected value: {:?}", value),
+            }
+            match form.fields[2].1 {
+                FormValue::File(ref file) => assert!(file.is_upload()),
+                ref value => panic!("Unexpected value: {:?}", value),
+            }
+        } else {
+            panic!("Unexpected request body: {:?}", req.body);
+        }
     }
 }
@@ -1,6 +1,6 @@
 use crate::{
     methods::method::*,
-    types::{ChatId, Integer, Message, ParseMode, ReplyMarkup},
+    types::{ChatId, InputFile, Message, MessageId, ParseMode, ReplyMarkup},
 };
 use failure::Error;
 use serde::Serialize;
@@ -9,7 +9,7 @@ use serde::Serialize;
 #[derive(Clone, Debug, Serialize)]
 pub struct SendPhoto {
     chat_id: ChatId,
-    photo: String,
+    photo: InputFile,
     #[serde(skip_serializing_if = "Option::is_none")]
     caption: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -17,7 +17,7 @@ pub struct SendPhoto {
     #[serde(skip_serializing_if = "Option::is_none")]
     disable_notification: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    reply_to_message_id: Option<Integer>,
+    reply_to_message_id: Option<MessageId>,
     #[serde(skip_serializing_if = "Option::is_none")]
     reply_markup: Option<ReplyMarkup>,
 }
@@ -31,8 +31,8 @@ impl SendPhoto {
     /// * photo - Photo to send
     ///           Pass a file_id as String to send a photo that exists on the Telegram servers (recommended),
     ///           pass an HTTP URL as a String for Telegram to get a photo from the Internet,
-    ///           or upload a new photo using multipart/form-data
-    pub fn new<C: Into<ChatId>, S: Into<String>>(chat_id: C, photo: S) -> Self {
+    ///           or pass InputFile::path() to upload a new photo
+    pub fn new<C: Into<ChatId>, F: Into<InputFile>>(chat_id: C, photo: F) -> Self {
         SendPhoto {
             chat_id: chat_id.into(),
             photo: photo.into(),
@@ -68,8 +68,8 @@ impl SendPhoto {
     }
 
     /// If the message is a reply, ID of the original message
-    pub fn reply_to_message_id(mut self, reply_to_message_id: Integer) -> Self {
-        self.reply_to_message_id = Some(reply_to_message_id);
+    pub fn reply_to_message_id<M: Into<MessageId>>(mut self, reply_to_message_id: M) -> Self {
+        self.reply_to_message_id = Some(reply_to_message_id.into());
         self
     }
 
@@ -84,6 +84,7 @@ impl Method for SendPhoto {
     type Response = Message;
 
     fn get_request(&self) -> Result<RequestBuilder, Error> {
-        RequestBuilder::json("sendPhoto", &self)
+        check_caption(&self.caption, self.parse_mode)?;
+        RequestBuilder::with_files("sendPhoto", &self, &[("photo", Some(&self.photo))])
     }
 }
@@ -0,0 +1,278 @@
+use crate::{
+    methods::method::*,
+    types::{ChatId, InlineKeyboardMarkup, Integer, Message, MessageId, ParseMode, Poll, PollKind, ReplyMarkup},
+};
+use failure::Error;
+use serde::Serialize;
+
+/// Maximum length of a quiz explanation
+pub const MAX_POLL_EXPLANATION_LENGTH: usize = 200;
+
+/// Send a native poll
+#[derive(Clone, Debug, Serialize)]
+pub struct SendPoll {
+    chat_id: ChatId,
+    question: String,
+    options: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    is_anonymous: Option<bool>,
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    kind: Option<PollKind>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    allows_multiple_answers: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    correct_option_id: Option<Integer>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    explanation: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    explanation_parse_mode: Option<ParseMode>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    is_closed: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    disable_notification: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reply_to_message_id: Option<MessageId>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reply_markup: Option<ReplyMarkup>,
+}
+
+impl SendPoll {
+    /// Creates a new SendPoll with empty optional parameters
+    ///
+    /// # Arguments
+    ///
+    /// * chat_id - Unique identifier for the target chat
+    /// * question - Poll question, 1-300 characters
+    /// * options - List of answer options, 2-10 strings 1-100 characters each
+    pub fn new<C, Q, O>(chat_id: C, question: Q, options: O) -> Self
+    where
+        C: Into<ChatId>,
+        Q: Into<String>,
+        O: IntoIterator,
+        O::Item: Into<String>,
+    {
+        SendPoll {
+            chat_id: chat_id.into(),
+            question: question.into(),
+            options: options.into_iter().map(Into::into).collect(),
+            is_anonymous: None,
+            kind: None,
+            allows_multiple_answers: None,
+            correct_option_id: None,
+            explanation: None,
+            explanation_parse_mode: None,
+            is_closed: None,
+            disable_notification: None,
+            reply_to_message_id: None,
+            reply_markup: None,
+        }
+    }
+
+    /// Creates a new quiz with a single correct answer
+    ///
+    /// # Arguments
+    ///
+    /// * chat_id - Unique identifier for the target chat
+    /// * question - Poll question, 1-300 characters
+    /// * options - List of answer options, 2-10 strings 1-100 characters each
+    /// * correct_option_id - 0-based identifier of the correct answer option,
+    ///   the request fails when it is out of options range
+    pub fn quiz<C, Q, O>(chat_id: C, question: Q, options: O, correct_option_id: Integer) -> Self
+    where
+        C: Into<ChatId>,
+        Q: Into<String>,
+        O: IntoIterator,
+        O::Item: Into<String>,
+    {
+        let mut method = SendPoll::new(chat_id, question, options);
+        method.kind = Some(PollKind::Quiz);
+        method.correct_option_id = Some(correct_option_id);
+        method
+    }
+
+    /// True, if the poll needs to be anonymous, defaults to True
+    pub fn is_anonymous(mut self, is_anonymous: bool) -> Self {
+        self.is_anonymous = Some(is_anonymous);
+        self
+    }
+
+    /// True, if the poll allows multiple answers, ignored for polls in quiz mode
+    ///
+    /// Defaults to False
+    pub fn allows_multiple_answers(mut self, allows_multiple_answers: bool) -> Self {
+        self.allows_multiple_answers = Some(allows_multiple_answers);
+        self
+    }
+
+    /// Text that is shown when a user chooses an incorrect answer or taps on the lamp icon in a quiz
+    ///
+    /// 0-200 characters with at most 2 line feeds
+    pub fn explanation<S: Into<String>>(mut self, explanation: S) -> Self {
+        self.explanation = Some(explanation.into());
+        self
+    }
+
+    /// Mode for parsing entities in the explanation
+    pub fn explanation_parse_mode(mut self, parse_mode: ParseMode) -> Self {
+        self.explanation_parse_mode = Some(parse_mode);
+        self
+    }
+
+    /// Pass True, if the poll needs to be immediately closed
+    ///
+    /// This can be useful for poll preview
+    pub fn is_closed(mut self, is_closed: bool) -> Self {
+        self.is_closed = Some(is_closed);
+        self
+    }
+
+    /// Sends the message silently
+    ///
+    /// Users will receive a notification with no sound
+    pub fn disable_notification(mut self, disable_notification: bool) -> Self {
+        self.disable_notification = Some(disable_notification);
+        self
+    }
+
+    /// If the message is a reply, ID of the original message
+    pub fn reply_to_message_id<M: Into<MessageId>>(mut self, reply_to_message_id: M) -> Self {
+        self.reply_to_message_id = Some(reply_to_message_id.into());
+        self
+    }
+
+    /// Additional interface options
+    pub fn reply_markup<R: Into<ReplyMarkup>>(mut self, reply_markup: R) -> Self {
+        self.reply_markup = Some(reply_markup.into());
+        self
+    }
+}
+
+impl Method for SendPoll {
+    type Response = Message;
+
+    fn get_request(&self) -> Result<RequestBuilder, Error> {
+        if let Some(correct_option_id) = self.correct_option_id {
+            check_range(
+                "correct_option_id",
+                correct_option_id,
+                0,
+                self.options.len() as Integer - 1,
+            )?;
+        }
+        // markup is not counted by Telegram, so formatted explanations are checked by the server
+        if let (Some(ref explanation), None) = (&self.explanation, self.explanation_parse_mode) {
+            check_length("explanation", explanation, MAX_POLL_EXPLANATION_LENGTH)?;
+        }
+        RequestBuilder::json("sendPoll", &self)
+    }
+}
+
+/// Stop a poll which was sent by the bot
+///
+/// On success, the stopped Poll with the final results is returned
+#[derive(Clone, Debug, Serialize)]
+pub struct StopPoll {
+    chat_id: ChatId,
+    message_id: MessageId,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reply_markup: Option<InlineKeyboardMarkup>,
+}
+
+impl StopPoll {
+    /// Creates a new StopPoll
+    ///
+    /// # Arguments
+    ///
+    /// * chat_id - Unique identifier for the target chat
+    /// * message_id - Identifier of the original message with the poll
+    pub fn new<C: Into<ChatId>, M: Into<MessageId>>(chat_id: C, message_id: M) -> Self {
+        StopPoll {
+            chat_id: chat_id.into(),
+            message_id: message_id.into(),
+            reply_markup: None,
+        }
+    }
+
+    /// A new message inline keyboard
+    pub fn reply_markup<I: Into<InlineKeyboardMarkup>>(mut self, reply_markup: I) -> Self {
+        self.reply_markup = Some(reply_markup.into());
+        self
+    }
+}
+
+impl Method for StopPoll {
+    type Response = Poll;
+
+    fn get_request(&self) -> Result<RequestBuilder, Error> {
+        RequestBuilder::json("stopPoll", &self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::methods::{RequestBody, DEFAULT_BASE_URL};
+    use serde_json::Value;
+
+    #[test]
+    fn test_send_poll() {
+        let req = SendPoll::quiz(1, "Rust?", vec!["Yes", "No"], 0)
+            .is_anonymous(false)
+            .explanation("*Yes*")
+            .explanation_parse_mode(ParseMode::Markdown)
+            .is_closed(true)
+            .get_request()
+            .unwrap()
+            .build(DEFAULT_BASE_URL, "token");
+        assert_eq!(req.url, "https://api.telegram.org/bottoken/sendPoll");
+        if let RequestBody::Json(data) = req.body {
+            let data: Value = serde_json::from_slice(&data).unwrap();
+            assert_eq!(data["chat_id"], 1);
+            assert_eq!(data["question"], "Rust?");
+            assert_eq!(data["options"], serde_json::json!(["Yes", "No"]));
+            assert_eq!(data["is_anonymous"], false);
+            assert_eq!(data["type"], "quiz");
+            assert_eq!(data["correct_option_id"], 0);
+            assert_eq!(data["explanation"], "*Yes*");
+            assert_eq!(data["explanation_parse_mode"], "Markdown");
+            assert_eq!(data["is_closed"], true);
+            assert!(data.get("allows_multiple_answers").is_none());
+        } else {
+            panic!("Unexpected request body: {:?}", req.body);
+        }
+    }
+
+    #[test]
+    fn test_send_poll_validation() {
+        assert!(SendPoll::quiz(1, "Rust?", vec!["Yes", "No"], 1).get_request().is_ok());
+        assert!(SendPoll::quiz(1, "Rust?", vec!["Yes", "No"], 2).get_request().is_err());
+        assert!(SendPoll::quiz(1, "Rust?", vec!["Yes", "No"], -1).get_request().is_err());
+        assert!(SendPoll::quiz(1, "Rust?", vec!["Yes", "No"], 0)
+            .explanation("a".repeat(MAX_POLL_EXPLANATION_LENGTH + 1))
+            .get_request()
+            .is_err());
+        assert!(SendPoll::quiz(1, "Rust?", vec!["Yes", "No"], 0)
+            .explanation(format!("<b>{}</b>", "a".repeat(MAX_POLL_EXPLANATION_LENGTH)))
+            .explanation_parse_mode(ParseMode::Html)
+            .get_request()
+            .is_ok());
+    }
+
+    #[test]
+    fn test_stop_poll() {
+        let req = StopPoll::new(1, 2)
+            .reply_markup(vec![vec![crate::types::InlineKeyboardButton::with_url("text", "url")]])
+            .get_request()
+            .unwrap()
+            .build(DEFAULT_BASE_URL, "token");
+        assert_eq!(req.url, "https://api.telegram.org/bottoken/stopPoll");
+        if let RequestBody::Json(data) = req.body {
+            let data: Value = serde_json::from_slice(&data).unwrap();
+            assert_eq!(data["chat_id"], 1);
+            assert_eq!(data["message_id"], 2);
+            assert_eq!(data["reply_markup"]["inline_keyboard"][0][0]["text"], "text");
+        } else {
+            panic!("Unexpected request body: {:?}", req.body);
+        }
+    }
+}
@@ -1,6 +1,6 @@
 use crate::{
     methods::method::*,
-    types::{ChatId, Integer, Message, ParseMode, ReplyMarkup},
+    types::{ChatId, InputFile, Integer, Message, MessageId, ParseMode, ReplyMarkup},
 };
 use failure::Error;
 use serde::Serialize;
@@ -12,7 +12,7 @@ use serde::Serialize;
 #[derive(Clone, Debug, Serialize)]
 pub struct SendVideo {
     chat_id: ChatId,
-    video: String,
+    video: InputFile,
     #[serde(skip_serializing_if = "Option::is_none")]
     duration: Option<Integer>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -20,7 +20,7 @@ pub struct SendVideo {
     #[serde(skip_serializing_if = "Option::is_none")]
     height: Option<Integer>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    thumb: Option<String>,
+    thumb: Option<InputFile>,
     #[serde(skip_serializing_if = "Option::is_none")]
     caption: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -30,7 +30,7 @@ pub struct SendVideo {
     #[serde(skip_serializing_if = "Option::is_none")]
     disable_notification: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    reply_to_message_id: Option<Integer>,
+    reply_to_message_id: Option<MessageId>,
     #[serde(skip_serializing_if = "Option::is_none")]
     reply_markup: Option<ReplyMarkup>,
 }
@@ -44,8 +44,8 @@ impl SendVideo {
     /// * video - Video to send
     ///           Pass a file_id as String to send a video that exists on the Telegram servers (recommended),
     ///           pass an HTTP URL as a String for Telegram to get a video from the Internet,
-    ///           or upload a new video using multipart/form-data
-    pub fn new<C: Into<ChatId>, S: Into<String>>(chat_id: C, video: S) -> Self {
+    ///           or pass InputFile::path() to upload a new video
+    pub fn new<C: Into<ChatId>, F: Into<InputFile>>(chat_id: C, video: F) -> Self {
         SendVideo {
             chat_id: chat_id.into(),
             video: video.into(),
@@ -86,9 +86,8 @@ impl SendVideo {
     /// A thumbnail‘s width and height should not exceed 90
     /// Ignored if the file is not uploaded using multipart/form-data
     /// Thumbnails can’t be reused and can be only uploaded as a new file,
-    /// so you can pass “attach://<file_attach_name>” if the thumbnail was
-    /// uploaded using multipart/form-data under <file_attach_name>
-    pub fn thumb<S: Into<String>>(mut self, thumb: S) -> Self {
+    /// so pass a file created using InputFile::path()
+    pub fn thumb<F: Into<InputFile>>(mut self, thumb: F) -> Self {
         self.thumb = Some(thumb.into());
         self
     }
@@ -122,8 +121,8 @@ impl SendVideo {
     }
 
     /// If the message is a reply, ID of the original message
-    pub fn reply_to_message_id(mut self, reply_to_message_id: Integer) -> Self {
-        self.reply_to_message_id = Some(reply_to_message_id);
+    pub fn reply_to_message_id<M: Into<MessageId>>(mut self, reply_to_message_id: M) -> Self {
+        self.reply_to_message_id = Some(reply_to_message_id.into());
         self
     }
 
@@ -138,6 +137,11 @@ impl Method for SendVideo {
     type Response = Message;
 
     fn get_request(&self) -> Result<RequestBuilder, Error> {
-        RequestBuilder::json("sendVideo", &self)
+        check_caption(&self.caption, self.parse_mode)?;
+        RequestBuilder::with_files(
+            "sendVideo",
+            &self,
+            &[("video", Some(&self.video)), ("thumb", self.thumb.as_ref())],
+        )
     }
 }
@@ -1,6 +1,6 @@
 use crate::{
     methods::method::*,
-    types::{ChatId, Integer, Message, ReplyMarkup},
+    types::{ChatId, InputFile, Integer, Message, MessageId, ReplyMarkup},
 };
 use failure::Error;
 use serde::Serialize;
@@ -11,17 +11,17 @@ use serde::Serialize;
 #[derive(Clone, Debug, Serialize)]
 pub struct SendVideoNote {
     chat_id: ChatId,
-    video_note: String,
+    video_note: InputFile,
     #[serde(skip_serializing_if = "Option::is_none")]
     duration: Option<Integer>,
     #[serde(skip_serializing_if = "Option::is_none")]
     length: Option<Integer>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    thumb: Option<String>,
+    thumb: Option<InputFile>,
     #[serde(skip_serializing_if = "Option::is_none")]
     disable_notification: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    reply_to_message_id: Option<Integer>,
+    reply_to_message_id: Option<MessageId>,
     #[serde(skip_serializing_if = "Option::is_none")]
     reply_markup: Option<ReplyMarkup>,
 }
@@ -34,9 +34,9 @@ impl SendVideoNote {
     /// * chat_id - Unique identifier for the target chat
     /// * video_note - Video note to send
     ///                Pass a file_id as String to send a video note that exists on the Telegram servers (recommended)
-    ///                or upload a new video using multipart/form-data
+    ///                or pass InputFile::path() to upload a new video
     ///                Sending video notes by a URL is currently unsupported
-    pub fn new<C: Into<ChatId>, S: Into<String>>(chat_id: C, video_note: S) -> Self {
+    pub fn new<C: Into<ChatId>, F: Into<InputFile>>(chat_id: C, video_note: F) -> Self {
         SendVideoNote {
             chat_id: chat_id.into(),
             video_note: video_note.into(),
@@ -67,9 +67,8 @@ impl SendVideoNote {
     /// A thumbnail‘s width and height should not exceed 90
     /// Ignored if the file is not uploaded using multipart/form-data
     /// Thumbnails can’t be reused and can be only uploaded as a new file,
-    /// so you can pass “attach://<file_attach_name>” if the thumbnail was
-    /// uploaded using multipart/form-data under <file_attach_name>
-    pub fn thumb<S: Into<String>>(mut self, thumb: S) -> Self {
+    /// so pass a file created using InputFile::path()
+    pub fn thumb<F: Into<InputFile>>(mut self, thumb: F) -> Self {
         self.thumb = Some(thumb.into());
         self
     }
@@ -82,8 +81,8 @@ impl SendVideoNote {
     }
 
     /// If the message is a reply, ID of the original message
-    pub fn reply_to_message_id(mut self, reply_to_message_id: Integer) -> Self {
-        self.reply_to_message_id = Some(reply_to_message_id);
+    pub fn reply_to_message_id<M: Into<MessageId>>(mut self, reply_to_message_id: M) -> Self {
+        self.reply_to_message_id = Some(reply_to_message_id.into());
         self
     }
 
@@ -98,6 +97,10 @@ impl Method for SendVideoNote {
     type Response = Message;
 
     fn get_request(&self) -> Result<RequestBuilder, Error> {
-        RequestBuilder::json("sendVideoNote", &self)
+        RequestBuilder::with_files(
+            "sendVideoNote",
+            &self,
+            &[("video_note", Some(&self.video_note)), ("thumb", self.thumb.as_ref())],
+        )
     }
 }
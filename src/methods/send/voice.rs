@@ -1,6 +1,6 @@
 use crate::{
     methods::method::*,
-    types::{ChatId, Integer, Message, ParseMode, ReplyMarkup},
+    types::{ChatId, InputFile, Integer, Message, MessageId, ParseMode, ReplyMarkup},
 };
 use failure::Error;
 use serde::Serialize;
@@ -14,7 +14,7 @@ use serde::Serialize;
 #[derive(Clone, Debug, Serialize)]
 pub struct SendVoice {
     chat_id: ChatId,
-    voice: String,
+    voice: InputFile,
     #[serde(skip_serializing_if = "Option::is_none")]
     caption: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -24,7 +24,7 @@ pub struct SendVoice {
     #[serde(skip_serializing_if = "Option::is_none")]
     disable_notification: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    reply_to_message_id: Option<Integer>,
+    reply_to_message_id: Option<MessageId>,
     #[serde(skip_serializing_if = "Option::is_none")]
     reply_markup: Option<ReplyMarkup>,
 }
@@ -38,8 +38,8 @@ impl SendVoice {
     /// * voice - Audio file to send
     ///           Pass a file_id as String to send a file that exists on the Telegram servers (recommended),
     ///           pass an HTTP URL as a String for Telegram to get a file from the Internet,
-    ///           or upload a new one using multipart/form-data
-    pub fn new<C: Into<ChatId>, S: Into<String>>(chat_id: C, voice: S) -> Self {
+    ///           or pass InputFile::path() to upload a new one
+    pub fn new<C: Into<ChatId>, F: Into<InputFile>>(chat_id: C, voice: F) -> Self {
         SendVoice {
             chat_id: chat_id.into(),
             voice: voice.into(),
@@ -78,8 +78,8 @@ impl SendVoice {
     }
 
     /// If the message is a reply, ID of the original message
-    pub fn reply_to_message_id(mut self, reply_to_message_id: Integer) -> Self {
-        self.reply_to_message_id = Some(reply_to_message_id);
+    pub fn reply_to_message_id<M: Into<MessageId>>(mut self, reply_to_message_id: M) -> Self {
+        self.reply_to_message_id = Some(reply_to_message_id.into());
         self
     }
 
@@ -94,6 +94,7 @@ impl Method for SendVoice {
     type Response = Message;
 
     fn get_request(&self) -> Result<RequestBuilder, Error> {
-        RequestBuilder::json("sendVoice", &self)
+        check_caption(&self.caption, self.parse_mode)?;
+        RequestBuilder::with_files("sendVoice", &self, &[("voice", Some(&self.voice))])
     }
 }
@@ -1,6 +1,6 @@
 use crate::{
-    methods::method::*,
-    types::{Integer, MaskPosition},
+    methods::{method::*, sticker::StickerFile},
+    types::{InputFile, MaskPosition, StickerFormat, UserId},
 };
 use failure::Error;
 use serde::Serialize;
@@ -8,9 +8,10 @@ use serde::Serialize;
 /// Add a new sticker to a set created by the bot
 #[derive(Clone, Debug, Serialize)]
 pub struct AddStickerToSet {
-    user_id: Integer,
+    user_id: UserId,
     name: String,
-    png_sticker: String,
+    #[serde(flatten)]
+    sticker: StickerFile,
     emojis: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     mask_position: Option<MaskPosition>,
@@ -23,18 +24,24 @@ impl AddStickerToSet {
     ///
     /// * user_id - User identifier of sticker set owner
     /// * name - Sticker set name
-    /// * png_sticker - Png image with the sticker, must be up to 512 kilobytes in size,
-    ///                 dimensions must not exceed 512px,
-    ///                 and either width or height must be exactly 512px
-    ///                 Pass a file_id as a String to send a file that already exists on the Telegram servers,
-    ///                 pass an HTTP URL as a String for Telegram to get a file from the Internet,
-    ///                 or upload a new one using multipart/form-data
+    /// * sticker - Sticker file, sent as png_sticker, tgs_sticker or webm_sticker depending on the format
+    ///             Png image must be up to 512 kilobytes in size, dimensions must not exceed 512px,
+    ///             and either width or height must be exactly 512px
+    ///             Pass a file_id as a String to send a file that already exists on the Telegram servers,
+    ///             pass an HTTP URL as a String for Telegram to get a file from the Internet,
+    ///             or pass InputFile::path() to upload a new file
+    /// * sticker_format - Format of the sticker
     /// * emojis - One or more emoji corresponding to the sticker
-    pub fn new<S: Into<String>>(user_id: Integer, name: S, png_sticker: S, emojis: S) -> Self {
+    pub fn new<S, F, U>(user_id: U, name: S, sticker: F, sticker_format: StickerFormat, emojis: S) -> Self
+    where
+        U: Into<UserId>,
+        S: Into<String>,
+        F: Into<InputFile>,
+    {
         AddStickerToSet {
-            user_id,
+            user_id: user_id.into(),
             name: name.into(),
-            png_sticker: png_sticker.into(),
+            sticker: StickerFile::new(sticker.into(), sticker_format),
             emojis: emojis.into(),
             mask_position: None,
         }
@@ -51,6 +58,6 @@ impl Method for AddStickerToSet {
     type Response = bool;
 
     fn get_request(&self) -> Result<RequestBuilder, Error> {
-        RequestBuilder::json("addStickerToSet", &self)
+        RequestBuilder::with_files("addStickerToSet", &self, &self.sticker.files())
     }
 }
@@ -1,3 +1,6 @@
+use crate::types::{InputFile, StickerFormat};
+use serde::Serialize;
+
 mod add_to_set;
 mod delete_from_set;
 mod get_set;
@@ -9,3 +12,40 @@ mod upload_file;
 pub use self::{
     add_to_set::*, delete_from_set::*, get_set::*, new_set::*, send::*, set_position_in_set::*, upload_file::*,
 };
+
+/// A sticker file sent in a field corresponding to its format
+#[derive(Clone, Debug, Serialize)]
+struct StickerFile {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    png_sticker: Option<InputFile>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tgs_sticker: Option<InputFile>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    webm_sticker: Option<InputFile>,
+    sticker_format: StickerFormat,
+}
+
+impl StickerFile {
+    fn new(file: InputFile, sticker_format: StickerFormat) -> Self {
+        let mut sticker = StickerFile {
+            png_sticker: None,
+            tgs_sticker: None,
+            webm_sticker: None,
+            sticker_format,
+        };
+        match sticker_format {
+            StickerFormat::Static => sticker.png_sticker = Some(file),
+            StickerFormat::Animated => sticker.tgs_sticker = Some(file),
+            StickerFormat::Video => sticker.webm_sticker = Some(file),
+        }
+        sticker
+    }
+
+    fn files(&self) -> [(&'static str, Option<&InputFile>); 3] {
+        [
+            ("png_sticker", self.png_sticker.as_ref()),
+            ("tgs_sticker", self.tgs_sticker.as_ref()),
+            ("webm_sticker", self.webm_sticker.as_ref()),
+        ]
+    }
+}
@@ -1,6 +1,6 @@
 use crate::{
-    methods::method::*,
-    types::{Integer, MaskPosition},
+    methods::{method::*, sticker::StickerFile},
+    types::{InputFile, MaskPosition, StickerFormat, UserId},
 };
 use failure::Error;
 use serde::Serialize;
@@ -10,10 +10,11 @@ use serde::Serialize;
 /// The bot will be able to edit the created sticker set
 #[derive(Clone, Debug, Serialize)]
 pub struct CreateNewStickerSet {
-    user_id: Integer,
+    user_id: UserId,
     name: String,
     title: String,
-    png_sticker: String,
+    #[serde(flatten)]
+    sticker: StickerFile,
     emojis: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     contains_masks: Option<bool>,
@@ -34,19 +35,25 @@ impl CreateNewStickerSet {
     ///          <bot_username> is case insensitive
     ///          1-64 characters
     /// * title - Sticker set title, 1-64 characters
-    /// * png_sticker - Png image with the sticker,
-    ///                 must be up to 512 kilobytes in size, dimensions must not exceed 512px,
-    ///                 and either width or height must be exactly 512px
-    ///                 Pass a file_id as a String to send a file that already exists on the Telegram servers,
-    ///                 pass an HTTP URL as a String for Telegram to get a file from the Internet,
-    ///                 or upload a new one using multipart/form-data
+    /// * sticker - Sticker file, sent as png_sticker, tgs_sticker or webm_sticker depending on the format
+    ///             Png image must be up to 512 kilobytes in size, dimensions must not exceed 512px,
+    ///             and either width or height must be exactly 512px
+    ///             Pass a file_id as a String to send a file that already exists on the Telegram servers,
+    ///             pass an HTTP URL as a String for Telegram to get a file from the Internet,
+    ///             or pass InputFile::path() to upload a new file
+    /// * sticker_format - Format of the sticker
     /// * emojis - One or more emoji corresponding to the sticker
-    pub fn new<S: Into<String>>(user_id: Integer, name: S, title: S, png_sticker: S, emojis: S) -> Self {
+    pub fn new<S, F, U>(user_id: U, name: S, title: S, sticker: F, sticker_format: StickerFormat, emojis: S) -> Self
+    where
+        U: Into<UserId>,
+        S: Into<String>,
+        F: Into<InputFile>,
+    {
         CreateNewStickerSet {
-            user_id,
+            user_id: user_id.into(),
             name: name.into(),
             title: title.into(),
-            png_sticker: png_sticker.into(),
+            sticker: StickerFile::new(sticker.into(), sticker_format),
             emojis: emojis.into(),
             contains_masks: None,
             mask_position: None,
@@ -70,6 +77,76 @@ impl Method for CreateNewStickerSet {
     type Response = bool;
 
     fn get_request(&self) -> Result<RequestBuilder, Error> {
-        RequestBuilder::json("createNewStickerSet", &self)
+        RequestBuilder::with_files("createNewStickerSet", &self, &self.sticker.files())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        methods::{FormValue, RequestBody, DEFAULT_BASE_URL},
+        types::MaskPositionPoint,
+    };
+    use serde_json::Value;
+    use std::env;
+
+    #[test]
+    fn test_create_new_sticker_set() {
+        let req = CreateNewStickerSet::new(1, "name", "title", "file-id", StickerFormat::Static, "^_^")
+            .get_request()
+            .unwrap()
+            .build(DEFAULT_BASE_URL, "token");
+        assert_eq!(req.url, "https://api.telegram.org/bottoken/createNewStickerSet");
+        if let RequestBody::Json(data) = req.body {
+            let data: Value = serde_json::from_slice(&data).unwrap();
+            assert_eq!(data["png_sticker"], "file-id");
+            assert_eq!(data["sticker_format"], "static");
+            assert_eq!(data["emojis"], "^_^");
+            assert!(data.get("tgs_sticker").is_none());
+        } else {
+            panic!("Unexpected request body: {:?}", req.body);
+        }
+
+        let path = env::current_dir().unwrap().join("Cargo.toml");
+        let req = CreateNewStickerSet::new(
+            1,
+            "name",
+            "title",
+            InputFile::path(&path).unwrap(),
+            StickerFormat::Animated,
+            "^_^",
+        )
+        .mask_position(MaskPosition {
+            point: MaskPositionPoint::Eyes,
+            x_shift: 0.0,
+            y_shift: 1.0,
+            scale: 2.0,
+        })
+        .get_request()
+        .unwrap()
+        .build(DEFAULT_BASE_URL, "token");
+        if let RequestBody::Multipart(form) = req.body {
+            let mut has_file = false;
+            for (name, value) in form.fields {
+                match (name.as_str(), value) {
+                    ("tgs_sticker", FormValue::File(file)) => {
+                        assert!(file.is_upload());
+                        has_file = true;
+                    }
+                    ("sticker_format", FormValue::Text(value)) => assert_eq!(value, "animated"),
+                    ("mask_position", FormValue::Text(value)) => {
+                        let value: Value = serde_json::from_str(&value).unwrap();
+                        assert_eq!(value["point"], "eyes");
+                    }
+                    ("user_id", FormValue::Text(value)) => assert_eq!(value, "1"),
+                    ("name", _) | ("title", _) | ("emojis", _) => {}
+                    (name, value) => panic!("Unexpected field {}: {:?}", name, value),
+                }
+            }
+            assert!(has_file);
+        } else {
+            panic!("Unexpected request body: {:?}", req.body);
+        }
     }
 }
@@ -1,6 +1,6 @@
 use crate::{
     methods::method::*,
-    types::{File, Integer},
+    types::{File, InputFile, UserId},
 };
 use failure::Error;
 use serde::Serialize;
@@ -8,8 +8,8 @@ use serde::Serialize;
 /// Upload a .png file with a sticker for later use in createNewStickerSet and addStickerToSet methods
 #[derive(Clone, Debug, Serialize)]
 pub struct UploadStickerFile {
-    user_id: Integer,
-    png_sticker: String,
+    user_id: UserId,
+    png_sticker: InputFile,
 }
 
 impl UploadStickerFile {
@@ -20,9 +20,10 @@ impl UploadStickerFile {
     /// * user_id - User identifier of sticker file owner
     /// * png_sticker - Png image with the sticker, must be up to 512 kilobytes in size,
     ///                 dimensions must not exceed 512px, and either width or height must be exactly 512px
-    pub fn new<S: Into<String>>(user_id: Integer, png_sticker: S) -> Self {
+    ///                 Pass InputFile::path() to upload a new file
+    pub fn new<F: Into<InputFile>, U: Into<UserId>>(user_id: U, png_sticker: F) -> Self {
         UploadStickerFile {
-            user_id,
+            user_id: user_id.into(),
             png_sticker: png_sticker.into(),
         }
     }
@@ -32,6 +33,6 @@ impl Method for UploadStickerFile {
     type Response = File;
 
     fn get_request(&self) -> Result<RequestBuilder, Error> {
-        RequestBuilder::json("uploadStickerFile", &self)
+        RequestBuilder::with_files("uploadStickerFile", &self, &[("png_sticker", Some(&self.png_sticker))])
     }
 }
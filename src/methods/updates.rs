@@ -1,10 +1,25 @@
 use crate::{
     methods::method::*,
-    types::{AllowedUpdate, Integer, Update, WebhookInfo},
+    types::{AllowedUpdate, InputFile, Integer, Update, WebhookInfo},
 };
 use failure::Error;
 use serde::Serialize;
-use std::collections::HashSet;
+use std::{
+    collections::HashSet,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+static CHAT_MEMBER_WARNING_SHOWN: AtomicBool = AtomicBool::new(false);
+
+/// Telegram silently drops chat_member updates when the bot is not an administrator,
+/// so we let the user know about it once
+fn check_allowed_updates(allowed_updates: &Option<HashSet<AllowedUpdate>>) {
+    if let Some(ref updates) = allowed_updates {
+        if updates.contains(&AllowedUpdate::ChatMember) && !CHAT_MEMBER_WARNING_SHOWN.swap(true, Ordering::Relaxed) {
+            log::warn!("chat_member updates will be received only in chats where the bot is an administrator");
+        }
+    }
+}
 
 /// Receive incoming updates using long polling
 ///
@@ -25,6 +40,7 @@ impl Method for GetUpdates {
     type Response = Vec<Update>;
 
     fn get_request(&self) -> Result<RequestBuilder, Error> {
+        check_allowed_updates(&self.allowed_updates);
         RequestBuilder::json("getUpdates", &self)
     }
 }
@@ -63,7 +79,8 @@ impl GetUpdates {
     /// List the types of updates you want your bot to receive
     ///
     /// For example, specify [“message”, “edited_channel_post”, “callback_query”] to only receive updates of these types
-    /// Specify an empty list to receive all updates regardless of type (default)
+    /// Specify an empty list to receive all updates except chat_member (default)
+    /// Use `AllowedUpdate::all_including_chat_member()` to receive chat_member updates too
     /// If not specified, the previous setting will be used
     /// Please note that this parameter doesn't affect updates created before the call to the getUpdates,
     /// so unwanted updates may be received for a short period of time
@@ -101,11 +118,15 @@ impl GetUpdates {
 pub struct SetWebhook {
     url: String,
     #[serde(skip_serializing_if = "Option::is_none")]
-    certificate: Option<String>,
+    certificate: Option<InputFile>,
     #[serde(skip_serializing_if = "Option::is_none")]
     max_connections: Option<Integer>,
     #[serde(skip_serializing_if = "Option::is_none")]
     allowed_updates: Option<HashSet<AllowedUpdate>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    drop_pending_updates: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    secret_token: Option<String>,
 }
 
 impl SetWebhook {
@@ -121,12 +142,16 @@ impl SetWebhook {
             certificate: None,
             max_connections: None,
             allowed_updates: None,
+            drop_pending_updates: None,
+            secret_token: None,
         }
     }
 
     /// Upload your public key certificate so that the root certificate in use can be checked
-    pub fn certificate(mut self, certificate: String) -> Self {
-        self.certificate = Some(certificate);
+    ///
+    /// Use InputFile::path() to upload a certificate file
+    pub fn certificate<F: Into<InputFile>>(mut self, certificate: F) -> Self {
+        self.certificate = Some(certificate.into());
         self
     }
 
@@ -144,7 +169,8 @@ impl SetWebhook {
     /// For example, specify [“message”, “edited_channel_post”, “callback_query”]
     /// to only receive updates of these types
     /// See Update for a complete list of available update types
-    /// Specify an empty list to receive all updates regardless of type (default)
+    /// Specify an empty list to receive all updates except chat_member (default)
+    /// Use `AllowedUpdate::all_including_chat_member()` to receive chat_member updates too
     /// If not specified, the previous setting will be used
     /// Please note that this parameter doesn't affect updates created before the call to the setWebhook,
     /// so unwanted updates may be received for a short period of time
@@ -167,27 +193,59 @@ impl SetWebhook {
         };
         self
     }
+
+    /// Drop all pending updates
+    pub fn drop_pending_updates(mut self, drop_pending_updates: bool) -> Self {
+        self.drop_pending_updates = Some(drop_pending_updates);
+        self
+    }
+
+    /// A secret token to be sent in a header “X-Telegram-Bot-Api-Secret-Token” in every webhook request
+    ///
+    /// 1-256 characters, only A-Z, a-z, 0-9, _ and - are allowed
+    /// The header is useful to ensure that the request comes from a webhook set by you
+    pub fn secret_token<S: Into<String>>(mut self, secret_token: S) -> Self {
+        self.secret_token = Some(secret_token.into());
+        self
+    }
 }
 
 impl Method for SetWebhook {
     type Response = bool;
 
     fn get_request(&self) -> Result<RequestBuilder, Error> {
-        RequestBuilder::json("setWebhook", &self)
+        check_allowed_updates(&self.allowed_updates);
+        RequestBuilder::with_files("setWebhook", &self, &[("certificate", self.certificate.as_ref())])
     }
 }
 
 /// Remove webhook integration if you decide to switch back to getUpdates
 ///
 /// Returns True on success
-#[derive(Clone, Copy, Debug)]
-pub struct DeleteWebhook;
+#[derive(Clone, Copy, Debug, Default, Serialize)]
+pub struct DeleteWebhook {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    drop_pending_updates: Option<bool>,
+}
+
+impl DeleteWebhook {
+    /// Creates a new DeleteWebhook
+    pub fn new() -> Self {
+        DeleteWebhook::default()
+    }
+
+    /// Drop all pending updates
+    pub fn drop_pending_updates(mut self, drop_pending_updates: bool) -> Self {
+        self.drop_pending_updates = Some(drop_pending_updates);
+        self
+    }
+}
 
 impl Method for DeleteWebhook {
     type Response = bool;
 
     fn get_request(&self) -> Result<RequestBuilder, Error> {
-        RequestBuilder::empty("deleteWebhook")
+        RequestBuilder::json("deleteWebhook", &self)
     }
 }
 
@@ -206,11 +264,15 @@ impl Method for GetWebhookInfo {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::methods::DEFAULT_BASE_URL;
     use serde_json::Value;
 
     #[test]
     fn test_serialize_get_updates() {
-        let req = GetUpdates::default().get_request().unwrap().build("token");
+        let req = GetUpdates::default()
+            .get_request()
+            .unwrap()
+            .build(DEFAULT_BASE_URL, "token");
         assert_eq!(req.method, RequestMethod::Post);
         assert_eq!(req.url, String::from("https://api.telegram.org/bottoken/getUpdates"));
         match req.body {
@@ -237,10 +299,10 @@ mod tests {
             .add_allowed_update(AllowedUpdate::ShippingQuery)
             .get_request()
             .unwrap()
-            .build("token");
+            .build(DEFAULT_BASE_URL, "token");
         match req.body {
             RequestBody::Json(data) => {
-                let data: Value = serde_json::from_slice(&data).unwrap();;
+                let data: Value = serde_json::from_slice(&data).unwrap();
                 assert_eq!(data["offset"], 0);
                 assert_eq!(data["limit"], 10);
                 let mut updates: Vec<&str> = data["allowed_updates"]
@@ -271,7 +333,10 @@ mod tests {
 
     #[test]
     fn test_serialize_set_webhook() {
-        let req = SetWebhook::new("url").get_request().unwrap().build("token");
+        let req = SetWebhook::new("url")
+            .get_request()
+            .unwrap()
+            .build(DEFAULT_BASE_URL, "token");
         assert_eq!(req.method, RequestMethod::Post);
         assert_eq!(req.url, String::from("https://api.telegram.org/bottoken/setWebhook"));
         match req.body {
@@ -280,22 +345,76 @@ mod tests {
             }
             data => panic!("Unexpected request data: {:?}", data),
         }
+
+        let req = SetWebhook::new("url")
+            .max_connections(10)
+            .drop_pending_updates(true)
+            .secret_token("secret")
+            .get_request()
+            .unwrap()
+            .build(DEFAULT_BASE_URL, "token");
+        match req.body {
+            RequestBody::Json(data) => {
+                let data: Value = serde_json::from_slice(&data).unwrap();
+                assert_eq!(data["url"], "url");
+                assert_eq!(data["max_connections"], 10);
+                assert_eq!(data["drop_pending_updates"], true);
+                assert_eq!(data["secret_token"], "secret");
+            }
+            data => panic!("Unexpected request data: {:?}", data),
+        }
+
+        let path = std::env::temp_dir().join("tgbot-set-webhook-test.pem");
+        std::fs::write(&path, b"certificate").unwrap();
+        let req = SetWebhook::new("url")
+            .certificate(InputFile::path(&path).unwrap())
+            .get_request()
+            .unwrap()
+            .build(DEFAULT_BASE_URL, "token");
+        std::fs::remove_file(&path).unwrap();
+        match req.body {
+            RequestBody::Multipart(form) => {
+                let names: Vec<&str> = form.fields.iter().map(|(name, _)| name.as_str()).collect();
+                assert_eq!(names, vec!["url", "certificate"]);
+            }
+            data => panic!("Unexpected request data: {:?}", data),
+        }
     }
 
     #[test]
     fn test_serialize_delete_webhook() {
-        let req = DeleteWebhook.get_request().unwrap().build("token");
-        assert_eq!(req.method, RequestMethod::Get);
+        let req = DeleteWebhook::new()
+            .get_request()
+            .unwrap()
+            .build(DEFAULT_BASE_URL, "token");
+        assert_eq!(req.method, RequestMethod::Post);
         assert_eq!(req.url, String::from("https://api.telegram.org/bottoken/deleteWebhook"));
         match req.body {
-            RequestBody::Empty => {}
+            RequestBody::Json(data) => {
+                assert_eq!(String::from_utf8(data).unwrap(), String::from(r#"{}"#));
+            }
+            data => panic!("Unexpected request data: {:?}", data),
+        }
+
+        let req = DeleteWebhook::new()
+            .drop_pending_updates(true)
+            .get_request()
+            .unwrap()
+            .build(DEFAULT_BASE_URL, "token");
+        match req.body {
+            RequestBody::Json(data) => {
+                assert_eq!(
+                    String::from_utf8(data).unwrap(),
+                    String::from(r#"{"drop_pending_updates":true}"#)
+                );
+            }
             data => panic!("Unexpected request data: {:?}", data),
         }
     }
 
     #[test]
     fn test_serialize_get_webhook_info() {
-        let req = GetWebhookInfo.get_request().unwrap().build("token");
+        let req = GetWebhookInfo.get_request().unwrap().build(DEFAULT_BASE_URL, "token");
         assert_eq!(req.method, RequestMethod::Get);
         assert_eq!(
             req.url,
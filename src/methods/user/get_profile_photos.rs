@@ -1,14 +1,17 @@
 use crate::{
     methods::method::*,
-    types::{Integer, UserProfilePhotos},
+    types::{Integer, UserId, UserProfilePhotos},
 };
 use failure::Error;
 use serde::Serialize;
 
+/// Maximum number of photos returned by GetUserProfilePhotos
+pub const MAX_USER_PROFILE_PHOTOS_LIMIT: Integer = 100;
+
 /// Get a list of profile pictures for a user
 #[derive(Clone, Debug, Serialize)]
 pub struct GetUserProfilePhotos {
-    user_id: Integer,
+    user_id: UserId,
     #[serde(skip_serializing_if = "Option::is_none")]
     offset: Option<Integer>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -20,10 +23,10 @@ impl GetUserProfilePhotos {
     ///
     /// # Arguments
     ///
-    /// user_id - Unique identifier of the target user
-    pub fn new(user_id: Integer) -> Self {
+    /// * user_id - Unique identifier of the target user
+    pub fn new<U: Into<UserId>>(user_id: U) -> Self {
         GetUserProfilePhotos {
-            user_id,
+            user_id: user_id.into(),
             offset: None,
             limit: None,
         }
@@ -51,6 +54,55 @@ impl Method for GetUserProfilePhotos {
     type Response = UserProfilePhotos;
 
     fn get_request(&self) -> Result<RequestBuilder, Error> {
+        if let Some(limit) = self.limit {
+            check_range("limit", limit, 1, MAX_USER_PROFILE_PHOTOS_LIMIT)?;
+        }
         RequestBuilder::json("getUserProfilePhotos", &self)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::methods::DEFAULT_BASE_URL;
+    use serde_json::Value;
+
+    #[test]
+    fn test_get_user_profile_photos() {
+        let req = GetUserProfilePhotos::new(1)
+            .offset(5)
+            .limit(10)
+            .get_request()
+            .unwrap()
+            .build(DEFAULT_BASE_URL, "token");
+        assert_eq!(req.url, "https://api.telegram.org/bottoken/getUserProfilePhotos");
+        match req.body {
+            RequestBody::Json(data) => {
+                let data: Value = serde_json::from_slice(&data).unwrap();
+                assert_eq!(data["user_id"], 1);
+                assert_eq!(data["offset"], 5);
+                assert_eq!(data["limit"], 10);
+            }
+            data => panic!("Unexpected request data: {:?}", data),
+        }
+
+        for limit in &[0, MAX_USER_PROFILE_PHOTOS_LIMIT + 1] {
+            let err = GetUserProfilePhotos::new(1).limit(*limit).get_request().unwrap_err();
+            assert_eq!(err.to_string(), "Field \"limit\" must be between 1 and 100");
+        }
+
+        let photos: UserProfilePhotos = serde_json::from_value(serde_json::json!({
+            "total_count": 2,
+            "photos": [
+                [
+                    {"file_id": "small", "width": 160, "height": 160},
+                    {"file_id": "big", "width": 640, "height": 640}
+                ],
+                [{"file_id": "other", "width": 160, "height": 160}]
+            ]
+        }))
+        .unwrap();
+        let largest: Vec<&str> = photos.largest().map(|size| size.file_id.as_str()).collect();
+        assert_eq!(largest, vec!["big", "other"]);
+    }
+}
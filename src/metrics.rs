@@ -0,0 +1,65 @@
+use crate::{api::ApiError, types::Integer};
+use std::time::Duration;
+
+/// Receives latency and outcome of each method executed by Api
+///
+/// Use it to export request metrics, e.g. Prometheus counters and histograms
+pub trait Metrics: Send + Sync {
+    /// Called when a method is completed
+    ///
+    /// # Arguments
+    ///
+    /// * method - Name of the method, e.g. "sendMessage"
+    /// * latency - Time spent on the method including retries and waiting for rate limit
+    /// * outcome - Whether the method succeeded and why it failed otherwise
+    fn observe_request(&self, method: &str, latency: Duration, outcome: RequestOutcome);
+}
+
+/// Outcome of a method passed to Metrics
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RequestOutcome {
+    /// Method succeeded
+    Success,
+    /// Telegram has returned an error with the given code
+    ResponseError(Integer),
+    /// Request has not been sent because it is invalid
+    InvalidRequest,
+    /// Failed to send a request or to receive a response
+    TransportError,
+    /// Response has not been received in time
+    Timeout,
+    /// Response is not a valid API response
+    InvalidResponse,
+}
+
+impl RequestOutcome {
+    pub(crate) fn from_result<T>(result: &Result<T, ApiError>) -> Self {
+        match result {
+            Ok(_) => RequestOutcome::Success,
+            Err(ApiError::Response { error, .. }) => RequestOutcome::ResponseError(error.error_code),
+            Err(ApiError::Request(_)) => RequestOutcome::InvalidRequest,
+            Err(ApiError::Transport(_)) | Err(ApiError::Write(_)) => RequestOutcome::TransportError,
+            Err(ApiError::Timeout) => RequestOutcome::Timeout,
+            Err(ApiError::Json(_)) | Err(ApiError::UnexpectedStatus { .. }) | Err(ApiError::MissingFilePath) => {
+                RequestOutcome::InvalidResponse
+            }
+        }
+    }
+
+    /// Returns true when method succeeded
+    pub fn is_success(self) -> bool {
+        self == RequestOutcome::Success
+    }
+
+    /// Returns a short name of the outcome suitable for a metric label
+    pub fn as_str(self) -> &'static str {
+        match self {
+            RequestOutcome::Success => "success",
+            RequestOutcome::ResponseError(_) => "response_error",
+            RequestOutcome::InvalidRequest => "invalid_request",
+            RequestOutcome::TransportError => "transport_error",
+            RequestOutcome::Timeout => "timeout",
+            RequestOutcome::InvalidResponse => "invalid_response",
+        }
+    }
+}
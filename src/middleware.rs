@@ -0,0 +1,15 @@
+use crate::{executor::Response, methods::Request};
+
+/// Modifies requests sent by Api and raw responses received from an executor
+///
+/// Use it to add HTTP headers, rewrite URLs or alter responses for all methods at once
+/// Requests are passed to middlewares in the order they were added, responses in the reverse order
+/// Unlike Inspector, a middleware gets the bot token in request URL
+/// File downloads are not passed to middlewares
+pub trait Middleware: Send + Sync {
+    /// Called before a request is sent, including retries
+    fn handle_request(&self, _request: &mut Request) {}
+
+    /// Called when a response is received, before it is inspected and parsed
+    fn handle_response(&self, _request: &Request, _response: &mut Response) {}
+}
@@ -0,0 +1,250 @@
+use crate::types::ChatId;
+use std::{
+    cmp::max,
+    collections::HashMap,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+use tokio::time::sleep_until;
+
+/// Maximum number of chat buckets kept before outdated ones are removed
+const MAX_IDLE_CHATS: usize = 1000;
+
+/// Limits the rate of outgoing requests
+///
+/// Only requests with a chat_id (i.e. sending or editing messages) are limited,
+/// other requests, including getUpdates, are sent immediately
+/// Groups, supergroups and channels (negative IDs and usernames) have an additional per minute limit
+#[derive(Clone, Copy, Debug)]
+pub struct RateLimit {
+    overall: u32,
+    per_chat: u32,
+    per_group: u32,
+}
+
+impl RateLimit {
+    /// Creates a new rate limit
+    ///
+    /// # Arguments
+    ///
+    /// * overall - Maximum number of requests per second in all chats
+    /// * per_chat - Maximum number of requests per second in a single chat
+    ///
+    /// Zero values are treated as 1
+    /// Limit in groups defaults to 20 messages per minute
+    pub fn new(overall: u32, per_chat: u32) -> Self {
+        RateLimit {
+            overall: max(overall, 1),
+            per_chat: max(per_chat, 1),
+            per_group: 20,
+        }
+    }
+
+    /// Maximum number of requests per minute in a single group, supergroup or channel
+    ///
+    /// Zero value is treated as 1
+    pub fn per_group(mut self, per_minute: u32) -> Self {
+        self.per_group = max(per_minute, 1);
+        self
+    }
+}
+
+impl Default for RateLimit {
+    /// Limits recommended by Telegram: 30 messages per second overall,
+    /// 1 message per second in a chat and 20 messages per minute in a group
+    fn default() -> Self {
+        RateLimit::new(30, 1)
+    }
+}
+
+pub(crate) struct RateLimiter {
+    limit: RateLimit,
+    state: Mutex<LimiterState>,
+}
+
+struct LimiterState {
+    overall: Bucket,
+    chats: HashMap<String, ChatBuckets>,
+}
+
+struct ChatBuckets {
+    chat: Bucket,
+    group: Option<Bucket>,
+}
+
+impl ChatBuckets {
+    fn new(limit: RateLimit, chat_id: &ChatId, now: Instant) -> Self {
+        ChatBuckets {
+            chat: Bucket::new(limit.per_chat, Duration::from_secs(1), now),
+            group: if is_group(chat_id) {
+                Some(Bucket::new(limit.per_group, Duration::from_secs(60), now))
+            } else {
+                None
+            },
+        }
+    }
+
+    fn conform_at(&self, now: Instant) -> Instant {
+        let at = self.chat.conform_at(now);
+        match self.group {
+            Some(ref group) => max(at, group.conform_at(now)),
+            None => at,
+        }
+    }
+
+    fn commit(&mut self, at: Instant) {
+        self.chat.commit(at);
+        if let Some(ref mut group) = self.group {
+            group.commit(at);
+        }
+    }
+
+    fn is_idle(&self, now: Instant) -> bool {
+        self.chat.tat <= now && self.group.as_ref().is_none_or(|group| group.tat <= now)
+    }
+}
+
+/// Group, supergroup and channel IDs are negative, usernames are available for supergroups and channels only
+fn is_group(chat_id: &ChatId) -> bool {
+    match chat_id {
+        ChatId::Id(id) => *id < 0,
+        ChatId::Username(_) => true,
+    }
+}
+
+impl RateLimiter {
+    pub(crate) fn new(limit: RateLimit) -> Self {
+        let now = Instant::now();
+        RateLimiter {
+            limit,
+            state: Mutex::new(LimiterState {
+                overall: Bucket::new(limit.overall, Duration::from_secs(1), now),
+                chats: HashMap::new(),
+            }),
+        }
+    }
+
+    pub(crate) fn limit(&self) -> RateLimit {
+        self.limit
+    }
+
+    /// Waits until a request to the chat can be sent
+    pub(crate) async fn acquire(&self, chat_id: &ChatId) {
+        let at = self.reserve(chat_id, Instant::now());
+        sleep_until(at.into()).await;
+    }
+
+    /// Reserves a slot in overall, chat and group buckets
+    ///
+    /// Returns a time when the request can be sent
+    fn reserve(&self, chat_id: &ChatId, now: Instant) -> Instant {
+        let mut state = match self.state.lock() {
+            Ok(state) => state,
+            Err(err) => err.into_inner(),
+        };
+        if state.chats.len() > MAX_IDLE_CHATS {
+            state.chats.retain(|_, buckets| !buckets.is_idle(now));
+        }
+        let limit = self.limit;
+        let key = chat_id.to_query_value();
+        let at = state
+            .chats
+            .entry(key.clone())
+            .or_insert_with(|| ChatBuckets::new(limit, chat_id, now))
+            .conform_at(now);
+        // a request delayed by a chat limit takes an overall slot when it is reserved,
+        // otherwise a request to a busy group would block requests to all other chats
+        let overall_at = state.overall.conform_at(now);
+        state.overall.commit(overall_at);
+        let at = max(at, overall_at);
+        if let Some(chat) = state.chats.get_mut(&key) {
+            chat.commit(at);
+        }
+        at
+    }
+}
+
+/// Token bucket implemented as generic cell rate algorithm
+///
+/// Allows a burst of `rate` requests, then one request per `period / rate`
+struct Bucket {
+    interval: Duration,
+    tolerance: Duration,
+    /// Theoretical arrival time of the next request
+    tat: Instant,
+}
+
+impl Bucket {
+    fn new(rate: u32, period: Duration, now: Instant) -> Self {
+        let interval = period / rate;
+        Bucket {
+            interval,
+            tolerance: interval * (rate - 1),
+            tat: now,
+        }
+    }
+
+    /// Returns the earliest time when a request conforms to the rate
+    fn conform_at(&self, now: Instant) -> Instant {
+        match self.tat.checked_sub(self.tolerance) {
+            Some(at) => max(at, now),
+            None => now,
+        }
+    }
+
+    fn commit(&mut self, at: Instant) {
+        self.tat = max(self.tat, at) + self.interval;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Integer;
+
+    #[test]
+    fn test_reserve() {
+        let limiter = RateLimiter::new(RateLimit::new(3, 1));
+        let now = Instant::now();
+        let reserve = |chat_id: Integer| limiter.reserve(&ChatId::Id(chat_id), now).duration_since(now);
+
+        // burst of overall limit in different chats
+        assert_eq!(reserve(1), Duration::from_secs(0));
+        assert_eq!(reserve(2), Duration::from_secs(0));
+        assert_eq!(reserve(3), Duration::from_secs(0));
+        // overall limit is exceeded, a new request is allowed when a token is refilled
+        assert_eq!(reserve(4), Duration::from_secs(1) / 3);
+        // chat limit is exceeded
+        assert_eq!(reserve(1), Duration::from_secs(1));
+        assert_eq!(reserve(1), Duration::from_secs(2));
+    }
+
+    #[test]
+    fn test_zero_rate() {
+        let limiter = RateLimiter::new(RateLimit::new(0, 0));
+        let now = Instant::now();
+        assert_eq!(limiter.reserve(&ChatId::Id(1), now), now);
+        assert_eq!(limiter.reserve(&ChatId::Id(2), now), now + Duration::from_secs(1));
+    }
+
+    #[test]
+    fn test_group_limit() {
+        let limiter = RateLimiter::new(RateLimit::new(100, 10).per_group(2));
+        let now = Instant::now();
+        let reserve = |chat_id: &str| limiter.reserve(&ChatId::from(chat_id), now).duration_since(now);
+
+        let reserve_id = |chat_id: Integer| limiter.reserve(&ChatId::Id(chat_id), now).duration_since(now);
+
+        assert_eq!(reserve_id(-1), Duration::from_secs(0));
+        assert_eq!(reserve_id(-1), Duration::from_secs(0));
+        // group limit is exceeded, a new request is allowed in 30 seconds
+        assert_eq!(reserve_id(-1), Duration::from_secs(30));
+        assert_eq!(reserve("@channel"), Duration::from_secs(0));
+        assert_eq!(reserve("@channel"), Duration::from_secs(0));
+        assert_eq!(reserve("@channel"), Duration::from_secs(30));
+        // private chats are not affected
+        assert_eq!(reserve_id(1), Duration::from_secs(0));
+        assert_eq!(reserve_id(1), Duration::from_secs(0));
+        assert_eq!(reserve_id(1), Duration::from_secs(0));
+    }
+}
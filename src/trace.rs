@@ -0,0 +1,35 @@
+use crate::{api::ApiError, types::Update};
+use std::{future::Future, time::Instant};
+use tracing::{field::Empty, Instrument, Span};
+
+/// Runs a request within a span with method name, chat_id, duration and outcome
+///
+/// Retries and chat migration are included into the span
+pub(crate) async fn instrument_request<F, T>(method: String, chat_id: Option<String>, request: F) -> Result<T, ApiError>
+where
+    F: Future<Output = Result<T, ApiError>>,
+{
+    let span = tracing::info_span!(
+        "api_request",
+        method = %method,
+        chat_id = chat_id.as_deref(),
+        duration_ms = Empty,
+        outcome = Empty,
+        error = Empty,
+    );
+    let started = Instant::now();
+    let result = request.instrument(span.clone()).await;
+    span.record("duration_ms", started.elapsed().as_millis() as u64);
+    match result {
+        Ok(_) => span.record("outcome", "ok"),
+        Err(ref err) => span
+            .record("outcome", "error")
+            .record("error", tracing::field::display(err)),
+    };
+    result
+}
+
+/// Returns a span for processing of an update
+pub(crate) fn update_span(update: &Update) -> Span {
+    tracing::info_span!("update", update_id = update.id, chat_id = update.get_chat_id())
+}
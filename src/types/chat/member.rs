@@ -6,7 +6,10 @@ use crate::types::{
 use serde::de::{Deserialize, Deserializer, Error};
 
 /// Information about one member of a chat
+///
+/// New member statuses may appear, use a wildcard arm in matches
 #[derive(Clone, Debug)]
+#[non_exhaustive]
 pub enum ChatMember {
     /// Chat admin
     Administrator(ChatMemberAdministrator),
@@ -35,6 +38,11 @@ impl ChatMember {
             Restricted(ref restricted) => &restricted.user,
         }
     }
+
+    /// Returns true if the user is the creator or an administrator of the chat
+    pub fn is_admin(&self) -> bool {
+        matches!(self, ChatMember::Administrator(_) | ChatMember::Creator(_))
+    }
 }
 
 impl<'de> Deserialize<'de> for ChatMember {
@@ -50,7 +58,7 @@ impl<'de> Deserialize<'de> for ChatMember {
                     None => return Err(D::Error::missing_field(stringify!($name))),
                 }
             }};
-        };
+        }
         Ok(match raw.status {
             RawChatMemberStatus::Administrator => ChatMember::Administrator(ChatMemberAdministrator {
                 user: raw.user,
@@ -4,8 +4,10 @@ use crate::types::{
     primitive::Integer,
 };
 use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
 
 mod member;
+mod permissions;
 mod photo;
 mod raw;
 #[cfg(test)]
@@ -13,6 +15,7 @@ mod tests;
 
 pub use self::{
     member::{ChatMember, ChatMemberAdministrator, ChatMemberKicked, ChatMemberRestricted},
+    permissions::ChatPermissions,
     photo::ChatPhoto,
 };
 
@@ -42,7 +45,7 @@ impl<'de> Deserialize<'de> for Chat {
                     None => return Err(D::Error::missing_field(stringify!($name))),
                 }
             }};
-        };
+        }
         Ok(match raw_chat.kind {
             RawChatKind::Channel => Chat::Channel(ChannelChat {
                 id: raw_chat.id,
@@ -180,6 +183,24 @@ pub enum ChatId {
     Id(Integer),
 }
 
+impl ChatId {
+    /// Returns a canonical string representation of chat ID
+    ///
+    /// Use it when chat ID should be passed outside of JSON body, e.g. in a query string
+    pub fn to_query_value(&self) -> String {
+        match self {
+            ChatId::Username(username) => username.clone(),
+            ChatId::Id(id) => id.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for ChatId {
+    fn fmt(&self, out: &mut fmt::Formatter) -> fmt::Result {
+        write!(out, "{}", self.to_query_value())
+    }
+}
+
 impl Serialize for ChatId {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -198,6 +219,12 @@ impl From<&str> for ChatId {
     }
 }
 
+impl From<String> for ChatId {
+    fn from(username: String) -> ChatId {
+        ChatId::Username(username)
+    }
+}
+
 impl From<Integer> for ChatId {
     fn from(id: Integer) -> ChatId {
         ChatId::Id(id)
@@ -206,11 +233,17 @@ impl From<Integer> for ChatId {
 
 /// Type of action to tell the user that some is happening on the bot's side
 #[derive(Clone, Copy, Debug, Serialize)]
+#[non_exhaustive]
 pub enum ChatAction {
+    /// For stickers
+    #[serde(rename = "choose_sticker")]
+    ChooseSticker,
     /// For location data
     #[serde(rename = "find_location")]
     FindLocation,
     /// For audio files
+    ///
+    /// Replaced by record_voice in Bot API, use RecordVoice instead
     #[serde(rename = "record_audio")]
     RecordAudio,
     /// For videos
@@ -219,10 +252,15 @@ pub enum ChatAction {
     /// For video notes
     #[serde(rename = "record_video_note")]
     RecordVideoNote,
+    /// For voice notes
+    #[serde(rename = "record_voice")]
+    RecordVoice,
     /// For text messages
     #[serde(rename = "typing")]
     Typing,
     /// For audio files
+    ///
+    /// Replaced by upload_voice in Bot API, use UploadVoice instead
     #[serde(rename = "upload_audio")]
     UploadAudio,
     /// For general files
@@ -237,4 +275,7 @@ pub enum ChatAction {
     /// For video notes
     #[serde(rename = "upload_video_note")]
     UploadVideoNote,
+    /// For voice notes
+    #[serde(rename = "upload_voice")]
+    UploadVoice,
 }
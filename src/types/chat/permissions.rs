@@ -0,0 +1,64 @@
+use serde::{Deserialize, Serialize};
+
+/// Actions that a non-administrator user is allowed to take in a chat
+///
+/// Permissions which are None are left unchanged when used in a request
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct ChatPermissions {
+    /// The user is allowed to send text messages, contacts, locations and venues
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub can_send_messages: Option<bool>,
+    /// The user is allowed to send audios, documents, photos,
+    /// videos, video notes and voice notes, implies can_send_messages
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub can_send_media_messages: Option<bool>,
+    /// The user is allowed to send polls, implies can_send_messages
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub can_send_polls: Option<bool>,
+    /// The user is allowed to send animations, games, stickers
+    /// and use inline bots, implies can_send_media_messages
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub can_send_other_messages: Option<bool>,
+    /// The user is allowed to add web page previews to their messages,
+    /// implies can_send_media_messages
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub can_add_web_page_previews: Option<bool>,
+    /// The user is allowed to change the chat title, photo and other settings
+    ///
+    /// Ignored in public supergroups
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub can_change_info: Option<bool>,
+    /// The user is allowed to invite new users to the chat
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub can_invite_users: Option<bool>,
+    /// The user is allowed to pin messages
+    ///
+    /// Ignored in public supergroups
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub can_pin_messages: Option<bool>,
+}
+
+impl ChatPermissions {
+    /// Permissions which allow everything
+    pub fn all_allowed() -> Self {
+        ChatPermissions::with_value(true)
+    }
+
+    /// Permissions which allow nothing
+    pub fn none() -> Self {
+        ChatPermissions::with_value(false)
+    }
+
+    fn with_value(value: bool) -> Self {
+        ChatPermissions {
+            can_send_messages: Some(value),
+            can_send_media_messages: Some(value),
+            can_send_polls: Some(value),
+            can_send_other_messages: Some(value),
+            can_add_web_page_previews: Some(value),
+            can_change_info: Some(value),
+            can_invite_users: Some(value),
+            can_pin_messages: Some(value),
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use crate::types::chat::Chat;
+use crate::types::chat::{Chat, ChatId};
 
 #[test]
 fn test_deserialize_channel() {
@@ -47,11 +47,11 @@ fn test_deserialize_channel() {
     if let Chat::Channel(chat) = chat {
         assert_eq!(chat.id, 1);
         assert_eq!(chat.title, String::from("channeltitle"));
-        assert_eq!(chat.username.is_none(), true);
-        assert_eq!(chat.photo.is_none(), true);
-        assert_eq!(chat.description.is_none(), true);
-        assert_eq!(chat.invite_link.is_none(), true);
-        assert_eq!(chat.pinned_message.is_none(), true);
+        assert!(chat.username.is_none());
+        assert!(chat.photo.is_none());
+        assert!(chat.description.is_none());
+        assert!(chat.invite_link.is_none());
+        assert!(chat.pinned_message.is_none());
     } else {
         panic!("Unexpected chat: {:?}", chat);
     }
@@ -90,7 +90,7 @@ fn test_deserialize_group() {
     if let Chat::Group(chat) = chat {
         assert_eq!(chat.id, 1);
         assert_eq!(chat.title, String::from("grouptitle"));
-        assert_eq!(chat.all_members_are_administrators, true);
+        assert!(chat.all_members_are_administrators);
         let photo = chat.photo.unwrap();
         assert_eq!(photo.small_file_id, String::from("smallfileid"));
         assert_eq!(photo.big_file_id, String::from("bigfileid"));
@@ -109,10 +109,10 @@ fn test_deserialize_group() {
     if let Chat::Group(chat) = chat {
         assert_eq!(chat.id, 1);
         assert_eq!(chat.title, String::from("grouptitle"));
-        assert_eq!(chat.all_members_are_administrators, false);
-        assert_eq!(chat.photo.is_none(), true);
-        assert_eq!(chat.invite_link.is_none(), true);
-        assert_eq!(chat.pinned_message.is_none(), true);
+        assert!(!chat.all_members_are_administrators);
+        assert!(chat.photo.is_none());
+        assert!(chat.invite_link.is_none());
+        assert!(chat.pinned_message.is_none());
     } else {
         panic!("Unexpected chat: {:?}", chat);
     }
@@ -152,10 +152,10 @@ fn test_deserialize_private() {
     let chat: Chat = serde_json::from_str(input).unwrap();
     if let Chat::Private(chat) = chat {
         assert_eq!(chat.id, 1);
-        assert_eq!(chat.username.is_none(), true);
+        assert!(chat.username.is_none());
         assert_eq!(chat.first_name, String::from("testfirstname"));
-        assert_eq!(chat.last_name.is_none(), true);
-        assert_eq!(chat.photo.is_none(), true);
+        assert!(chat.last_name.is_none());
+        assert!(chat.photo.is_none());
     } else {
         panic!("Unexpected chat: {:?}", chat)
     }
@@ -220,13 +220,29 @@ fn test_deserialize_supergroup() {
         assert_eq!(chat.id, 1);
         assert_eq!(chat.title, String::from("supergrouptitle"));
         assert_eq!(chat.username, Some(String::from("supergroupusername")));
-        assert_eq!(chat.photo.is_none(), true);
-        assert_eq!(chat.description.is_none(), true);
-        assert_eq!(chat.invite_link.is_none(), true);
-        assert_eq!(chat.sticker_set_name.is_none(), true);
-        assert_eq!(chat.can_set_sticker_set.is_none(), true);
-        assert_eq!(chat.pinned_message.is_none(), true);
+        assert!(chat.photo.is_none());
+        assert!(chat.description.is_none());
+        assert!(chat.invite_link.is_none());
+        assert!(chat.sticker_set_name.is_none());
+        assert!(chat.can_set_sticker_set.is_none());
+        assert!(chat.pinned_message.is_none());
     } else {
         panic!("Unexpected chat: {:?}", chat)
     }
 }
+
+#[test]
+fn test_chat_id() {
+    let chat_id = ChatId::from(1);
+    assert_eq!(chat_id.to_query_value(), "1");
+    assert_eq!(chat_id.to_string(), "1");
+    assert_eq!(serde_json::to_string(&chat_id).unwrap(), "1");
+
+    let chat_id = ChatId::from("@username");
+    assert_eq!(chat_id.to_query_value(), "@username");
+    assert_eq!(chat_id.to_string(), "@username");
+    assert_eq!(serde_json::to_string(&chat_id).unwrap(), r#""@username""#);
+
+    let chat_id = ChatId::from(String::from("@username"));
+    assert_eq!(chat_id.to_query_value(), "@username");
+}
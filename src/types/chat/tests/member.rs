@@ -23,22 +23,23 @@ fn test_deserialize_chat_member_admin() {
         "can_promote_members": true
     }"#;
     let admin: ChatMember = serde_json::from_str(admin).unwrap();
+    assert!(admin.is_admin());
     if let ChatMember::Administrator(ref admin) = admin {
         assert_eq!(admin.user.id, 1);
-        assert_eq!(admin.user.is_bot, false);
+        assert!(!admin.user.is_bot);
         assert_eq!(admin.user.first_name, String::from("firstname"));
         assert_eq!(admin.user.last_name, Some(String::from("lastname")));
         assert_eq!(admin.user.username, Some(String::from("username")));
         assert_eq!(admin.user.language_code, Some(String::from("RU")));
-        assert_eq!(admin.can_be_edited, true);
-        assert_eq!(admin.can_change_info, false);
-        assert_eq!(admin.can_post_messages, true);
-        assert_eq!(admin.can_edit_messages, false);
-        assert_eq!(admin.can_delete_messages, true);
-        assert_eq!(admin.can_invite_users, false);
-        assert_eq!(admin.can_restrict_members, true);
-        assert_eq!(admin.can_pin_messages, false);
-        assert_eq!(admin.can_promote_members, true);
+        assert!(admin.can_be_edited);
+        assert!(!admin.can_change_info);
+        assert!(admin.can_post_messages);
+        assert!(!admin.can_edit_messages);
+        assert!(admin.can_delete_messages);
+        assert!(!admin.can_invite_users);
+        assert!(admin.can_restrict_members);
+        assert!(!admin.can_pin_messages);
+        assert!(admin.can_promote_members);
     } else {
         panic!("Unexpected chat member: {:?}", admin);
     }
@@ -55,9 +56,10 @@ fn test_deserialize_chat_member_creator() {
         }
     }"#;
     let creator: ChatMember = serde_json::from_str(creator).unwrap();
+    assert!(creator.is_admin());
     if let ChatMember::Creator(ref creator) = creator {
         assert_eq!(creator.id, 1);
-        assert_eq!(creator.is_bot, false);
+        assert!(!creator.is_bot);
         assert_eq!(creator.first_name, String::from("firstname"));
         assert_eq!(creator.last_name, None);
         assert_eq!(creator.username, None);
@@ -81,9 +83,10 @@ fn test_deserialize_chat_member_kicked() {
         "until_date": 0
     }"#;
     let kicked: ChatMember = serde_json::from_str(kicked).unwrap();
+    assert!(!kicked.is_admin());
     if let ChatMember::Kicked(ref kicked) = kicked {
         assert_eq!(kicked.user.id, 1);
-        assert_eq!(kicked.user.is_bot, true);
+        assert!(kicked.user.is_bot);
         assert_eq!(kicked.user.first_name, String::from("firstname"));
         assert_eq!(kicked.user.last_name, Some(String::from("lastname")));
         assert_eq!(kicked.user.username, Some(String::from("username")));
@@ -105,9 +108,10 @@ fn test_deserialize_chat_member_left() {
         }
     }"#;
     let left: ChatMember = serde_json::from_str(left).unwrap();
+    assert!(!left.is_admin());
     if let ChatMember::Left(ref left) = left {
         assert_eq!(left.id, 1);
-        assert_eq!(left.is_bot, true);
+        assert!(left.is_bot);
         assert_eq!(left.first_name, String::from("firstname"));
         assert_eq!(left.last_name, None);
         assert_eq!(left.username, None);
@@ -128,9 +132,10 @@ fn test_deserialize_chat_member_plain() {
         }
     }"#;
     let plain: ChatMember = serde_json::from_str(plain).unwrap();
+    assert!(!plain.is_admin());
     if let ChatMember::Member(ref plain) = plain {
         assert_eq!(plain.id, 1);
-        assert_eq!(plain.is_bot, false);
+        assert!(!plain.is_bot);
         assert_eq!(plain.first_name, String::from("firstname"));
         assert_eq!(plain.last_name, None);
         assert_eq!(plain.username, None);
@@ -156,18 +161,19 @@ fn test_deserialize_chat_member_restricted() {
         "can_add_web_page_previews": false
     }"#;
     let restricted: ChatMember = serde_json::from_str(restricted).unwrap();
+    assert!(!restricted.is_admin());
     if let ChatMember::Restricted(ref restricted) = restricted {
         assert_eq!(restricted.user.id, 1);
-        assert_eq!(restricted.user.is_bot, true);
+        assert!(restricted.user.is_bot);
         assert_eq!(restricted.user.first_name, String::from("firstname"));
         assert_eq!(restricted.user.last_name, None);
         assert_eq!(restricted.user.username, None);
         assert_eq!(restricted.user.language_code, None);
         assert_eq!(restricted.until_date, 0);
-        assert_eq!(restricted.can_send_messages, true);
-        assert_eq!(restricted.can_send_media_messages, false);
-        assert_eq!(restricted.can_send_other_messages, true);
-        assert_eq!(restricted.can_add_web_page_previews, false);
+        assert!(restricted.can_send_messages);
+        assert!(!restricted.can_send_media_messages);
+        assert!(restricted.can_send_other_messages);
+        assert!(!restricted.can_add_web_page_previews);
     } else {
         panic!("Unexpected chat member: {:?}", restricted);
     }
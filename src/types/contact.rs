@@ -1,4 +1,4 @@
-use crate::types::primitive::Integer;
+use crate::types::user::UserId;
 use serde::Deserialize;
 
 /// Phone contact
@@ -11,7 +11,7 @@ pub struct Contact {
     /// Contact's last name
     pub last_name: Option<String>,
     /// Contact's user identifier in Telegram
-    pub user_id: Option<Integer>,
+    pub user_id: Option<UserId>,
     /// Additional data about the contact in the form of a vCard
     pub vcard: Option<String>,
 }
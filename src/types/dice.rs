@@ -0,0 +1,13 @@
+use crate::types::primitive::Integer;
+use serde::Deserialize;
+
+/// A dice with a random value
+#[derive(Clone, Debug, Deserialize)]
+pub struct Dice {
+    /// Emoji on which the dice throw animation is based
+    pub emoji: String,
+    /// Value of the dice
+    ///
+    /// 1-6 for “🎲” and “🎯” base emoji, 1-5 for “🏀” base emoji
+    pub value: Integer,
+}
@@ -7,6 +7,7 @@ use serde::Deserialize;
 /// It is guaranteed that the link will be valid for at least 1 hour
 /// When the link expires, a new one can be requested by calling getFile
 /// Maximum file size to download is 20 MB
+/// A local Bot API server started with --local returns an absolute path instead
 #[derive(Clone, Debug, Deserialize)]
 pub struct File {
     /// Unique identifier for this file
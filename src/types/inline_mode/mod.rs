@@ -3,8 +3,9 @@ use serde::Deserialize;
 
 mod message_content;
 mod query_result;
+mod results_button;
 
-pub use self::{message_content::*, query_result::*};
+pub use self::{message_content::*, query_result::*, results_button::*};
 
 /// Incoming inline query
 ///
@@ -39,3 +40,11 @@ pub struct ChosenInlineResult {
     /// The query that was used to obtain the result
     pub query: String,
 }
+
+/// Information about an inline message sent by a Web App on behalf of a user
+#[derive(Clone, Debug, Deserialize)]
+pub struct SentWebAppMessage {
+    /// Identifier of the sent inline message
+    /// Available only if there is an inline keyboard attached to the message
+    pub inline_message_id: Option<String>,
+}
@@ -0,0 +1,43 @@
+use crate::types::reply_markup::WebAppInfo;
+use serde::Serialize;
+
+/// Button to be shown above inline query results
+#[derive(Clone, Debug, Serialize)]
+pub struct InlineQueryResultsButton {
+    text: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    web_app: Option<WebAppInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    start_parameter: Option<String>,
+}
+
+impl InlineQueryResultsButton {
+    /// Button that launches a Web App
+    ///
+    /// # Arguments
+    ///
+    /// * text - Label text on the button
+    /// * web_app - Web App to be launched when the user presses the button
+    pub fn with_web_app<S: Into<String>>(text: S, web_app: WebAppInfo) -> Self {
+        InlineQueryResultsButton {
+            text: text.into(),
+            web_app: Some(web_app),
+            start_parameter: None,
+        }
+    }
+
+    /// Button that switches the user to a private chat with the bot
+    ///
+    /// # Arguments
+    ///
+    /// * text - Label text on the button
+    /// * start_parameter - Deep-linking parameter for the /start message sent to the bot,
+    ///                     1-64 characters, only A-Z, a-z, 0-9, _ and - are allowed
+    pub fn with_start_parameter<S: Into<String>>(text: S, start_parameter: S) -> Self {
+        InlineQueryResultsButton {
+            text: text.into(),
+            web_app: None,
+            start_parameter: Some(start_parameter.into()),
+        }
+    }
+}
@@ -0,0 +1,297 @@
+use bytes::Bytes;
+use serde::{Serialize, Serializer};
+use std::{
+    fmt,
+    io::{Error as IoError, ErrorKind as IoErrorKind, Result as IoResult},
+    path::{Path, PathBuf},
+    pin::Pin,
+    sync::{Arc, Mutex},
+};
+use tokio::io::AsyncRead;
+
+/// File to send
+///
+/// Use a file_id to send a file that exists on the Telegram servers,
+/// an HTTP URL for Telegram to get a file from the Internet,
+/// or a path, in-memory bytes or a reader to upload a new file using multipart/form-data
+#[derive(Clone, Debug)]
+pub struct InputFile {
+    pub(crate) kind: InputFileKind,
+}
+
+#[derive(Clone, Debug)]
+pub(crate) enum InputFileKind {
+    Id(String),
+    Url(String),
+    Path(InputFilePath),
+    Bytes(InputFileBytes),
+    Reader(InputFileReader),
+}
+
+#[derive(Clone, Debug)]
+pub(crate) struct InputFilePath {
+    pub(crate) path: PathBuf,
+    pub(crate) file_name: String,
+    pub(crate) mime_type: &'static str,
+}
+
+#[derive(Clone)]
+pub(crate) struct InputFileBytes {
+    pub(crate) data: Bytes,
+    pub(crate) file_name: String,
+    pub(crate) mime_type: &'static str,
+}
+
+pub(crate) type InputFileStream = Pin<Box<dyn AsyncRead + Send>>;
+
+#[derive(Clone)]
+pub(crate) struct InputFileReader {
+    reader: Arc<Mutex<Option<InputFileStream>>>,
+    pub(crate) file_name: String,
+    pub(crate) mime_type: &'static str,
+}
+
+impl InputFileReader {
+    /// Takes the reader out, so it can be read only once even if the file is cloned
+    pub(crate) fn take(&self) -> Option<InputFileStream> {
+        self.reader.lock().ok().and_then(|mut reader| reader.take())
+    }
+}
+
+impl fmt::Debug for InputFileReader {
+    fn fmt(&self, out: &mut fmt::Formatter) -> fmt::Result {
+        out.debug_struct("InputFileReader")
+            .field("file_name", &self.file_name)
+            .field("mime_type", &self.mime_type)
+            .finish()
+    }
+}
+
+impl fmt::Debug for InputFileBytes {
+    fn fmt(&self, out: &mut fmt::Formatter) -> fmt::Result {
+        out.debug_struct("InputFileBytes")
+            .field("file_name", &self.file_name)
+            .field("mime_type", &self.mime_type)
+            .field("len", &self.data.len())
+            .finish()
+    }
+}
+
+impl InputFile {
+    /// A file that exists on the Telegram servers
+    pub fn file_id<S: Into<String>>(file_id: S) -> Self {
+        InputFile {
+            kind: InputFileKind::Id(file_id.into()),
+        }
+    }
+
+    /// A file from the Internet
+    pub fn url<S: Into<String>>(url: S) -> Self {
+        InputFile {
+            kind: InputFileKind::Url(url.into()),
+        }
+    }
+
+    /// A file from the filesystem
+    ///
+    /// The file is opened only when a request is sent
+    /// File name and MIME type are inferred from the path
+    ///
+    /// Returns an error when the path does not point to a regular file
+    pub fn path<P: AsRef<Path>>(path: P) -> IoResult<Self> {
+        let path = path.as_ref();
+        if !path.metadata()?.is_file() {
+            return Err(IoError::new(
+                IoErrorKind::InvalidInput,
+                format!("{} is not a file", path.display()),
+            ));
+        }
+        let file_name = match path.file_name() {
+            Some(file_name) => file_name.to_string_lossy().into_owned(),
+            None => return Err(IoError::new(IoErrorKind::InvalidInput, "file name is missing")),
+        };
+        Ok(InputFile {
+            kind: InputFileKind::Path(InputFilePath {
+                mime_type: guess_mime_type(path),
+                path: path.to_path_buf(),
+                file_name,
+            }),
+        })
+    }
+
+    /// A file from memory
+    ///
+    /// MIME type is inferred from the file name
+    ///
+    /// # Arguments
+    ///
+    /// * file_name - Name of the file shown to users
+    /// * data - Contents of the file
+    pub fn bytes<N: Into<String>, D: Into<Bytes>>(file_name: N, data: D) -> Self {
+        let file_name = file_name.into();
+        InputFile {
+            kind: InputFileKind::Bytes(InputFileBytes {
+                mime_type: guess_mime_type(Path::new(&file_name)),
+                data: data.into(),
+                file_name,
+            }),
+        }
+    }
+
+    /// A file from an async reader
+    ///
+    /// Contents are streamed when a request is sent, so a large file is never held in memory entirely
+    /// MIME type is inferred from the file name
+    ///
+    /// The reader can be consumed only once, so a request with such file can not be retried
+    /// and all clones of the file share the same reader
+    ///
+    /// # Arguments
+    ///
+    /// * file_name - Name of the file shown to users
+    /// * reader - Source of the file contents
+    pub fn reader<N, R>(file_name: N, reader: R) -> Self
+    where
+        N: Into<String>,
+        R: AsyncRead + Send + 'static,
+    {
+        let file_name = file_name.into();
+        InputFile {
+            kind: InputFileKind::Reader(InputFileReader {
+                reader: Arc::new(Mutex::new(Some(Box::pin(reader)))),
+                mime_type: guess_mime_type(Path::new(&file_name)),
+                file_name,
+            }),
+        }
+    }
+
+    /// Whether the file must be uploaded using multipart/form-data
+    pub(crate) fn is_upload(&self) -> bool {
+        match self.kind {
+            InputFileKind::Id(_) | InputFileKind::Url(_) => false,
+            InputFileKind::Path(_) | InputFileKind::Bytes(_) | InputFileKind::Reader(_) => true,
+        }
+    }
+}
+
+/// A string is sent as is, so it can be either a file_id or an URL
+impl From<String> for InputFile {
+    fn from(file_id: String) -> Self {
+        InputFile::file_id(file_id)
+    }
+}
+
+impl<'a> From<&'a str> for InputFile {
+    fn from(file_id: &'a str) -> Self {
+        InputFile::file_id(file_id)
+    }
+}
+
+impl Serialize for InputFile {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self.kind {
+            InputFileKind::Id(ref value) | InputFileKind::Url(ref value) => serializer.serialize_str(value),
+            // content is sent in a separate part of multipart/form-data request
+            InputFileKind::Path(_) | InputFileKind::Bytes(_) | InputFileKind::Reader(_) => serializer.serialize_none(),
+        }
+    }
+}
+
+fn guess_mime_type(path: &Path) -> &'static str {
+    let extension = path
+        .extension()
+        .map(|extension| extension.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    match extension.as_str() {
+        "jpg" | "jpeg" => "image/jpeg",
+        "png" => "image/png",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "mp4" => "video/mp4",
+        "webm" => "video/webm",
+        "mp3" => "audio/mpeg",
+        "m4a" => "audio/mp4",
+        "ogg" | "oga" => "audio/ogg",
+        "pdf" => "application/pdf",
+        "zip" => "application/zip",
+        "tgs" => "application/x-tgsticker",
+        "txt" => "text/plain",
+        _ => "application/octet-stream",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{env, fs};
+
+    #[test]
+    fn test_input_file_path() {
+        let path = env::temp_dir().join("tgbot-input-file-test.JPG");
+        fs::write(&path, b"test").unwrap();
+        let input_file = InputFile::path(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        if let InputFileKind::Path(InputFilePath {
+            path: file_path,
+            file_name,
+            mime_type,
+        }) = input_file.kind
+        {
+            assert_eq!(file_path, path);
+            assert_eq!(file_name, "tgbot-input-file-test.JPG");
+            assert_eq!(mime_type, "image/jpeg");
+        } else {
+            panic!("Unexpected input file: {:?}", input_file);
+        }
+
+        assert_eq!(guess_mime_type(Path::new("sticker.tgs")), "application/x-tgsticker");
+        assert_eq!(guess_mime_type(Path::new("unknown")), "application/octet-stream");
+
+        assert!(InputFile::path(env::temp_dir()).is_err());
+        assert!(InputFile::path(env::temp_dir().join("tgbot-input-file-missing")).is_err());
+    }
+
+    #[test]
+    fn test_input_file_bytes() {
+        let input_file = InputFile::bytes("photo.png", vec![1, 2, 3]);
+        assert!(input_file.is_upload());
+        assert_eq!(
+            format!("{:?}", input_file),
+            r#"InputFile { kind: Bytes(InputFileBytes { file_name: "photo.png", mime_type: "image/png", len: 3 }) }"#
+        );
+        if let InputFileKind::Bytes(InputFileBytes {
+            data,
+            file_name,
+            mime_type,
+        }) = input_file.kind
+        {
+            assert_eq!(data, Bytes::from(vec![1, 2, 3]));
+            assert_eq!(file_name, "photo.png");
+            assert_eq!(mime_type, "image/png");
+        } else {
+            panic!("Unexpected input file: {:?}", input_file);
+        }
+    }
+
+    #[test]
+    fn test_input_file_reader() {
+        let input_file = InputFile::reader("video.mp4", &b"video"[..]);
+        assert!(input_file.is_upload());
+        let clone = input_file.clone();
+        if let (InputFileKind::Reader(reader), InputFileKind::Reader(clone)) = (input_file.kind, clone.kind) {
+            assert_eq!(reader.file_name, "video.mp4");
+            assert_eq!(reader.mime_type, "video/mp4");
+            assert_eq!(
+                format!("{:?}", reader),
+                r#"InputFileReader { file_name: "video.mp4", mime_type: "video/mp4" }"#
+            );
+            assert!(clone.take().is_some());
+            assert!(reader.take().is_none());
+        } else {
+            panic!("Unexpected input file");
+        }
+    }
+}
@@ -1,9 +1,21 @@
-use crate::types::primitive::{Integer, ParseMode};
+use crate::types::{
+    input_file::InputFile,
+    primitive::{Integer, ParseMode},
+};
 use serde::Serialize;
 
-/// Photo or video to be sent in a media group
+/// Photo, video, audio or document to be sent in a media group
+///
+/// Documents and audio files can be only grouped in an album with messages of the same type
 #[derive(Clone, Debug, derive_more::From, Serialize)]
+#[serde(tag = "type")]
 pub enum MediaGroupItem {
+    /// Audio file to be sent
+    #[serde(rename = "audio")]
+    Audio(InputMediaAudio),
+    /// General file to be sent
+    #[serde(rename = "document")]
+    Document(InputMediaDocument),
     /// Photo to be sent
     #[serde(rename = "photo")]
     Photo(InputMediaPhoto),
@@ -12,6 +24,18 @@ pub enum MediaGroupItem {
     Video(InputMediaVideo),
 }
 
+impl MediaGroupItem {
+    /// Returns files of the item with names of fields
+    pub(crate) fn files(&self) -> Vec<(&'static str, &InputFile)> {
+        match self {
+            MediaGroupItem::Audio(media) => get_files(&media.media, &media.thumb),
+            MediaGroupItem::Document(media) => get_files(&media.media, &media.thumb),
+            MediaGroupItem::Photo(media) => get_files(&media.media, &None),
+            MediaGroupItem::Video(media) => get_files(&media.media, &media.thumb),
+        }
+    }
+}
+
 /// Content of a media message to be sent
 #[derive(Clone, Debug, derive_more::From, Serialize)]
 #[serde(tag = "type")]
@@ -33,12 +57,33 @@ pub enum InputMedia {
     Video(InputMediaVideo),
 }
 
+impl InputMedia {
+    /// Returns files of the media with names of fields
+    pub(crate) fn files(&self) -> Vec<(&'static str, &InputFile)> {
+        match self {
+            InputMedia::Animation(media) => get_files(&media.media, &media.thumb),
+            InputMedia::Audio(media) => get_files(&media.media, &media.thumb),
+            InputMedia::Document(media) => get_files(&media.media, &media.thumb),
+            InputMedia::Photo(media) => get_files(&media.media, &None),
+            InputMedia::Video(media) => get_files(&media.media, &media.thumb),
+        }
+    }
+}
+
+fn get_files<'a>(media: &'a InputFile, thumb: &'a Option<InputFile>) -> Vec<(&'static str, &'a InputFile)> {
+    let mut files = vec![("media", media)];
+    if let Some(thumb) = thumb {
+        files.push(("thumb", thumb));
+    }
+    files
+}
+
 /// Animation file (GIF or H.264/MPEG-4 AVC video without sound) to be sent
 #[derive(Clone, Debug, Serialize)]
 pub struct InputMediaAnimation {
-    media: String,
+    media: InputFile,
     #[serde(skip_serializing_if = "Option::is_none")]
-    thumb: Option<String>,
+    thumb: Option<InputFile>,
     #[serde(skip_serializing_if = "Option::is_none")]
     caption: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -58,9 +103,8 @@ impl InputMediaAnimation {
     ///
     /// * media - Pass a file_id to send a file that exists on the Telegram servers (recommended),
     ///           pass an HTTP URL for Telegram to get a file from the Internet,
-    ///           or pass “attach://<file_attach_name>” to upload a new one using multipart/form-data
-    ///           under <file_attach_name> name
-    pub fn new<S: Into<String>>(media: S) -> Self {
+    ///           or pass InputFile::path() to upload a new one
+    pub fn new<F: Into<InputFile>>(media: F) -> Self {
         InputMediaAnimation {
             media: media.into(),
             thumb: None,
@@ -78,10 +122,8 @@ impl InputMediaAnimation {
     /// A thumbnail‘s width and height should not exceed 90
     /// Ignored if the file is not uploaded using multipart/form-data
     /// Thumbnails can’t be reused and can be only uploaded
-    /// as a new file, so you can pass “attach://<file_attach_name>”
-    /// if the thumbnail was uploaded using multipart/form-data
-    /// under <file_attach_name>
-    pub fn thumb<S: Into<String>>(mut self, thumb: S) -> Self {
+    /// as a new file, so pass a file created using InputFile::path()
+    pub fn thumb<F: Into<InputFile>>(mut self, thumb: F) -> Self {
         self.thumb = Some(thumb.into());
         self
     }
@@ -120,9 +162,9 @@ impl InputMediaAnimation {
 /// Audio file to be treated as music to be sent
 #[derive(Clone, Debug, Serialize)]
 pub struct InputMediaAudio {
-    media: String,
+    media: InputFile,
     #[serde(skip_serializing_if = "Option::is_none")]
-    thumb: Option<String>,
+    thumb: Option<InputFile>,
     #[serde(skip_serializing_if = "Option::is_none")]
     caption: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -142,9 +184,8 @@ impl InputMediaAudio {
     ///
     /// * media - Pass a file_id to send a file that exists on the Telegram servers (recommended),
     ///           pass an HTTP URL for Telegram to get a file from the Internet,
-    ///           or pass “attach://<file_attach_name>” to upload a new one using multipart/form-data
-    ///           under <file_attach_name> name
-    pub fn new<S: Into<String>>(media: S) -> Self {
+    ///           or pass InputFile::path() to upload a new one
+    pub fn new<F: Into<InputFile>>(media: F) -> Self {
         InputMediaAudio {
             media: media.into(),
             thumb: None,
@@ -162,10 +203,8 @@ impl InputMediaAudio {
     /// A thumbnail‘s width and height should not exceed 90
     /// Ignored if the file is not uploaded using multipart/form-data
     /// Thumbnails can’t be reused and can be only uploaded
-    /// as a new file, so you can pass “attach://<file_attach_name>”
-    /// if the thumbnail was uploaded using multipart/form-data
-    /// under <file_attach_name>
-    pub fn thumb<S: Into<String>>(mut self, thumb: S) -> Self {
+    /// as a new file, so pass a file created using InputFile::path()
+    pub fn thumb<F: Into<InputFile>>(mut self, thumb: F) -> Self {
         self.thumb = Some(thumb.into());
         self
     }
@@ -204,9 +243,9 @@ impl InputMediaAudio {
 /// General file to be sent
 #[derive(Clone, Debug, Serialize)]
 pub struct InputMediaDocument {
-    media: String,
+    media: InputFile,
     #[serde(skip_serializing_if = "Option::is_none")]
-    thumb: Option<String>,
+    thumb: Option<InputFile>,
     #[serde(skip_serializing_if = "Option::is_none")]
     caption: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -220,9 +259,8 @@ impl InputMediaDocument {
     ///
     /// * media - Pass a file_id to send a file that exists on the Telegram servers (recommended),
     ///           pass an HTTP URL for Telegram to get a file from the Internet,
-    ///           or pass “attach://<file_attach_name>” to upload a new one using multipart/form-data
-    ///           under <file_attach_name> name
-    pub fn new<S: Into<String>>(media: S) -> Self {
+    ///           or pass InputFile::path() to upload a new one
+    pub fn new<F: Into<InputFile>>(media: F) -> Self {
         InputMediaDocument {
             media: media.into(),
             thumb: None,
@@ -237,10 +275,8 @@ impl InputMediaDocument {
     /// A thumbnail‘s width and height should not exceed 90
     /// Ignored if the file is not uploaded using multipart/form-data
     /// Thumbnails can’t be reused and can be only uploaded
-    /// as a new file, so you can pass “attach://<file_attach_name>”
-    /// if the thumbnail was uploaded using multipart/form-data
-    /// under <file_attach_name>
-    pub fn thumb<S: Into<String>>(mut self, thumb: S) -> Self {
+    /// as a new file, so pass a file created using InputFile::path()
+    pub fn thumb<F: Into<InputFile>>(mut self, thumb: F) -> Self {
         self.thumb = Some(thumb.into());
         self
     }
@@ -261,7 +297,7 @@ impl InputMediaDocument {
 /// Photo to be sent
 #[derive(Clone, Debug, Serialize)]
 pub struct InputMediaPhoto {
-    media: String,
+    media: InputFile,
     #[serde(skip_serializing_if = "Option::is_none")]
     caption: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -275,9 +311,8 @@ impl InputMediaPhoto {
     ///
     /// * media - Pass a file_id to send a file that exists on the Telegram servers (recommended),
     ///           pass an HTTP URL for Telegram to get a file from the Internet,
-    ///           or pass “attach://<file_attach_name>” to upload a new one using multipart/form-data
-    ///           under <file_attach_name> name
-    pub fn new<S: Into<String>>(media: S) -> Self {
+    ///           or pass InputFile::path() to upload a new one
+    pub fn new<F: Into<InputFile>>(media: F) -> Self {
         InputMediaPhoto {
             media: media.into(),
             caption: None,
@@ -301,9 +336,9 @@ impl InputMediaPhoto {
 /// Video to be sent
 #[derive(Clone, Debug, Serialize)]
 pub struct InputMediaVideo {
-    media: String,
+    media: InputFile,
     #[serde(skip_serializing_if = "Option::is_none")]
-    thumb: Option<String>,
+    thumb: Option<InputFile>,
     #[serde(skip_serializing_if = "Option::is_none")]
     caption: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -325,9 +360,8 @@ impl InputMediaVideo {
     ///
     /// * media - Pass a file_id to send a file that exists on the Telegram servers (recommended),
     ///           pass an HTTP URL for Telegram to get a file from the Internet,
-    ///           or pass “attach://<file_attach_name>” to upload a new one using multipart/form-data
-    ///           under <file_attach_name> name
-    pub fn new<S: Into<String>>(media: S) -> Self {
+    ///           or pass InputFile::path() to upload a new one
+    pub fn new<F: Into<InputFile>>(media: F) -> Self {
         InputMediaVideo {
             media: media.into(),
             thumb: None,
@@ -346,10 +380,8 @@ impl InputMediaVideo {
     /// A thumbnail‘s width and height should not exceed 90
     /// Ignored if the file is not uploaded using multipart/form-data
     /// Thumbnails can’t be reused and can be only uploaded
-    /// as a new file, so you can pass “attach://<file_attach_name>”
-    /// if the thumbnail was uploaded using multipart/form-data
-    /// under <file_attach_name>
-    pub fn thumb<S: Into<String>>(mut self, thumb: S) -> Self {
+    /// as a new file, so pass a file created using InputFile::path()
+    pub fn thumb<F: Into<InputFile>>(mut self, thumb: F) -> Self {
         self.thumb = Some(thumb.into());
         self
     }
@@ -2,6 +2,7 @@ use crate::types::{
     animation::Animation,
     audio::Audio,
     contact::Contact,
+    dice::Dice,
     document::Document,
     games::Game,
     location::Location,
@@ -9,6 +10,7 @@ use crate::types::{
     passport::PassportData,
     payments::{Invoice, SuccessfulPayment},
     photo_size::PhotoSize,
+    poll::Poll,
     primitive::Integer,
     stickers::Sticker,
     user::User,
@@ -19,8 +21,12 @@ use crate::types::{
 };
 
 /// Contains message data
+///
+/// Telegram adds new kinds of messages frequently,
+/// so a match on this enum must have a wildcard arm
 #[derive(Clone, Debug)]
 #[allow(clippy::large_enum_variant)]
+#[non_exhaustive]
 pub enum MessageData {
     /// Message is an animation, information about the animation
     Animation(Animation),
@@ -42,6 +48,8 @@ pub enum MessageData {
     Contact(Contact),
     /// Service message: the chat photo was deleted
     DeleteChatPhoto,
+    /// Message is a dice with a random value
+    Dice(Dice),
     /// Document message
     Document {
         /// Document caption
@@ -84,6 +92,8 @@ pub enum MessageData {
         /// Photos
         data: Vec<PhotoSize>,
     },
+    /// Message is a native poll, information about the poll
+    Poll(Poll),
     /// Message is a sticker, information about the sticker
     Sticker(Sticker),
     /// Message is a service message about a successful payment, information about the payment
@@ -1,4 +1,4 @@
-use crate::types::{chat::ChannelChat, primitive::Integer, user::User};
+use crate::types::{chat::ChannelChat, message::MessageId, primitive::Integer, user::User};
 
 /// Contains information about original message
 #[derive(Clone, Debug)]
@@ -19,7 +19,7 @@ pub enum ForwardFrom {
         /// Information about the original chat
         chat: ChannelChat,
         /// Identifier of the original message in the channel
-        message_id: Integer,
+        message_id: MessageId,
         /// Signature of the post author if present
         signature: Option<String>,
     },
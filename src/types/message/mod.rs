@@ -1,5 +1,6 @@
 use crate::types::{chat::Chat, message::raw::RawMessage, primitive::Integer, user::User};
-use serde::{de::Error, Deserialize, Deserializer};
+use serde::{de::Error, Deserialize, Deserializer, Serialize};
+use std::fmt;
 
 mod data;
 mod forward;
@@ -16,7 +17,7 @@ pub use self::{data::*, forward::*, kind::*, text::*};
 #[derive(Clone, Debug)]
 pub struct Message {
     /// Unique message identifier inside this chat
-    pub id: Integer,
+    pub id: MessageId,
     /// Date the message was sent in Unix time
     pub date: Integer,
     /// Contains chat-specific data
@@ -105,6 +106,46 @@ impl Message {
         }
     }
 
+    /// Returns a command at the beginning of the message text
+    ///
+    /// The command is taken from a bot_command entity at offset 0,
+    /// so a command in the middle of a text is not returned
+    pub fn get_command(&self) -> Option<&BotCommand> {
+        self.commands.as_ref()?.iter().find(|command| command.data.offset == 0)
+    }
+
+    /// Returns arguments of a command at the beginning of the message text
+    ///
+    /// Arguments are separated by whitespace
+    /// Returns None when the message does not start with a command
+    pub fn get_command_args(&self) -> Option<Vec<&str>> {
+        let command = self.get_command()?;
+        let text = self.get_text()?;
+        text.data
+            .get(command.data.data.len()..)
+            .map(|args| args.split_whitespace().collect())
+    }
+
+    /// Returns a deep-linking parameter of a /start command
+    ///
+    /// When a user opens `https://t.me/<bot>?start=<parameter>`, the bot receives `/start <parameter>`
+    /// Returns None for other messages, for /start without a parameter
+    /// and when the parameter is not 1-64 characters of `A-Z`, `a-z`, `0-9`, `_` and `-`
+    pub fn get_start_param(&self) -> Option<&str> {
+        if self.get_command()?.command != "/start" {
+            return None;
+        }
+        match self.get_command_args()?.as_slice() {
+            [param] if param.len() <= MAX_START_PARAM_LENGTH && param.bytes().all(is_start_param_byte) => Some(param),
+            _ => None,
+        }
+    }
+
+    /// Returns the original message when the message is a reply
+    pub fn get_reply_to(&self) -> Option<&Message> {
+        self.reply_to.as_deref()
+    }
+
     fn from_raw(raw: RawMessage) -> Result<Message, ParseError> {
         macro_rules! required {
             ($name:ident) => {{
@@ -113,7 +154,7 @@ impl Message {
                     None => return Err(ParseError::MissingField(stringify!($name))),
                 }
             }};
-        };
+        }
 
         let forward_info = match (
             raw.forward_date,
@@ -202,7 +243,7 @@ impl Message {
                     commands: $commands,
                 });
             };
-        };
+        }
 
         message!(Animation(animation));
         message!(Audio(audio, caption));
@@ -210,6 +251,7 @@ impl Message {
         message!(ConnectedWebsite(connected_website));
         message!(Contact(contact));
         message!(DeleteChatPhoto(delete_chat_photo, flag));
+        message!(Dice(dice));
         message!(Document(document, caption));
         message!(Game(game));
         message!(GroupChatCreated(group_chat_created, flag));
@@ -223,6 +265,7 @@ impl Message {
         message!(NewChatTitle(new_chat_title));
         message!(PassportData(passport_data));
         message!(Photo(photo, caption));
+        message!(Poll(poll));
         message!(Sticker(sticker));
         message!(SuccessfulPayment(successful_payment));
         message!(SupergroupChatCreated(supergroup_chat_created, flag));
@@ -256,9 +299,61 @@ impl<'de> Deserialize<'de> for Message {
     }
 }
 
+/// Unique identifier of a message inside a chat
+///
+/// A separate type prevents passing a user or chat identifier where a message is expected
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize)]
+#[serde(transparent)]
+pub struct MessageId(pub Integer);
+
+impl<'de> Deserialize<'de> for MessageId {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        // copyMessage returns an object with a single message_id field instead of a plain integer
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Raw {
+            Plain(Integer),
+            Object { message_id: Integer },
+        }
+
+        Ok(MessageId(match Raw::deserialize(deserializer)? {
+            Raw::Plain(id) => id,
+            Raw::Object { message_id } => message_id,
+        }))
+    }
+}
+
+impl fmt::Display for MessageId {
+    fn fmt(&self, out: &mut fmt::Formatter) -> fmt::Result {
+        write!(out, "{}", self.0)
+    }
+}
+
+impl From<Integer> for MessageId {
+    fn from(id: Integer) -> MessageId {
+        MessageId(id)
+    }
+}
+
+impl From<MessageId> for Integer {
+    fn from(id: MessageId) -> Integer {
+        id.0
+    }
+}
+
+impl PartialEq<Integer> for MessageId {
+    fn eq(&self, other: &Integer) -> bool {
+        self.0 == *other
+    }
+}
+
 /// Result of editMessage* requests
 #[derive(Clone, Debug, Deserialize)]
 #[allow(clippy::large_enum_variant)]
+#[serde(untagged)]
 pub enum EditMessageResult {
     /// Returned if edited message is sent by the bot
     Message(Message),
@@ -278,6 +373,12 @@ enum ParseError {
     NoData,
 }
 
+const MAX_START_PARAM_LENGTH: usize = 64;
+
+fn is_start_param_byte(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || byte == b'_' || byte == b'-'
+}
+
 fn get_commands(text: &Text) -> Option<Vec<BotCommand>> {
     if let Text {
         entities: Some(ref entities),
@@ -3,12 +3,15 @@ use crate::types::{
     audio::Audio,
     chat::Chat,
     contact::Contact,
+    dice::Dice,
     document::Document,
     games::Game,
     location::Location,
+    message::MessageId,
     passport::PassportData,
     payments::{Invoice, SuccessfulPayment},
     photo_size::PhotoSize,
+    poll::Poll,
     primitive::Integer,
     stickers::Sticker,
     user::User,
@@ -21,13 +24,13 @@ use serde::Deserialize;
 
 #[derive(Debug, Deserialize)]
 pub(super) struct RawMessage {
-    pub message_id: Integer,
+    pub message_id: MessageId,
     pub from: Option<User>,
     pub date: Integer,
     pub chat: Chat,
     pub forward_from: Option<User>,
     pub forward_from_chat: Option<Chat>,
-    pub forward_from_message_id: Option<Integer>,
+    pub forward_from_message_id: Option<MessageId>,
     pub forward_signature: Option<String>,
     pub forward_date: Option<Integer>,
     pub reply_to_message: Option<Box<RawMessage>>,
@@ -48,8 +51,10 @@ pub(super) struct RawMessage {
     pub video_note: Option<VideoNote>,
     pub caption: Option<String>,
     pub contact: Option<Contact>,
+    pub dice: Option<Dice>,
     pub location: Option<Location>,
     pub venue: Option<Venue>,
+    pub poll: Option<Poll>,
     pub new_chat_members: Option<Vec<User>>,
     pub left_chat_member: Option<User>,
     pub new_chat_title: Option<String>,
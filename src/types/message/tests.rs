@@ -1,4 +1,8 @@
-use crate::types::{chat::ChannelChat, message::*, user::User};
+use crate::types::{
+    chat::ChannelChat,
+    message::*,
+    user::{User, UserId},
+};
 
 #[test]
 fn test_deserialize_message_channel() {
@@ -60,16 +64,16 @@ fn test_deserialize_message_group() {
     assert_eq!(msg.id, 1);
     assert_eq!(msg.date, 0);
     assert_eq!(msg.get_chat_id(), 1);
-    assert_eq!(msg.get_user().map(|u| u.id), Some(1));
+    assert_eq!(msg.get_user().map(|u| u.id), Some(UserId(1)));
     assert_eq!(msg.get_text().map(|t| t.data.as_str()), Some("test"));
     assert!(msg.is_edited());
     if let MessageKind::Group { chat, from } = msg.kind {
         assert_eq!(chat.id, 1);
         assert_eq!(chat.title, "grouptitle");
-        assert_eq!(chat.all_members_are_administrators, true);
+        assert!(chat.all_members_are_administrators);
         assert_eq!(from.id, 1);
         assert_eq!(from.first_name, "firstname");
-        assert_eq!(from.is_bot, false);
+        assert!(!from.is_bot);
     } else {
         panic!("Unexpected message kind: {:?}", msg.kind);
     }
@@ -109,14 +113,14 @@ fn test_deserialize_message_private() {
     assert_eq!(msg.id, 1);
     assert_eq!(msg.date, 0);
     assert_eq!(msg.get_chat_id(), 1);
-    assert_eq!(msg.get_user().map(|u| u.id), Some(1));
+    assert_eq!(msg.get_user().map(|u| u.id), Some(UserId(1)));
     assert_eq!(msg.get_text().map(|t| t.data.as_str()), Some("test"));
     if let MessageKind::Private { chat, from } = msg.kind {
         assert_eq!(chat.id, 1);
         assert_eq!(chat.first_name, "firstname");
         assert_eq!(from.id, 1);
         assert_eq!(from.first_name, "firstname");
-        assert_eq!(from.is_bot, false);
+        assert!(!from.is_bot);
     } else {
         panic!("Unexpected message kind: {:?}", msg.kind);
     }
@@ -158,14 +162,14 @@ fn test_deserialize_message_supergroup() {
     assert_eq!(msg.date, 0);
     assert_eq!(msg.get_chat_id(), 1);
     assert_eq!(msg.get_chat_username().unwrap(), "supergroupusername");
-    assert_eq!(msg.get_user().map(|u| u.id), Some(1));
+    assert_eq!(msg.get_user().map(|u| u.id), Some(UserId(1)));
     assert_eq!(msg.get_text().map(|t| t.data.as_str()), Some("test"));
     if let MessageKind::Supergroup { chat, from } = msg.kind {
         assert_eq!(chat.id, 1);
         assert_eq!(chat.title, "supergrouptitle");
         assert_eq!(from.id, 1);
         assert_eq!(from.first_name, "firstname");
-        assert_eq!(from.is_bot, false);
+        assert!(!from.is_bot);
     } else {
         panic!("Unexpected message kind: {:?}", msg.kind);
     }
@@ -204,7 +208,7 @@ fn test_deserialize_message_forward() {
         assert_eq!(date, 0);
         assert_eq!(user.id, 2);
         assert_eq!(user.first_name, String::from("firstname"));
-        assert_eq!(user.is_bot, false);
+        assert!(!user.is_bot);
     } else {
         panic!("Unexpected forward data: {:?}", msg.forward);
     }
@@ -302,6 +306,46 @@ fn test_deserialize_message_data() {
     }
 }
 
+#[test]
+fn test_deserialize_message_poll_and_dice() {
+    let input = r#"{
+        "message_id": 1, "date": 0,
+        "from": {"id": 1, "first_name": "firstname", "is_bot": false},
+        "chat": {"id": 1, "type": "private", "first_name": "firstname"},
+        "dice": {"emoji": "🎲", "value": 6}
+    }"#;
+    let msg: Message = serde_json::from_str(input).unwrap();
+    if let MessageData::Dice(dice) = msg.data {
+        assert_eq!(dice.emoji, "🎲");
+        assert_eq!(dice.value, 6);
+    } else {
+        panic!("Unexpected message data: {:?}", msg.data)
+    }
+
+    let input = r#"{
+        "message_id": 1, "date": 0,
+        "from": {"id": 1, "first_name": "firstname", "is_bot": false},
+        "chat": {"id": 1, "type": "private", "first_name": "firstname"},
+        "poll": {
+            "id": "poll-id",
+            "question": "Rust?",
+            "options": [{"text": "Yes", "voter_count": 1}],
+            "total_voter_count": 1,
+            "is_closed": false,
+            "is_anonymous": false,
+            "type": "regular",
+            "allows_multiple_answers": true
+        }
+    }"#;
+    let msg: Message = serde_json::from_str(input).unwrap();
+    if let MessageData::Poll(poll) = msg.data {
+        assert_eq!(poll.id, "poll-id");
+        assert!(poll.correct_option_id.is_none());
+    } else {
+        panic!("Unexpected message data: {:?}", msg.data)
+    }
+}
+
 #[test]
 fn test_deserialize_message_entities() {
     let input = r#"{
@@ -409,7 +453,7 @@ fn test_deserialize_message_entities() {
                         length: 11
                     },
                     user: User {
-                        id: 1,
+                        id: UserId(1),
                         is_bot: false,
                         first_name: String::from("test"),
                         last_name: None,
@@ -534,3 +578,145 @@ fn test_deserialize_message_bad_entities() {
         assert_eq!(err.to_string(), error.to_string());
     }
 }
+
+#[test]
+fn test_entity_text() {
+    let input = r#"{
+        "message_id": 1, "date": 0,
+        "from": {"id": 1, "first_name": "firstname", "is_bot": false},
+        "chat": {"id": 1, "type": "private", "first_name": "firstname"},
+        "text": "👍 привет @mention https://example.com",
+        "entities": [
+            {"type": "mention", "offset": 10, "length": 8},
+            {"type": "url", "offset": 19, "length": 19}
+        ]
+    }"#;
+    let msg: Message = serde_json::from_str(input).unwrap();
+    let text = msg.get_text().unwrap();
+    let entities: Vec<(&TextEntity, &str)> = text.entities_with_text().collect();
+    assert_eq!(entities.len(), 2);
+    assert!(matches!(entities[0].0, TextEntity::Mention(_)));
+    assert_eq!(entities[0].1, "@mention");
+    assert!(matches!(entities[1].0, TextEntity::Url(_)));
+    assert_eq!(entities[1].1, "https://example.com");
+    for (entity, data) in entities {
+        assert_eq!(text.entity_text(entity.data()), Some(data));
+    }
+
+    let data = |offset, length| TextEntityData {
+        offset,
+        length,
+        data: String::new(),
+    };
+    assert_eq!(text.entity_text(&data(0, 2)), Some("👍"));
+    assert_eq!(text.entity_text(&data(0, 1)), None);
+    assert_eq!(text.entity_text(&data(1, 1)), None);
+    assert_eq!(text.entity_text(&data(38, 0)), Some(""));
+    assert_eq!(text.entity_text(&data(38, 1)), None);
+}
+
+#[test]
+fn test_bot_command_is_addressed_to() {
+    let input = r#"{
+        "message_id": 1, "date": 0,
+        "from": {"id": 1, "first_name": "firstname", "is_bot": false},
+        "chat": {"id": 1, "type": "supergroup", "title": "supergrouptitle"},
+        "text": "/start /help@TestBot",
+        "entities": [
+            {"type": "bot_command", "offset": 0, "length": 6},
+            {"type": "bot_command", "offset": 7, "length": 13}
+        ]
+    }"#;
+    let msg: Message = serde_json::from_str(input).unwrap();
+    let commands = msg.commands.unwrap();
+    assert_eq!(commands.len(), 2);
+    assert!(commands[0].is_addressed_to("testbot"));
+    assert!(commands[0].is_addressed_to("otherbot"));
+    assert_eq!(commands[1].command, "/help");
+    assert!(commands[1].is_addressed_to("testbot"));
+    assert!(commands[1].is_addressed_to("@TestBot"));
+    assert!(!commands[1].is_addressed_to("otherbot"));
+}
+
+#[test]
+fn test_get_command() {
+    let input = r#"{
+        "message_id": 2, "date": 0,
+        "from": {"id": 1, "first_name": "firstname", "is_bot": false},
+        "chat": {"id": 1, "type": "private", "first_name": "firstname"},
+        "reply_to_message": {
+            "message_id": 1, "date": 0,
+            "from": {"id": 1, "first_name": "firstname", "is_bot": false},
+            "chat": {"id": 1, "type": "private", "first_name": "firstname"},
+            "text": "say /start to begin",
+            "entities": [{"type": "bot_command", "offset": 4, "length": 6}]
+        },
+        "text": "/start@TestBot  arg1 arg2",
+        "entities": [{"type": "bot_command", "offset": 0, "length": 14}]
+    }"#;
+    let msg: Message = serde_json::from_str(input).unwrap();
+    let command = msg.get_command().unwrap();
+    assert_eq!(command.command, "/start");
+    assert_eq!(command.bot_name, Some(String::from("TestBot")));
+    assert_eq!(msg.get_command_args(), Some(vec!["arg1", "arg2"]));
+
+    let reply_to = msg.get_reply_to().unwrap();
+    assert_eq!(reply_to.id, 1);
+    assert!(reply_to.commands.is_some());
+    assert!(reply_to.get_command().is_none());
+    assert!(reply_to.get_command_args().is_none());
+    assert!(reply_to.get_reply_to().is_none());
+}
+
+#[test]
+fn test_get_start_param() {
+    fn message(text: &str, command_length: usize) -> Message {
+        serde_json::from_value(serde_json::json!({
+            "message_id": 1, "date": 0,
+            "from": {"id": 1, "first_name": "firstname", "is_bot": false},
+            "chat": {"id": 1, "type": "private", "first_name": "firstname"},
+            "text": text,
+            "entities": [{"type": "bot_command", "offset": 0, "length": command_length}]
+        }))
+        .unwrap()
+    }
+
+    assert_eq!(message("/start ref_12345", 6).get_start_param(), Some("ref_12345"));
+    assert_eq!(message("/start@TestBot a-B", 14).get_start_param(), Some("a-B"));
+    assert_eq!(message("/start", 6).get_start_param(), None);
+    assert_eq!(message("/start a b", 6).get_start_param(), None);
+    assert_eq!(message("/start ref=1", 6).get_start_param(), None);
+    assert_eq!(
+        message(&format!("/start {}", "a".repeat(65)), 6).get_start_param(),
+        None
+    );
+    assert_eq!(
+        message(&format!("/start {}", "a".repeat(64)), 6)
+            .get_start_param()
+            .map(str::len),
+        Some(64)
+    );
+    assert_eq!(message("/help ref_12345", 5).get_start_param(), None);
+}
+
+#[test]
+fn test_deserialize_edit_message_result() {
+    let result: EditMessageResult = serde_json::from_str(
+        r#"{
+            "message_id": 1,
+            "date": 0,
+            "from": {"id": 1, "first_name": "test", "is_bot": false},
+            "chat": {"id": 1, "type": "private", "first_name": "test"},
+            "text": "edited"
+        }"#,
+    )
+    .unwrap();
+    if let EditMessageResult::Message(msg) = result {
+        assert_eq!(msg.id, 1);
+    } else {
+        panic!("Unexpected result: {:?}", result);
+    }
+
+    let result: EditMessageResult = serde_json::from_str("true").unwrap();
+    assert!(matches!(result, EditMessageResult::Bool(true)));
+}
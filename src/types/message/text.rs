@@ -50,10 +50,49 @@ impl Text {
         };
         Ok(Text { data, entities })
     }
+
+    /// Returns a slice of the text covered by an entity
+    ///
+    /// Parsed entities already contain a copy of this part in TextEntityData::data,
+    /// use this method to borrow it from the text or to check an entity created manually
+    /// Offset and length of an entity are measured in UTF-16 code units,
+    /// they are converted to byte positions before slicing the text
+    /// Returns None when the entity is out of text bounds or splits a character
+    pub fn entity_text(&self, entity: &TextEntityData) -> Option<&str> {
+        let start = utf16_to_byte_index(&self.data, entity.offset)?;
+        let end = start + utf16_to_byte_index(&self.data[start..], entity.length)?;
+        Some(&self.data[start..end])
+    }
+
+    /// Returns an iterator over entities along with parts of the text covered by them
+    pub fn entities_with_text(&self) -> impl Iterator<Item = (&TextEntity, &str)> {
+        self.entities
+            .iter()
+            .flatten()
+            .map(|entity| (entity, entity.data().data.as_str()))
+    }
+}
+
+fn utf16_to_byte_index(text: &str, utf16_index: usize) -> Option<usize> {
+    let mut utf16_len = 0;
+    for (byte_index, c) in text.char_indices() {
+        if utf16_len == utf16_index {
+            return Some(byte_index);
+        }
+        utf16_len += c.len_utf16();
+    }
+    if utf16_len == utf16_index {
+        Some(text.len())
+    } else {
+        None
+    }
 }
 
 /// Respresents an entity in a text
+///
+/// List of entities may be extended, use a wildcard arm in matches
 #[derive(Clone, Debug, PartialEq, PartialOrd)]
+#[non_exhaustive]
 pub enum TextEntity {
     /// Bold text
     Bold(TextEntityData),
@@ -84,6 +123,25 @@ pub enum TextEntity {
 }
 
 impl TextEntity {
+    /// Returns position and contents of the entity
+    pub fn data(&self) -> &TextEntityData {
+        match self {
+            TextEntity::Bold(data)
+            | TextEntity::Cashtag(data)
+            | TextEntity::Code(data)
+            | TextEntity::Email(data)
+            | TextEntity::Hashtag(data)
+            | TextEntity::Italic(data)
+            | TextEntity::Mention(data)
+            | TextEntity::PhoneNumber(data)
+            | TextEntity::Pre(data)
+            | TextEntity::Url(data) => data,
+            TextEntity::BotCommand(command) => &command.data,
+            TextEntity::TextLink(link) => &link.data,
+            TextEntity::TextMention(mention) => &mention.data,
+        }
+    }
+
     fn from_raw(entity: RawMessageEntity, data: TextEntityData) -> Result<TextEntity, ParseTextError> {
         Ok(match entity.kind {
             RawMessageEntityKind::Bold => TextEntity::Bold(data),
@@ -129,6 +187,19 @@ pub struct BotCommand {
     pub data: TextEntityData,
 }
 
+impl BotCommand {
+    /// Returns true if command is addressed to a bot with given username
+    ///
+    /// Commands without a bot username (e.g. /start instead of /start@botname)
+    /// are addressed to any bot
+    pub fn is_addressed_to(&self, username: &str) -> bool {
+        match self.bot_name {
+            Some(ref bot_name) => bot_name.eq_ignore_ascii_case(username.trim_start_matches('@')),
+            None => true,
+        }
+    }
+}
+
 /// Clickable text URLs
 #[derive(Clone, Debug, PartialEq, PartialOrd)]
 pub struct TextLink {
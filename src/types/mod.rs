@@ -3,16 +3,19 @@ mod audio;
 mod callback_query;
 mod chat;
 mod contact;
+mod dice;
 mod document;
 mod file;
 mod games;
 mod inline_mode;
+mod input_file;
 mod input_media;
 mod location;
 mod message;
 mod passport;
 mod payments;
 mod photo_size;
+mod poll;
 mod primitive;
 mod reply_markup;
 mod response;
@@ -25,7 +28,8 @@ mod video_note;
 mod voice;
 
 pub use self::{
-    animation::*, audio::*, callback_query::*, chat::*, contact::*, document::*, file::*, games::*, inline_mode::*,
-    input_media::*, location::*, message::*, passport::*, payments::*, photo_size::*, primitive::*, reply_markup::*,
-    response::*, stickers::*, update::*, user::*, venue::*, video::*, video_note::*, voice::*,
+    animation::*, audio::*, callback_query::*, chat::*, contact::*, dice::*, document::*, file::*, games::*,
+    inline_mode::*, input_file::*, input_media::*, location::*, message::*, passport::*, payments::*, photo_size::*,
+    poll::*, primitive::*, reply_markup::*, response::*, stickers::*, update::*, user::*, venue::*, video::*,
+    video_note::*, voice::*,
 };
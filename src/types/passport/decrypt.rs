@@ -0,0 +1,309 @@
+use crate::types::passport::{EncryptedCredentials, PassportData};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use openssl::{
+    error::ErrorStack,
+    hash::{hash, MessageDigest},
+    pkey::Private,
+    rsa::{Padding, Rsa},
+    symm::{Cipher, Crypter, Mode},
+};
+use serde::{de::DeserializeOwned, Deserialize};
+
+/// Private RSA key of the bot used to decrypt Telegram Passport credentials
+#[derive(Clone, Debug)]
+pub struct PassportKey(Rsa<Private>);
+
+impl PassportKey {
+    /// Loads a key from PEM-encoded PKCS#1 or PKCS#8 data
+    pub fn from_pem(pem: &[u8]) -> Result<Self, PassportDecryptError> {
+        Ok(PassportKey(Rsa::private_key_from_pem(pem)?))
+    }
+
+    /// Loads a key from DER-encoded PKCS#1 data
+    pub fn from_der(der: &[u8]) -> Result<Self, PassportDecryptError> {
+        Ok(PassportKey(Rsa::private_key_from_der(der)?))
+    }
+
+    fn decrypt_secret(&self, secret: &[u8]) -> Result<Vec<u8>, PassportDecryptError> {
+        let mut buf = vec![0; self.0.size() as usize];
+        let len = self.0.private_decrypt(secret, &mut buf, Padding::PKCS1_OAEP)?;
+        buf.truncate(len);
+        Ok(buf)
+    }
+}
+
+impl PassportData {
+    /// Decrypts credentials required to decrypt the data
+    ///
+    /// See [`EncryptedCredentials::decrypt`] for details
+    pub fn decrypt(&self, key: &PassportKey) -> Result<Credentials, PassportDecryptError> {
+        self.credentials.decrypt(key)
+    }
+}
+
+impl EncryptedCredentials {
+    /// Decrypts credentials using the bot's private key
+    ///
+    /// Returns [`PassportDecryptError::HashMismatch`] when decrypted data does not match the hash
+    pub fn decrypt(&self, key: &PassportKey) -> Result<Credentials, PassportDecryptError> {
+        let secret = key.decrypt_secret(&decode_base64(&self.secret)?)?;
+        let hash = decode_base64(&self.hash)?;
+        let data = decode_base64(&self.data)?;
+        let data = decrypt_data(&data, &secret, &hash)?;
+        Ok(serde_json::from_slice(&data)?)
+    }
+}
+
+/// Decrypted credentials
+#[derive(Clone, Debug, Deserialize)]
+pub struct Credentials {
+    /// Credentials for encrypted data
+    pub secure_data: SecureData,
+    /// Bot-specified nonce
+    pub nonce: String,
+}
+
+/// Credentials for each kind of Telegram Passport element
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct SecureData {
+    /// Credentials for encrypted personal details
+    pub personal_details: Option<SecureValue>,
+    /// Credentials for encrypted passport
+    pub passport: Option<SecureValue>,
+    /// Credentials for encrypted internal passport
+    pub internal_passport: Option<SecureValue>,
+    /// Credentials for encrypted driver license
+    pub driver_license: Option<SecureValue>,
+    /// Credentials for encrypted ID card
+    pub identity_card: Option<SecureValue>,
+    /// Credentials for encrypted residential address
+    pub address: Option<SecureValue>,
+    /// Credentials for encrypted utility bill
+    pub utility_bill: Option<SecureValue>,
+    /// Credentials for encrypted bank statement
+    pub bank_statement: Option<SecureValue>,
+    /// Credentials for encrypted rental agreement
+    pub rental_agreement: Option<SecureValue>,
+    /// Credentials for encrypted registration from internal passport
+    pub passport_registration: Option<SecureValue>,
+    /// Credentials for encrypted temporary registration
+    pub temporary_registration: Option<SecureValue>,
+}
+
+/// Credentials required to decrypt a single Telegram Passport element
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct SecureValue {
+    /// Credentials for encrypted element data
+    pub data: Option<DataCredentials>,
+    /// Credentials for an encrypted document's front side
+    pub front_side: Option<FileCredentials>,
+    /// Credentials for an encrypted document's reverse side
+    pub reverse_side: Option<FileCredentials>,
+    /// Credentials for an encrypted selfie of the user with a document
+    pub selfie: Option<FileCredentials>,
+    /// Credentials for an encrypted translation of the document
+    pub translation: Option<Vec<FileCredentials>>,
+    /// Credentials for encrypted files
+    pub files: Option<Vec<FileCredentials>>,
+}
+
+/// Credentials required to decrypt the data field of an element
+#[derive(Clone, Debug, Deserialize)]
+pub struct DataCredentials {
+    /// Checksum of encrypted data
+    pub data_hash: String,
+    /// Secret of encrypted data
+    pub secret: String,
+}
+
+impl DataCredentials {
+    /// Decrypts base64-encoded data field of an element
+    pub fn decrypt(&self, data: &str) -> Result<Vec<u8>, PassportDecryptError> {
+        decrypt_data(
+            &decode_base64(data)?,
+            &decode_base64(&self.secret)?,
+            &decode_base64(&self.data_hash)?,
+        )
+    }
+
+    /// Decrypts data field of an element and parses it as JSON
+    pub fn decrypt_json<T: DeserializeOwned>(&self, data: &str) -> Result<T, PassportDecryptError> {
+        Ok(serde_json::from_slice(&self.decrypt(data)?)?)
+    }
+}
+
+/// Credentials required to decrypt a file
+#[derive(Clone, Debug, Deserialize)]
+pub struct FileCredentials {
+    /// Checksum of encrypted file
+    pub file_hash: String,
+    /// Secret of encrypted file
+    pub secret: String,
+}
+
+impl FileCredentials {
+    /// Decrypts contents of a downloaded file
+    pub fn decrypt(&self, data: &[u8]) -> Result<Vec<u8>, PassportDecryptError> {
+        decrypt_data(data, &decode_base64(&self.secret)?, &decode_base64(&self.file_hash)?)
+    }
+}
+
+/// An error when decrypting Telegram Passport data
+#[derive(Debug, failure::Fail)]
+pub enum PassportDecryptError {
+    /// Value is not a valid base64 string
+    #[fail(display = "Failed to decode base64: {}", _0)]
+    Base64(#[cause] base64::DecodeError),
+    /// Failed to load a key or to decrypt data
+    #[fail(display = "Failed to decrypt data: {}", _0)]
+    Crypto(#[cause] ErrorStack),
+    /// Hash of decrypted data does not match the expected hash
+    #[fail(display = "Data hash mismatch")]
+    HashMismatch,
+    /// Decrypted data has an invalid padding
+    #[fail(display = "Invalid data padding")]
+    InvalidPadding,
+    /// Failed to parse decrypted data
+    #[fail(display = "Failed to parse decrypted data: {}", _0)]
+    Json(#[cause] serde_json::Error),
+}
+
+impl From<base64::DecodeError> for PassportDecryptError {
+    fn from(err: base64::DecodeError) -> Self {
+        PassportDecryptError::Base64(err)
+    }
+}
+
+impl From<ErrorStack> for PassportDecryptError {
+    fn from(err: ErrorStack) -> Self {
+        PassportDecryptError::Crypto(err)
+    }
+}
+
+impl From<serde_json::Error> for PassportDecryptError {
+    fn from(err: serde_json::Error) -> Self {
+        PassportDecryptError::Json(err)
+    }
+}
+
+fn decode_base64(value: &str) -> Result<Vec<u8>, PassportDecryptError> {
+    Ok(BASE64.decode(value)?)
+}
+
+fn decrypt_data(data: &[u8], secret: &[u8], data_hash: &[u8]) -> Result<Vec<u8>, PassportDecryptError> {
+    let cipher = Cipher::aes_256_cbc();
+    if data.is_empty() || !data.len().is_multiple_of(cipher.block_size()) {
+        return Err(PassportDecryptError::InvalidPadding);
+    }
+    let (key, iv) = derive_key(secret, data_hash)?;
+    let mut crypter = Crypter::new(cipher, Mode::Decrypt, &key, Some(&iv))?;
+    crypter.pad(false);
+    let mut buf = vec![0; data.len() + cipher.block_size()];
+    let mut len = crypter.update(data, &mut buf)?;
+    len += crypter.finalize(&mut buf[len..])?;
+    buf.truncate(len);
+    if hash(MessageDigest::sha256(), &buf)?.as_ref() != data_hash {
+        return Err(PassportDecryptError::HashMismatch);
+    }
+    let padding = buf[0] as usize;
+    if padding < 32 || padding > buf.len() {
+        return Err(PassportDecryptError::InvalidPadding);
+    }
+    Ok(buf.split_off(padding))
+}
+
+fn derive_key(secret: &[u8], data_hash: &[u8]) -> Result<(Vec<u8>, Vec<u8>), PassportDecryptError> {
+    let secret_hash = hash(MessageDigest::sha512(), &[secret, data_hash].concat())?;
+    Ok((secret_hash[..32].to_vec(), secret_hash[32..48].to_vec()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use openssl::rand::rand_bytes;
+
+    fn encrypt_data(data: &[u8]) -> (Vec<u8>, Vec<u8>, Vec<u8>) {
+        let mut padding_len = 32;
+        while !(data.len() + padding_len).is_multiple_of(16) {
+            padding_len += 1;
+        }
+        let mut padded = vec![0; padding_len];
+        rand_bytes(&mut padded).unwrap();
+        padded[0] = padding_len as u8;
+        padded.extend_from_slice(data);
+        let data_hash = hash(MessageDigest::sha256(), &padded).unwrap().to_vec();
+        let mut secret = vec![0; 32];
+        rand_bytes(&mut secret).unwrap();
+        let (key, iv) = derive_key(&secret, &data_hash).unwrap();
+        let cipher = Cipher::aes_256_cbc();
+        let mut crypter = Crypter::new(cipher, Mode::Encrypt, &key, Some(&iv)).unwrap();
+        crypter.pad(false);
+        let mut buf = vec![0; padded.len() + cipher.block_size()];
+        let mut len = crypter.update(&padded, &mut buf).unwrap();
+        len += crypter.finalize(&mut buf[len..]).unwrap();
+        buf.truncate(len);
+        (buf, secret, data_hash)
+    }
+
+    #[test]
+    fn test_decrypt_credentials() {
+        let rsa = Rsa::generate(2048).unwrap();
+        let key = PassportKey::from_pem(&rsa.private_key_to_pem().unwrap()).unwrap();
+
+        let (file_data, file_secret, file_hash) = encrypt_data(b"file-data");
+        let (element_data, element_secret, element_hash) = encrypt_data(br#"{"first_name":"John"}"#);
+        let credentials = serde_json::json!({
+            "secure_data": {
+                "personal_details": {
+                    "data": {
+                        "data_hash": BASE64.encode(&element_hash),
+                        "secret": BASE64.encode(&element_secret)
+                    }
+                },
+                "passport": {
+                    "front_side": {
+                        "file_hash": BASE64.encode(&file_hash),
+                        "secret": BASE64.encode(&file_secret)
+                    }
+                }
+            },
+            "nonce": "nonce"
+        });
+        let (data, secret, data_hash) = encrypt_data(credentials.to_string().as_bytes());
+        let mut encrypted_secret = vec![0; rsa.size() as usize];
+        let len = rsa
+            .public_encrypt(&secret, &mut encrypted_secret, Padding::PKCS1_OAEP)
+            .unwrap();
+        encrypted_secret.truncate(len);
+        let mut encrypted = EncryptedCredentials {
+            data: BASE64.encode(&data),
+            hash: BASE64.encode(&data_hash),
+            secret: BASE64.encode(&encrypted_secret),
+        };
+
+        let credentials = encrypted.decrypt(&key).unwrap();
+        assert_eq!(credentials.nonce, "nonce");
+        let personal_details = credentials.secure_data.personal_details.unwrap();
+        let value: serde_json::Value = personal_details
+            .data
+            .unwrap()
+            .decrypt_json(&BASE64.encode(&element_data))
+            .unwrap();
+        assert_eq!(value["first_name"], "John");
+        let front_side = credentials.secure_data.passport.unwrap().front_side.unwrap();
+        assert_eq!(front_side.decrypt(&file_data).unwrap(), b"file-data");
+
+        let mut file_data = file_data;
+        file_data[0] ^= 1;
+        assert!(matches!(
+            front_side.decrypt(&file_data),
+            Err(PassportDecryptError::HashMismatch)
+        ));
+
+        encrypted.hash = BASE64.encode(&file_hash);
+        assert!(matches!(
+            encrypted.decrypt(&key),
+            Err(PassportDecryptError::HashMismatch)
+        ));
+    }
+}
@@ -2,7 +2,10 @@ use crate::types::passport::PassportFile;
 use serde::{de::Error, Deserialize, Deserializer, Serialize};
 
 /// Information about documents or other Telegram Passport elements shared with the bot by the user
+///
+/// New types of elements may be supported later, use a wildcard arm in matches
 #[derive(Clone, Debug)]
+#[non_exhaustive]
 pub enum EncryptedPassportElement {
     /// Address
     Address(EncryptedPassportElementAddress),
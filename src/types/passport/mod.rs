@@ -1,6 +1,8 @@
 use crate::types::primitive::Integer;
 use serde::Deserialize;
 
+#[cfg(feature = "passport")]
+mod decrypt;
 mod element;
 mod error;
 #[cfg(test)]
@@ -8,6 +10,9 @@ mod tests;
 
 pub use self::{element::*, error::*};
 
+#[cfg(feature = "passport")]
+pub use self::decrypt::*;
+
 /// Telegram Passport data shared with the bot by the user
 #[derive(Clone, Debug, Deserialize)]
 pub struct PassportData {
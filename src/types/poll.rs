@@ -0,0 +1,160 @@
+use crate::types::{
+    message::{RawMessageEntity, Text},
+    primitive::Integer,
+    user::User,
+};
+use serde::{de::Error, Deserialize, Deserializer, Serialize};
+
+/// Information about a poll
+#[derive(Clone, Debug)]
+pub struct Poll {
+    /// Unique poll identifier
+    pub id: String,
+    /// Poll question, 1-300 characters
+    pub question: String,
+    /// List of poll options
+    pub options: Vec<PollOption>,
+    /// Total number of users that voted in the poll
+    pub total_voter_count: Integer,
+    /// True, if the poll is closed
+    pub is_closed: bool,
+    /// True, if the poll is anonymous
+    pub is_anonymous: bool,
+    /// Poll type
+    pub kind: PollKind,
+    /// True, if the poll allows multiple answers
+    pub allows_multiple_answers: bool,
+    /// 0-based identifier of the correct answer option
+    ///
+    /// Available only for polls in the quiz mode,
+    /// which are closed, or was sent (not forwarded) by the bot or to the private chat with the bot
+    pub correct_option_id: Option<Integer>,
+    /// Text that is shown when a user chooses an incorrect answer or taps on the lamp icon in a quiz
+    pub explanation: Option<Text>,
+}
+
+impl<'de> Deserialize<'de> for Poll {
+    fn deserialize<D>(deserializer: D) -> Result<Poll, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let raw_poll: RawPoll = Deserialize::deserialize(deserializer)?;
+        Ok(Poll {
+            id: raw_poll.id,
+            question: raw_poll.question,
+            options: raw_poll.options,
+            total_voter_count: raw_poll.total_voter_count,
+            is_closed: raw_poll.is_closed,
+            is_anonymous: raw_poll.is_anonymous,
+            kind: raw_poll.kind,
+            allows_multiple_answers: raw_poll.allows_multiple_answers,
+            correct_option_id: raw_poll.correct_option_id,
+            explanation: match raw_poll.explanation {
+                Some(data) => Some(Text::parse(data, raw_poll.explanation_entities).map_err(D::Error::custom)?),
+                None => None,
+            },
+        })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct RawPoll {
+    id: String,
+    question: String,
+    options: Vec<PollOption>,
+    total_voter_count: Integer,
+    is_closed: bool,
+    is_anonymous: bool,
+    #[serde(rename = "type")]
+    kind: PollKind,
+    allows_multiple_answers: bool,
+    correct_option_id: Option<Integer>,
+    explanation: Option<String>,
+    explanation_entities: Option<Vec<RawMessageEntity>>,
+}
+
+/// Type of a poll
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub enum PollKind {
+    /// A poll with a single correct answer
+    #[serde(rename = "quiz")]
+    Quiz,
+    /// A regular poll
+    #[serde(rename = "regular")]
+    Regular,
+}
+
+/// Information about one answer option in a poll
+#[derive(Clone, Debug, Deserialize)]
+pub struct PollOption {
+    /// Option text, 1-100 characters
+    pub text: String,
+    /// Number of users that voted for this option
+    pub voter_count: Integer,
+}
+
+/// An answer of a user in a non-anonymous poll
+#[derive(Clone, Debug, Deserialize)]
+pub struct PollAnswer {
+    /// Unique poll identifier
+    pub poll_id: String,
+    /// The user, who changed the answer to the poll
+    pub user: User,
+    /// 0-based identifiers of answer options, chosen by the user
+    ///
+    /// May be empty if the user retracted their vote
+    pub option_ids: Vec<Integer>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deserialize_poll() {
+        let input = r#"{
+            "id": "poll-id",
+            "question": "Rust?",
+            "options": [
+                {"text": "Yes", "voter_count": 2},
+                {"text": "No", "voter_count": 0}
+            ],
+            "total_voter_count": 2,
+            "is_closed": false,
+            "is_anonymous": true,
+            "type": "quiz",
+            "allows_multiple_answers": false,
+            "correct_option_id": 0,
+            "explanation": "Rust is great",
+            "explanation_entities": [{"type": "bold", "offset": 0, "length": 4}]
+        }"#;
+        let poll: Poll = serde_json::from_str(input).unwrap();
+        assert_eq!(poll.id, "poll-id");
+        assert_eq!(poll.question, "Rust?");
+        assert_eq!(poll.options.len(), 2);
+        assert_eq!(poll.options[0].text, "Yes");
+        assert_eq!(poll.options[0].voter_count, 2);
+        assert_eq!(poll.total_voter_count, 2);
+        assert!(!poll.is_closed);
+        assert!(poll.is_anonymous);
+        assert_eq!(poll.kind, PollKind::Quiz);
+        assert!(!poll.allows_multiple_answers);
+        assert_eq!(poll.correct_option_id, Some(0));
+        let explanation = poll.explanation.unwrap();
+        assert_eq!(explanation.data, "Rust is great");
+        assert_eq!(explanation.entities.unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_deserialize_poll_answer() {
+        let input = r#"{
+            "poll_id": "poll-id",
+            "user": {"id": 1, "is_bot": false, "first_name": "user"},
+            "option_ids": [0, 2]
+        }"#;
+        let answer: PollAnswer = serde_json::from_str(input).unwrap();
+        assert_eq!(answer.poll_id, "poll-id");
+        assert_eq!(answer.user.id, 1);
+        assert_eq!(answer.option_ids, vec![0, 2]);
+    }
+}
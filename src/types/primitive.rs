@@ -6,7 +6,7 @@ pub type Integer = i64;
 /// Telegram Float type
 pub type Float = f32;
 
-/// Send Markdown or HTML,
+/// Send Markdown, MarkdownV2 or HTML,
 /// if you want Telegram apps to show
 /// bold, italic, fixed-width text or
 /// inline URLs in the media caption.
@@ -15,6 +15,108 @@ pub enum ParseMode {
     /// HTML
     #[serde(rename = "HTML")]
     Html,
-    /// Markdown
+    /// Legacy Markdown
+    ///
+    /// Use MarkdownV2 instead
     Markdown,
+    /// MarkdownV2
+    MarkdownV2,
+}
+
+impl ParseMode {
+    /// Escapes reserved characters of the parse mode
+    ///
+    /// Use it to insert untrusted text (e.g. a username) into a formatted message
+    pub fn escape<S: AsRef<str>>(self, input: S) -> String {
+        match self {
+            ParseMode::Html => escape_html(input),
+            ParseMode::Markdown => escape_markdown(input),
+            ParseMode::MarkdownV2 => escape_markdown_v2(input),
+        }
+    }
+}
+
+/// Escapes reserved characters for HTML parse mode
+///
+/// "<", ">" and "&" are replaced by corresponding HTML entities, as well as '"' to use in attributes
+pub fn escape_html<S: AsRef<str>>(input: S) -> String {
+    let input = input.as_ref();
+    let mut result = String::with_capacity(input.len());
+    for c in input.chars() {
+        match c {
+            '<' => result.push_str("&lt;"),
+            '>' => result.push_str("&gt;"),
+            '&' => result.push_str("&amp;"),
+            '"' => result.push_str("&quot;"),
+            c => result.push(c),
+        }
+    }
+    result
+}
+
+/// Escapes reserved characters for legacy Markdown parse mode
+///
+/// "_", "*", "`" and "[" are prefixed with a backslash
+/// Note that entities can not be nested, so the result can be used outside of entities only
+pub fn escape_markdown<S: AsRef<str>>(input: S) -> String {
+    escape_with_backslash(input.as_ref(), &['_', '*', '`', '['])
+}
+
+/// Escapes reserved characters for MarkdownV2 parse mode
+///
+/// All reserved characters and a backslash itself are prefixed with a backslash
+pub fn escape_markdown_v2<S: AsRef<str>>(input: S) -> String {
+    escape_with_backslash(input.as_ref(), MARKDOWN_V2_RESERVED)
+}
+
+const MARKDOWN_V2_RESERVED: &[char] = &[
+    '\\', '_', '*', '[', ']', '(', ')', '~', '`', '>', '#', '+', '-', '=', '|', '{', '}', '.', '!',
+];
+
+fn escape_with_backslash(input: &str, reserved: &[char]) -> String {
+    let mut result = String::with_capacity(input.len());
+    for c in input.chars() {
+        if reserved.contains(&c) {
+            result.push('\\');
+        }
+        result.push(c);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escape_html() {
+        assert_eq!(
+            escape_html("<b>Tom & \"Jerry\"</b>"),
+            "&lt;b&gt;Tom &amp; &quot;Jerry&quot;&lt;/b&gt;"
+        );
+        assert_eq!(escape_html("plain text"), "plain text");
+        assert_eq!(ParseMode::Html.escape("a<b"), "a&lt;b");
+    }
+
+    #[test]
+    fn test_escape_markdown() {
+        assert_eq!(
+            escape_markdown("user_name *bold* `code` [link]"),
+            "user\\_name \\*bold\\* \\`code\\` \\[link]"
+        );
+        assert_eq!(ParseMode::Markdown.escape("a_b"), "a\\_b");
+    }
+
+    #[test]
+    fn test_escape_markdown_v2() {
+        let reserved = "\\_*[]()~`>#+-=|{}.!";
+        let escaped = escape_markdown_v2(reserved);
+        assert_eq!(escaped.len(), reserved.len() * 2);
+        for (idx, c) in reserved.chars().enumerate() {
+            assert_eq!(&escaped[idx * 2..idx * 2 + 2], format!("\\{}", c));
+        }
+        assert_eq!(escape_markdown_v2("John_Doe. Hi!"), "John\\_Doe\\. Hi\\!");
+        assert_eq!(escape_markdown_v2("привет"), "привет");
+        assert_eq!(ParseMode::MarkdownV2.escape("1+1=2"), "1\\+1\\=2");
+    }
 }
@@ -1,3 +1,4 @@
+use crate::types::reply_markup::web_app::WebAppInfo;
 use serde::Serialize;
 
 /// Inline keyboard that appears right next to the message it belongs to
@@ -13,8 +14,10 @@ impl InlineKeyboardMarkup {
     }
 
     /// Adds a row to keyboard
-    pub fn row(mut self, row: Vec<InlineKeyboardButton>) -> Self {
-        self.inline_keyboard.push(row);
+    ///
+    /// Accepts a Vec or an array of buttons
+    pub fn row<R: IntoIterator<Item = InlineKeyboardButton>>(mut self, row: R) -> Self {
+        self.inline_keyboard.push(row.into_iter().collect());
         self
     }
 }
@@ -43,6 +46,10 @@ pub struct InlineKeyboardButton {
     callback_game: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pay: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    login_url: Option<LoginUrl>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    web_app: Option<WebAppInfo>,
 }
 
 impl InlineKeyboardButton {
@@ -56,32 +63,24 @@ impl InlineKeyboardButton {
             switch_inline_query_current_chat: None,
             callback_game: None,
             pay: None,
+            login_url: None,
+            web_app: None,
         }
     }
 
     /// HTTP or tg:// url to be opened when button is pressed
     pub fn with_url<S: Into<String>>(text: S, url: S) -> Self {
         InlineKeyboardButton {
-            text: text.into(),
             url: Some(url.into()),
-            callback_data: None,
-            switch_inline_query: None,
-            switch_inline_query_current_chat: None,
-            callback_game: None,
-            pay: None,
+            ..InlineKeyboardButton::new(text)
         }
     }
 
     /// Data to be sent in a callback query to the bot when button is pressed, 1-64 bytes
     pub fn with_callback_data<S: Into<String>>(text: S, callback_data: S) -> Self {
         InlineKeyboardButton {
-            text: text.into(),
-            url: None,
             callback_data: Some(callback_data.into()),
-            switch_inline_query: None,
-            switch_inline_query_current_chat: None,
-            callback_game: None,
-            pay: None,
+            ..InlineKeyboardButton::new(text)
         }
     }
 
@@ -99,13 +98,8 @@ impl InlineKeyboardButton {
     /// skipping the chat selection screen
     pub fn with_switch_inline_query<S: Into<String>>(text: S, switch_inline_query: S) -> Self {
         InlineKeyboardButton {
-            text: text.into(),
-            url: None,
-            callback_data: None,
             switch_inline_query: Some(switch_inline_query.into()),
-            switch_inline_query_current_chat: None,
-            callback_game: None,
-            pay: None,
+            ..InlineKeyboardButton::new(text)
         }
     }
 
@@ -120,13 +114,8 @@ impl InlineKeyboardButton {
         switch_inline_query_current_chat: S,
     ) -> Self {
         InlineKeyboardButton {
-            text: text.into(),
-            url: None,
-            callback_data: None,
-            switch_inline_query: None,
             switch_inline_query_current_chat: Some(switch_inline_query_current_chat.into()),
-            callback_game: None,
-            pay: None,
+            ..InlineKeyboardButton::new(text)
         }
     }
 
@@ -135,13 +124,8 @@ impl InlineKeyboardButton {
     /// NOTE: This type of button must always be the first button in the first row
     pub fn with_callback_game<S: Into<String>>(text: S) -> Self {
         InlineKeyboardButton {
-            text: text.into(),
-            url: None,
-            callback_data: None,
-            switch_inline_query: None,
-            switch_inline_query_current_chat: None,
             callback_game: Some(String::new()),
-            pay: None,
+            ..InlineKeyboardButton::new(text)
         }
     }
 
@@ -150,13 +134,77 @@ impl InlineKeyboardButton {
     /// NOTE: This type of button must always be the first button in the first row
     pub fn with_pay<S: Into<String>>(text: S) -> Self {
         InlineKeyboardButton {
-            text: text.into(),
-            url: None,
-            callback_data: None,
-            switch_inline_query: None,
-            switch_inline_query_current_chat: None,
-            callback_game: None,
             pay: Some(true),
+            ..InlineKeyboardButton::new(text)
+        }
+    }
+
+    /// An HTTP URL used to automatically authorize the user
+    ///
+    /// Can be used as a replacement for the Telegram Login Widget
+    pub fn with_login_url<S: Into<String>>(text: S, login_url: LoginUrl) -> Self {
+        InlineKeyboardButton {
+            login_url: Some(login_url),
+            ..InlineKeyboardButton::new(text)
+        }
+    }
+
+    /// Description of the Web App that will be launched when the user presses the button
+    ///
+    /// Available only in private chats between a user and the bot
+    pub fn with_web_app<S: Into<String>>(text: S, web_app: WebAppInfo) -> Self {
+        InlineKeyboardButton {
+            web_app: Some(web_app),
+            ..InlineKeyboardButton::new(text)
         }
     }
 }
+
+/// A parameter of the inline keyboard button used to automatically authorize a user
+#[derive(Clone, Debug, Serialize)]
+pub struct LoginUrl {
+    url: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    forward_text: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    bot_username: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    request_write_access: Option<bool>,
+}
+
+impl LoginUrl {
+    /// Creates a new LoginUrl
+    ///
+    /// # Arguments
+    ///
+    /// * url - An HTTP URL to be opened with user authorization data added to the query string
+    ///         when the button is pressed
+    pub fn new<S: Into<String>>(url: S) -> Self {
+        LoginUrl {
+            url: url.into(),
+            forward_text: None,
+            bot_username: None,
+            request_write_access: None,
+        }
+    }
+
+    /// New text of the button in forwarded messages
+    pub fn forward_text<S: Into<String>>(mut self, forward_text: S) -> Self {
+        self.forward_text = Some(forward_text.into());
+        self
+    }
+
+    /// Username of a bot, which will be used for user authorization
+    ///
+    /// Defaults to the current bot
+    pub fn bot_username<S: Into<String>>(mut self, bot_username: S) -> Self {
+        self.bot_username = Some(bot_username.into());
+        self
+    }
+
+    /// Pass True to request the permission for your bot to send messages to the user
+    pub fn request_write_access(mut self, request_write_access: bool) -> Self {
+        self.request_write_access = Some(request_write_access);
+        self
+    }
+}
@@ -5,8 +5,9 @@ mod inline_keyboard;
 mod reply_keyboard;
 #[cfg(test)]
 mod tests;
+mod web_app;
 
-pub use self::{force_reply::*, inline_keyboard::*, reply_keyboard::*};
+pub use self::{force_reply::*, inline_keyboard::*, reply_keyboard::*, web_app::*};
 
 /// Reply markup
 #[derive(Clone, Debug, derive_more::From, Serialize)]
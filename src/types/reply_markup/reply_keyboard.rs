@@ -62,8 +62,10 @@ impl ReplyKeyboardMarkup {
     }
 
     /// Adds a row to keyboard
-    pub fn row(mut self, row: Vec<KeyboardButton>) -> Self {
-        self.keyboard.push(row);
+    ///
+    /// Accepts a Vec or an array of buttons
+    pub fn row<R: IntoIterator<Item = KeyboardButton>>(mut self, row: R) -> Self {
+        self.keyboard.push(row.into_iter().collect());
         self
     }
 }
@@ -45,3 +45,33 @@ fn test_serialize() {
     let j = serde_json::to_string(&markup).unwrap();
     assert_eq!(j, r#"{"remove_keyboard":true,"selective":true}"#);
 }
+
+#[test]
+fn test_keyboard_builder() {
+    let markup: ReplyMarkup = InlineKeyboardMarkup::default()
+        .row([
+            InlineKeyboardButton::with_url("Open", "https://example.com"),
+            InlineKeyboardButton::with_callback_data("Click", "data"),
+        ])
+        .row(vec![
+            InlineKeyboardButton::with_login_url(
+                "Login",
+                LoginUrl::new("https://example.com/login")
+                    .bot_username("bot")
+                    .request_write_access(true),
+            ),
+            InlineKeyboardButton::with_web_app("App", WebAppInfo::new("https://example.com/app")),
+        ])
+        .into();
+    let j = serde_json::to_string(&markup).unwrap();
+    assert_eq!(j, r#"{"inline_keyboard":[[{"text":"Open","url":"https://example.com"},{"text":"Click","callback_data":"data"}],[{"text":"Login","login_url":{"url":"https://example.com/login","bot_username":"bot","request_write_access":true}},{"text":"App","web_app":{"url":"https://example.com/app"}}]]}"#);
+
+    let markup: ReplyMarkup = ReplyKeyboardMarkup::default()
+        .row([KeyboardButton::new("Yes"), KeyboardButton::new("No")])
+        .resize_keyboard(true)
+        .one_time_keyboard(true)
+        .selective(true)
+        .into();
+    let j = serde_json::to_string(&markup).unwrap();
+    assert_eq!(j, r#"{"keyboard":[[{"text":"Yes"},{"text":"No"}]],"resize_keyboard":true,"one_time_keyboard":true,"selective":true}"#);
+}
@@ -0,0 +1,18 @@
+use serde::Serialize;
+
+/// Describes a Web App
+#[derive(Clone, Debug, Serialize)]
+pub struct WebAppInfo {
+    url: String,
+}
+
+impl WebAppInfo {
+    /// Creates a new WebAppInfo
+    ///
+    /// # Arguments
+    ///
+    /// * url - An HTTPS URL of a Web App to be opened
+    pub fn new<S: Into<String>>(url: S) -> Self {
+        WebAppInfo { url: url.into() }
+    }
+}
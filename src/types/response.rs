@@ -26,13 +26,13 @@ where
                     None => return Err(D::Error::missing_field(stringify!($name))),
                 }
             }};
-        };
+        }
         Ok(if raw.ok {
             Response::Success(required!(result))
         } else {
             Response::Error(ResponseError {
                 description: required!(description),
-                error_code: raw.error_code,
+                error_code: required!(error_code),
                 parameters: raw.parameters,
             })
         })
@@ -42,18 +42,32 @@ where
 /// Response error
 #[derive(Clone, Debug, failure::Fail)]
 #[fail(
-    display = "A telegram error has occurred: code={:?} message={}",
+    display = "A telegram error has occurred: code={} message={}",
     error_code, description
 )]
 pub struct ResponseError {
     /// Human-readable description
     pub description: String,
     /// Error code
-    pub error_code: Option<Integer>,
+    pub error_code: Integer,
     /// Parameters
     pub parameters: Option<ResponseParameters>,
 }
 
+impl ResponseError {
+    /// Returns an identifier of a supergroup when a group has been migrated
+    ///
+    /// Repeat the request with the new chat_id
+    pub fn migrate_to_chat_id(&self) -> Option<Integer> {
+        self.parameters.and_then(|parameters| parameters.migrate_to_chat_id)
+    }
+
+    /// Returns a number of seconds to wait before the request can be repeated
+    pub fn retry_after(&self) -> Option<Integer> {
+        self.parameters.and_then(|parameters| parameters.retry_after)
+    }
+}
+
 /// Contains information about why a request was unsuccessful
 #[derive(Clone, Copy, Debug, Deserialize)]
 pub struct ResponseParameters {
@@ -73,3 +87,38 @@ struct RawResponse<T> {
     result: Option<T>,
     parameters: Option<ResponseParameters>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deserialize_error() {
+        let response: Response<bool> = serde_json::from_str(
+            r#"{
+                "ok": false,
+                "error_code": 400,
+                "description": "Bad Request: group chat was upgraded to a supergroup chat",
+                "parameters": {"migrate_to_chat_id": -1001}
+            }"#,
+        )
+        .unwrap();
+        if let Response::Error(err) = response {
+            assert_eq!(err.error_code, 400);
+            assert_eq!(err.migrate_to_chat_id(), Some(-1001));
+            assert!(err.retry_after().is_none());
+        } else {
+            panic!("Unexpected response: {:?}", response);
+        }
+
+        let response: Response<bool> =
+            serde_json::from_str(r#"{"ok": false, "error_code": 403, "description": "Forbidden"}"#).unwrap();
+        if let Response::Error(err) = response {
+            assert_eq!(err.error_code, 403);
+            assert_eq!(err.description, "Forbidden");
+            assert!(err.migrate_to_chat_id().is_none());
+        } else {
+            panic!("Unexpected response: {:?}", response);
+        }
+    }
+}
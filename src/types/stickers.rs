@@ -45,6 +45,18 @@ pub struct MaskPosition {
     pub scale: Float,
 }
 
+/// Format of a sticker file
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StickerFormat {
+    /// PNG or WEBP image
+    Static,
+    /// TGS animation
+    Animated,
+    /// WEBM video
+    Video,
+}
+
 /// Sticker
 #[derive(Clone, Debug, Deserialize)]
 pub struct Sticker {
@@ -3,10 +3,13 @@ use crate::types::{
     inline_mode::{ChosenInlineResult, InlineQuery},
     message::Message,
     payments::{PreCheckoutQuery, ShippingQuery},
+    poll::{Poll, PollAnswer},
     primitive::Integer,
     user::User,
 };
 use serde::{de::Error, Deserialize, Deserializer, Serialize};
+use serde_json::Value as JsonValue;
+use std::collections::HashSet;
 
 /// Incoming update
 #[derive(Clone, Debug)]
@@ -58,13 +61,19 @@ impl Update {
             UpdateKind::CallbackQuery(ref query) => &query.from,
             UpdateKind::ShippingQuery(ref query) => &query.from,
             UpdateKind::PreCheckoutQuery(ref query) => &query.from,
+            UpdateKind::PollAnswer(ref answer) => &answer.user,
+            UpdateKind::Poll(_) | UpdateKind::Unknown(_) => return None,
         })
     }
 }
 
 /// Kind of update
+///
+/// New kinds of updates may be added in future releases,
+/// so a match on this enum must have a wildcard arm
 #[derive(Clone, Debug)]
 #[allow(clippy::large_enum_variant)]
+#[non_exhaustive]
 pub enum UpdateKind {
     /// New incoming message of any kind — text, photo, sticker, etc
     Message(Message),
@@ -87,6 +96,19 @@ pub enum UpdateKind {
     ShippingQuery(ShippingQuery),
     /// New incoming pre-checkout query. Contains full information about checkout
     PreCheckoutQuery(PreCheckoutQuery),
+    /// New poll state
+    ///
+    /// Bots receive only updates about stopped polls and polls, which are sent by the bot
+    Poll(Poll),
+    /// A user changed their answer in a non-anonymous poll
+    ///
+    /// Bots receive new votes only in polls that were sent by the bot itself
+    PollAnswer(PollAnswer),
+    /// Update of a kind which is not supported yet
+    ///
+    /// Contains raw JSON data of the update, so it can be inspected or skipped
+    /// Also used when data of a supported kind can not be parsed
+    Unknown(JsonValue),
 }
 
 impl<'de> Deserialize<'de> for Update {
@@ -94,7 +116,23 @@ impl<'de> Deserialize<'de> for Update {
     where
         D: Deserializer<'de>,
     {
-        let raw: RawUpdate = Deserialize::deserialize(deserializer)?;
+        let value = JsonValue::deserialize(deserializer)?;
+        let raw = match RawUpdate::deserialize(&value) {
+            Ok(raw) => raw,
+            Err(err) => {
+                // A known kind with unexpected data must not break the whole batch of updates,
+                // otherwise offset will never advance
+                let id = value
+                    .get("update_id")
+                    .and_then(JsonValue::as_i64)
+                    .ok_or_else(|| D::Error::custom(&err))?;
+                log::warn!("Failed to parse update {}: {}", id, err);
+                return Ok(Update {
+                    id,
+                    kind: UpdateKind::Unknown(value),
+                });
+            }
+        };
         Ok(Update {
             id: raw.update_id,
             kind: if let Some(data) = raw.message {
@@ -115,8 +153,12 @@ impl<'de> Deserialize<'de> for Update {
                 UpdateKind::ShippingQuery(data)
             } else if let Some(data) = raw.pre_checkout_query {
                 UpdateKind::PreCheckoutQuery(data)
+            } else if let Some(data) = raw.poll {
+                UpdateKind::Poll(data)
+            } else if let Some(data) = raw.poll_answer {
+                UpdateKind::PollAnswer(data)
             } else {
-                return Err(D::Error::custom("Can not detect update kind"));
+                UpdateKind::Unknown(value)
             },
         })
     }
@@ -143,7 +185,10 @@ pub struct WebhookInfo {
 }
 
 /// Type of update to receive
+///
+/// Follows UpdateKind, so it may get new variants as well
 #[derive(Debug, Deserialize, Eq, Clone, Copy, Hash, PartialEq, PartialOrd, Serialize)]
+#[non_exhaustive]
 pub enum AllowedUpdate {
     /// Message
     #[serde(rename = "message")]
@@ -172,6 +217,55 @@ pub enum AllowedUpdate {
     /// Pre checkout query
     #[serde(rename = "pre_checkout_query")]
     PreCheckoutQuery,
+    /// Poll
+    #[serde(rename = "poll")]
+    Poll,
+    /// Poll answer
+    #[serde(rename = "poll_answer")]
+    PollAnswer,
+    /// Bot's chat member status was updated in a chat
+    #[serde(rename = "my_chat_member")]
+    MyChatMember,
+    /// Chat member status was updated in a chat
+    ///
+    /// Must be requested explicitly, the bot must be an administrator in the chat
+    #[serde(rename = "chat_member")]
+    ChatMember,
+}
+
+impl AllowedUpdate {
+    /// Returns all types of updates except chat_member
+    ///
+    /// That's what Telegram sends when the list of allowed updates is empty
+    pub fn all() -> HashSet<AllowedUpdate> {
+        let mut updates = Self::all_including_chat_member();
+        updates.remove(&AllowedUpdate::ChatMember);
+        updates
+    }
+
+    /// Returns all types of updates including chat_member
+    ///
+    /// Note that chat_member updates are sent only if the bot is an administrator in the chat
+    pub fn all_including_chat_member() -> HashSet<AllowedUpdate> {
+        use self::AllowedUpdate::*;
+        vec![
+            Message,
+            EditedMessage,
+            ChannelPost,
+            EditedChannelPost,
+            InlineQuery,
+            ChosenInlineResult,
+            CallbackQuery,
+            ShippingQuery,
+            PreCheckoutQuery,
+            Poll,
+            PollAnswer,
+            MyChatMember,
+            ChatMember,
+        ]
+        .into_iter()
+        .collect()
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -186,11 +280,14 @@ struct RawUpdate {
     callback_query: Option<CallbackQuery>,
     shipping_query: Option<ShippingQuery>,
     pre_checkout_query: Option<PreCheckoutQuery>,
+    poll: Option<Poll>,
+    poll_answer: Option<PollAnswer>,
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::types::UserId;
 
     #[test]
     fn test_deserialize() {
@@ -214,7 +311,7 @@ mod tests {
         }"#;
         let update: Update = serde_json::from_str(input).unwrap();
         assert_eq!(update.get_chat_id(), Some(1));
-        assert_eq!(update.get_user().map(|u| u.id), Some(1));
+        assert_eq!(update.get_user().map(|u| u.id), Some(UserId(1)));
         if let Update {
             id,
             kind: UpdateKind::Message(msg),
@@ -226,4 +323,74 @@ mod tests {
             panic!("Unexpected update {:?}", update);
         }
     }
+
+    #[test]
+    fn test_allowed_update_all() {
+        let all = AllowedUpdate::all();
+        assert!(all.contains(&AllowedUpdate::Message));
+        assert!(all.contains(&AllowedUpdate::MyChatMember));
+        assert!(!all.contains(&AllowedUpdate::ChatMember));
+        let all = AllowedUpdate::all_including_chat_member();
+        assert_eq!(all.len(), 13);
+        assert!(all.contains(&AllowedUpdate::ChatMember));
+    }
+
+    #[test]
+    fn test_deserialize_unknown() {
+        let input = r#"{
+            "update_id": 2,
+            "some_new_update": {
+                "id": 1
+            }
+        }"#;
+        let update: Update = serde_json::from_str(input).unwrap();
+        assert_eq!(update.id, 2);
+        assert_eq!(update.get_chat_id(), None);
+        assert!(update.get_user().is_none());
+        if let UpdateKind::Unknown(data) = update.kind {
+            assert_eq!(data["some_new_update"]["id"], 1);
+        } else {
+            panic!("Unexpected update {:?}", update);
+        }
+    }
+
+    #[test]
+    fn test_deserialize_invalid() {
+        let input = r#"{
+            "update_id": 3,
+            "message": {
+                "message_id": "not a number"
+            }
+        }"#;
+        let update: Update = serde_json::from_str(input).unwrap();
+        assert_eq!(update.id, 3);
+        if let UpdateKind::Unknown(data) = update.kind {
+            assert_eq!(data["message"]["message_id"], "not a number");
+        } else {
+            panic!("Unexpected update {:?}", update);
+        }
+
+        let input = r#"{"message": {}}"#;
+        assert!(serde_json::from_str::<Update>(input).is_err());
+    }
+
+    #[test]
+    fn test_deserialize_poll_answer() {
+        let input = r#"{
+            "update_id": 4,
+            "poll_answer": {
+                "poll_id": "poll-id",
+                "user": {"id": 1, "is_bot": false, "first_name": "user"},
+                "option_ids": [1]
+            }
+        }"#;
+        let update: Update = serde_json::from_str(input).unwrap();
+        assert_eq!(update.get_user().map(|user| user.id), Some(UserId(1)));
+        if let UpdateKind::PollAnswer(answer) = update.kind {
+            assert_eq!(answer.poll_id, "poll-id");
+            assert_eq!(answer.option_ids, vec![1]);
+        } else {
+            panic!("Unexpected update {:?}", update);
+        }
+    }
 }
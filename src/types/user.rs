@@ -1,11 +1,12 @@
-use crate::types::{photo_size::PhotoSize, primitive::Integer};
-use serde::Deserialize;
+use crate::types::{chat::ChatId, photo_size::PhotoSize, primitive::Integer};
+use serde::{Deserialize, Serialize};
+use std::fmt;
 
 /// Telegram user or bot
 #[derive(Clone, Debug, Deserialize, PartialEq, PartialOrd)]
 pub struct User {
     /// Unique identifier for this user or bot
-    pub id: Integer,
+    pub id: UserId,
     /// True, if this user is a bot
     pub is_bot: bool,
     /// User‘s or bot’s first name
@@ -26,3 +27,52 @@ pub struct UserProfilePhotos {
     /// Requested profile pictures (in up to 4 sizes each)
     pub photos: Vec<Vec<PhotoSize>>,
 }
+
+impl UserProfilePhotos {
+    /// Returns the largest size of each profile picture
+    ///
+    /// Use file_id of a returned size to download the picture
+    pub fn largest(&self) -> impl Iterator<Item = &PhotoSize> {
+        self.photos
+            .iter()
+            .filter_map(|sizes| sizes.iter().max_by_key(|size| size.width * size.height))
+    }
+}
+
+/// Unique identifier of a user
+///
+/// A separate type prevents passing a message or chat identifier where a user is expected
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize)]
+#[serde(transparent)]
+pub struct UserId(pub Integer);
+
+impl fmt::Display for UserId {
+    fn fmt(&self, out: &mut fmt::Formatter) -> fmt::Result {
+        write!(out, "{}", self.0)
+    }
+}
+
+impl From<Integer> for UserId {
+    fn from(id: Integer) -> UserId {
+        UserId(id)
+    }
+}
+
+impl From<UserId> for Integer {
+    fn from(id: UserId) -> Integer {
+        id.0
+    }
+}
+
+impl PartialEq<Integer> for UserId {
+    fn eq(&self, other: &Integer) -> bool {
+        self.0 == *other
+    }
+}
+
+/// Identifier of a private chat with the user
+impl From<UserId> for ChatId {
+    fn from(id: UserId) -> ChatId {
+        ChatId::Id(id.0)
+    }
+}
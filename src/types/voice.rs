@@ -5,11 +5,11 @@ use serde::Deserialize;
 #[derive(Clone, Debug, Deserialize)]
 pub struct Voice {
     /// Unique identifier for this file
-    file_id: String,
+    pub file_id: String,
     /// Duration of the audio in seconds as defined by sender
-    duration: Integer,
+    pub duration: Integer,
     /// MIME type of the file as defined by sender
-    mime_type: Option<String>,
+    pub mime_type: Option<String>,
     /// File size
-    file_size: Option<Integer>,
+    pub file_size: Option<Integer>,
 }